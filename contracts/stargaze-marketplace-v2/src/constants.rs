@@ -3,3 +3,40 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // 100% represented as basis points
 pub const MAX_BASIS_POINTS: u64 = 10_000;
+
+// Limits for the number of expired orders removed in a single transaction
+pub const DEFAULT_REMOVAL_LIMIT: u32 = 10;
+pub const MAX_REMOVAL_LIMIT: u32 = 50;
+
+// The maximum number of messages in a single batch
+pub const MAX_BATCH_SIZE: usize = 100;
+
+// The maximum number of asks bought in a single sweep
+pub const MAX_SWEEP_ITEMS: u32 = 50;
+
+// The number of sales kept in the sale history of a token and of a collection
+pub const MAX_TOKEN_SALE_HISTORY: usize = 10;
+pub const MAX_COLLECTION_SALE_HISTORY: u64 = 100;
+
+// The number of sale prices kept per collection and denom for the price oracle
+pub const MAX_PRICE_OBSERVATIONS: usize = 50;
+
+// Defaults of the auction config for configs stored before it was introduced
+pub const DEFAULT_AUCTION_MIN_BID_INCREMENT_BPS: u64 = 500;
+pub const DEFAULT_AUCTION_EXTENSION_SECONDS: u64 = 600;
+// Limits for the duration of an auction
+pub const MIN_AUCTION_DURATION: u64 = 600;
+pub const MAX_AUCTION_DURATION: u64 = 30 * 24 * 60 * 60;
+
+// Limits for the number of NFTs in a bundle
+pub const MIN_BUNDLE_ITEMS: usize = 2;
+pub const MAX_BUNDLE_ITEMS: usize = 25;
+
+// The maximum number of NFTs on each side of a swap
+pub const MAX_SWAP_ITEMS: usize = 25;
+
+// The maximum number of buyers an ask can be reserved for
+pub const MAX_RESERVED_BUYERS: usize = 20;
+
+// The maximum number of recipients of a collection royalty
+pub const MAX_ROYALTY_RECIPIENTS: usize = 10;
@@ -1,5 +1,6 @@
 use cosmwasm_std::{OverflowError, StdError};
 use cw_utils::PaymentError;
+use sg_controllers::HookError;
 use sg_marketplace_common::MarketplaceStdError;
 use thiserror::Error;
 
@@ -17,6 +18,9 @@ pub enum ContractError {
     #[error("{0}")]
     MarketplaceStdError(#[from] MarketplaceStdError),
 
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
     #[error("No match found")]
     NoMatchFound,
 
@@ -26,6 +30,9 @@ pub enum ContractError {
     #[error("InsufficientFunds")]
     InsufficientFunds,
 
+    #[error("Paused: {0} are paused")]
+    Paused(String),
+
     #[error("InternalError: {0}")]
     InternalError(String),
 }
@@ -1,9 +1,9 @@
 use crate::{
-    orders::{Ask, Bid, CollectionBid},
-    state::Config,
+    orders::{Ask, Auction, Bid, Bundle, CollectionBid, CounterOffer, Swap, TraitBid},
+    state::{CollectionRoyalty, CollectionStatus, Config, FeeOverride, PauseState, RoyaltyPolicy},
 };
 
-use cosmwasm_std::{attr, Addr, Event, Uint128};
+use cosmwasm_std::{attr, Addr, Coin, Event, Uint128};
 use std::vec;
 
 pub struct ConfigEvent<'a> {
@@ -24,6 +24,34 @@ impl<'a> From<ConfigEvent<'a>> for Event {
             attr("maker_reward_bps", ce.config.maker_reward_bps.to_string()),
             attr("taker_reward_bps", ce.config.taker_reward_bps.to_string()),
             attr("default_denom", ce.config.default_denom.to_string()),
+            attr(
+                "removal_reward_bps",
+                ce.config.removal_reward_bps.to_string(),
+            ),
+            attr(
+                "registered_finders_only",
+                ce.config.registered_finders_only.to_string(),
+            ),
+            attr(
+                "protocol_payout_mode",
+                ce.config.payout_modes.protocol.to_string(),
+            ),
+            attr(
+                "royalty_payout_mode",
+                ce.config.payout_modes.royalty.to_string(),
+            ),
+            attr(
+                "reward_payout_mode",
+                ce.config.payout_modes.reward.to_string(),
+            ),
+            attr(
+                "auction_min_bid_increment_bps",
+                ce.config.auction_min_bid_increment_bps.to_string(),
+            ),
+            attr(
+                "auction_extension_seconds",
+                ce.config.auction_extension_seconds.to_string(),
+            ),
         ])
     }
 }
@@ -43,6 +71,139 @@ impl<'a> From<CollectionDenomEvent<'a>> for Event {
     }
 }
 
+pub struct FeeOverrideEvent<'a> {
+    pub ty: &'a str,
+    pub collection: &'a str,
+    pub fee_override: Option<&'a FeeOverride>,
+}
+
+impl<'a> From<FeeOverrideEvent<'a>> for Event {
+    fn from(foe: FeeOverrideEvent) -> Self {
+        let mut event =
+            Event::new(foe.ty.to_string()).add_attribute("collection", foe.collection.to_string());
+        if let Some(fee_override) = foe.fee_override {
+            event = event.add_attributes(vec![
+                attr(
+                    "protocol_fee_bps",
+                    fee_override.protocol_fee_bps.to_string(),
+                ),
+                attr(
+                    "maker_reward_bps",
+                    fee_override.maker_reward_bps.to_string(),
+                ),
+                attr(
+                    "taker_reward_bps",
+                    fee_override.taker_reward_bps.to_string(),
+                ),
+            ]);
+            if let Some(start_time) = fee_override.start_time {
+                event = event.add_attribute("start_time", start_time.to_string());
+            }
+            if let Some(end_time) = fee_override.end_time {
+                event = event.add_attribute("end_time", end_time.to_string());
+            }
+        }
+        event
+    }
+}
+
+pub struct RoyaltyPolicyEvent<'a> {
+    pub ty: &'a str,
+    pub collection: &'a str,
+    pub royalty_policy: Option<&'a RoyaltyPolicy>,
+}
+
+impl<'a> From<RoyaltyPolicyEvent<'a>> for Event {
+    fn from(rpe: RoyaltyPolicyEvent) -> Self {
+        let mut event =
+            Event::new(rpe.ty.to_string()).add_attribute("collection", rpe.collection.to_string());
+        if let Some(royalty_policy) = rpe.royalty_policy {
+            event = event.add_attribute("royalty_policy", royalty_policy.to_string());
+        }
+        event
+    }
+}
+
+pub struct CollectionRoyaltyEvent<'a> {
+    pub ty: &'a str,
+    pub collection: &'a str,
+    pub collection_royalty: Option<&'a CollectionRoyalty<Addr>>,
+}
+
+impl<'a> From<CollectionRoyaltyEvent<'a>> for Event {
+    fn from(cre: CollectionRoyaltyEvent) -> Self {
+        let mut event =
+            Event::new(cre.ty.to_string()).add_attribute("collection", cre.collection.to_string());
+        if let Some(collection_royalty) = cre.collection_royalty {
+            event = event.add_attribute("share", collection_royalty.share.to_string());
+            for (recipient, weight) in &collection_royalty.recipients {
+                event = event.add_attributes(vec![
+                    attr("recipient", recipient.to_string()),
+                    attr("weight", weight.to_string()),
+                ]);
+            }
+        }
+        event
+    }
+}
+
+pub struct FinderEvent<'a> {
+    pub ty: &'a str,
+    pub finder: &'a str,
+    pub reward_bps: Option<u64>,
+}
+
+impl<'a> From<FinderEvent<'a>> for Event {
+    fn from(fe: FinderEvent) -> Self {
+        let mut event =
+            Event::new(fe.ty.to_string()).add_attribute("finder", fe.finder.to_string());
+        if let Some(reward_bps) = fe.reward_bps {
+            event = event.add_attribute("reward_bps", reward_bps.to_string());
+        }
+        event
+    }
+}
+
+pub struct CollectionStatusEvent<'a> {
+    pub ty: &'a str,
+    pub collection: &'a str,
+    pub status: &'a CollectionStatus,
+}
+
+impl<'a> From<CollectionStatusEvent<'a>> for Event {
+    fn from(cse: CollectionStatusEvent) -> Self {
+        let status = match cse.status {
+            CollectionStatus::Allowed => "allowed",
+            CollectionStatus::Blocked => "blocked",
+            CollectionStatus::Delisted => "delisted",
+        };
+        Event::new(cse.ty.to_string()).add_attributes(vec![
+            attr("collection", cse.collection.to_string()),
+            attr("status", status.to_string()),
+        ])
+    }
+}
+
+pub struct PauseStateEvent<'a> {
+    pub ty: &'a str,
+    pub collection: Option<&'a str>,
+    pub pause_state: &'a PauseState,
+}
+
+impl<'a> From<PauseStateEvent<'a>> for Event {
+    fn from(pse: PauseStateEvent) -> Self {
+        let mut event = Event::new(pse.ty.to_string());
+        if let Some(collection) = pse.collection {
+            event = event.add_attribute("collection", collection.to_string());
+        }
+        event.add_attributes(vec![
+            attr("orders", pse.pause_state.orders.to_string()),
+            attr("sales", pse.pause_state.sales.to_string()),
+            attr("withdrawals", pse.pause_state.withdrawals.to_string()),
+        ])
+    }
+}
+
 pub struct ListingFeeEvent<'a> {
     pub ty: &'a str,
     pub denom: &'a str,
@@ -60,6 +221,139 @@ impl<'a> From<ListingFeeEvent<'a>> for Event {
     }
 }
 
+pub struct HookEvent<'a> {
+    pub ty: &'a str,
+    pub hook_type: &'a str,
+    pub hook: &'a str,
+}
+
+impl<'a> From<HookEvent<'a>> for Event {
+    fn from(he: HookEvent) -> Self {
+        Event::new(he.ty.to_string()).add_attributes(vec![
+            attr("hook_type", he.hook_type.to_string()),
+            attr("hook", he.hook.to_string()),
+        ])
+    }
+}
+
+pub struct BatchEvent<'a> {
+    pub ty: &'a str,
+    pub num_msgs: usize,
+    pub num_failed: usize,
+}
+
+impl<'a> From<BatchEvent<'a>> for Event {
+    fn from(be: BatchEvent) -> Self {
+        Event::new(be.ty.to_string()).add_attributes(vec![
+            attr("num_msgs", be.num_msgs.to_string()),
+            attr("num_failed", be.num_failed.to_string()),
+        ])
+    }
+}
+
+pub struct BatchFailureEvent<'a> {
+    pub ty: &'a str,
+    pub index: usize,
+    pub error: &'a str,
+}
+
+impl<'a> From<BatchFailureEvent<'a>> for Event {
+    fn from(bfe: BatchFailureEvent) -> Self {
+        Event::new(bfe.ty.to_string()).add_attributes(vec![
+            attr("index", bfe.index.to_string()),
+            attr("error", bfe.error.to_string()),
+        ])
+    }
+}
+
+pub struct SweepEvent<'a> {
+    pub ty: &'a str,
+    pub collection: &'a str,
+    pub num_items: u32,
+    pub total: &'a Coin,
+}
+
+impl<'a> From<SweepEvent<'a>> for Event {
+    fn from(se: SweepEvent) -> Self {
+        Event::new(se.ty.to_string()).add_attributes(vec![
+            attr("collection", se.collection.to_string()),
+            attr("num_items", se.num_items.to_string()),
+            attr("denom", se.total.denom.to_string()),
+            attr("total", se.total.amount.to_string()),
+        ])
+    }
+}
+
+pub struct ClaimEvent<'a> {
+    pub ty: &'a str,
+    pub claimant: &'a str,
+    pub funds: &'a [Coin],
+}
+
+impl<'a> From<ClaimEvent<'a>> for Event {
+    fn from(ce: ClaimEvent) -> Self {
+        let funds = ce
+            .funds
+            .iter()
+            .map(|funds| funds.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        Event::new(ce.ty.to_string()).add_attributes(vec![
+            attr("claimant", ce.claimant.to_string()),
+            attr("funds", funds),
+        ])
+    }
+}
+
+pub struct AuctionEvent<'a> {
+    pub ty: &'a str,
+    pub auction: &'a Auction,
+    pub attr_keys: Vec<&'a str>,
+}
+
+impl<'a> From<AuctionEvent<'a>> for Event {
+    fn from(ae: AuctionEvent) -> Self {
+        Event::new(ae.ty.to_string()).add_attributes(ae.auction.get_event_attrs(ae.attr_keys))
+    }
+}
+
+pub struct BundleEvent<'a> {
+    pub ty: &'a str,
+    pub bundle: &'a Bundle,
+    pub attr_keys: Vec<&'a str>,
+}
+
+impl<'a> From<BundleEvent<'a>> for Event {
+    fn from(be: BundleEvent) -> Self {
+        Event::new(be.ty.to_string()).add_attributes(be.bundle.get_event_attrs(be.attr_keys))
+    }
+}
+
+pub struct SwapEvent<'a> {
+    pub ty: &'a str,
+    pub swap: &'a Swap,
+    pub attr_keys: Vec<&'a str>,
+}
+
+impl<'a> From<SwapEvent<'a>> for Event {
+    fn from(se: SwapEvent) -> Self {
+        Event::new(se.ty.to_string()).add_attributes(se.swap.get_event_attrs(se.attr_keys))
+    }
+}
+
+pub struct CounterOfferEvent<'a> {
+    pub ty: &'a str,
+    pub counter_offer: &'a CounterOffer,
+    pub attr_keys: Vec<&'a str>,
+}
+
+impl<'a> From<CounterOfferEvent<'a>> for Event {
+    fn from(coe: CounterOfferEvent) -> Self {
+        Event::new(coe.ty.to_string())
+            .add_attributes(coe.counter_offer.get_event_attrs(coe.attr_keys))
+    }
+}
+
 pub struct AskEvent<'a> {
     pub ty: &'a str,
     pub ask: &'a Ask,
@@ -96,3 +390,15 @@ impl<'a> From<CollectionBidEvent<'a>> for Event {
             .add_attributes(coe.collection_bid.get_event_attrs(coe.attr_keys))
     }
 }
+
+pub struct TraitBidEvent<'a> {
+    pub ty: &'a str,
+    pub trait_bid: &'a TraitBid,
+    pub attr_keys: Vec<&'a str>,
+}
+
+impl<'a> From<TraitBidEvent<'a>> for Event {
+    fn from(tbe: TraitBidEvent) -> Self {
+        Event::new(tbe.ty.to_string()).add_attributes(tbe.trait_bid.get_event_attrs(tbe.attr_keys))
+    }
+}
@@ -1,24 +1,56 @@
-use cosmwasm_std::{ensure, ensure_eq, has_coins, Addr, Coin, DepsMut, Env, MessageInfo, Response};
-use cw_utils::{nonpayable, one_coin, NativeBalance};
+use cosmwasm_std::{
+    coin, ensure, ensure_eq, from_json, has_coins, Addr, Api, Coin, Decimal, Deps, DepsMut, Env,
+    Event, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{maybe_addr, nonpayable, one_coin, NativeBalance};
+use sg_controllers::Hooks;
 use sg_marketplace_common::{
-    coin::{transfer_coin, transfer_coins},
+    address::address_or,
+    asset::{is_cw20_denom, transfer_asset, transfer_assets, Asset},
     nft::{only_owner, only_tradable, transfer_nft},
     MarketplaceStdError,
 };
 use std::ops::{Add, Sub};
 
 use crate::{
+    constants::{
+        DEFAULT_REMOVAL_LIMIT, MAX_BASIS_POINTS, MAX_BATCH_SIZE, MAX_REMOVAL_LIMIT, MAX_SWEEP_ITEMS,
+    },
     error::ContractError,
     events::{
-        AskEvent, BidEvent, CollectionBidEvent, CollectionDenomEvent, ConfigEvent, ListingFeeEvent,
+        AskEvent, AuctionEvent, BatchEvent, BatchFailureEvent, BidEvent, BundleEvent, ClaimEvent,
+        CollectionBidEvent, CollectionDenomEvent, CollectionRoyaltyEvent, CollectionStatusEvent,
+        ConfigEvent, CounterOfferEvent, FeeOverrideEvent, FinderEvent, HookEvent, ListingFeeEvent,
+        PauseStateEvent, RoyaltyPolicyEvent, SwapEvent, SweepEvent, TraitBidEvent,
+    },
+    helpers::{
+        fetch_ask_custody, finalize_bundle_sale, finalize_sale, finalize_swap, generate_id,
+        is_paused, only_collection_admin, only_contract_admin, only_listed_collection, only_trait,
+        only_unpaused, only_valid_auction_end_time, only_valid_bundle_items,
+        only_valid_dutch_auction, only_valid_expiration, only_valid_price, only_valid_quantity,
+        only_valid_reserved_for, only_valid_swap, only_without_royalty_tip,
+    },
+    hooks::{
+        prepare_ask_hook, prepare_bid_hook, prepare_collection_bid_hook, prepare_trait_bid_hook,
+    },
+    msg::{BatchMsg, ExecuteMsg, HookAction, ReceiveMsg, ReceiveNftMsg},
+    orders::{
+        cheapest_asks, Ask, Auction, AuctionBid, Bid, Bundle, BundleItem, CollectionBid,
+        CounterOffer, DutchAuction, MatchingBid, OrderDetails, Swap, SwapNft, SwapRequest,
+        TraitBid,
     },
-    helpers::{finalize_sale, generate_id, only_contract_admin, only_valid_price},
-    msg::ExecuteMsg,
-    orders::{Ask, Bid, CollectionBid, MatchingBid, OrderDetails},
     state::{
-        asks, bids, collection_bids, Config, Denom, OrderId, TokenId, COLLECTION_DENOMS, CONFIG,
-        LISTING_FEES, NONCE,
+        asks, auctions, bids, bundles, collection_bids, swaps, trait_bids, CollectionRoyalty,
+        CollectionStatus, Config, Denom, FeeOverride, OrderId, PausableOperation, PauseState,
+        RoyaltyPolicy, TokenId, ASK_HOOKS, BID_HOOKS, CLAIMABLE_BALANCES, COLLECTION_BID_HOOKS,
+        COLLECTION_DENOMS, COLLECTION_FEE_OVERRIDES, COLLECTION_PAUSE_STATES, COLLECTION_ROYALTIES,
+        COLLECTION_ROYALTY_POLICIES, COLLECTION_STATUSES, CONFIG, COUNTER_OFFERS, FINDERS,
+        LISTING_FEES, NONCE, PAUSE_STATE, SALE_HOOKS, TRAIT_BID_HOOKS,
     },
+    transaction::{commit, StorageTransaction},
 };
 
 #[cfg(not(feature = "library"))]
@@ -33,6 +65,12 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let api = deps.api;
 
+    // cw20 tokens are only received through `Receive`, native funds cannot pose as one
+    ensure!(
+        info.funds.iter().all(|fund| !is_cw20_denom(&fund.denom)),
+        ContractError::InvalidInput("native denoms cannot use the cw20 prefix".to_string())
+    );
+
     match msg {
         ExecuteMsg::UpdateConfig { config } => {
             execute_update_config(deps, env, info, config.str_to_addr(api)?)
@@ -40,14 +78,165 @@ pub fn execute(
         ExecuteMsg::UpdateCollectionDenom { collection, denom } => {
             execute_update_collection_denom(deps, env, info, api.addr_validate(&collection)?, denom)
         }
+        ExecuteMsg::SetCollectionFeeOverride {
+            collection,
+            fee_override,
+        } => execute_set_collection_fee_override(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            fee_override,
+        ),
+        ExecuteMsg::RemoveCollectionFeeOverride { collection } => {
+            execute_remove_collection_fee_override(deps, env, info, api.addr_validate(&collection)?)
+        }
+        ExecuteMsg::SetCollectionRoyaltyPolicy {
+            collection,
+            royalty_policy,
+        } => execute_set_collection_royalty_policy(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            royalty_policy,
+        ),
+        ExecuteMsg::RemoveCollectionRoyaltyPolicy { collection } => {
+            execute_remove_collection_royalty_policy(
+                deps,
+                env,
+                info,
+                api.addr_validate(&collection)?,
+            )
+        }
+        ExecuteMsg::SetCollectionRoyalty {
+            collection,
+            collection_royalty,
+        } => execute_set_collection_royalty(
+            deps,
+            info,
+            api.addr_validate(&collection)?,
+            collection_royalty.str_to_addr(api)?,
+        ),
+        ExecuteMsg::RemoveCollectionRoyalty { collection } => {
+            execute_remove_collection_royalty(deps, info, api.addr_validate(&collection)?)
+        }
+        ExecuteMsg::SetFinder { finder, reward_bps } => {
+            execute_set_finder(deps, env, info, api.addr_validate(&finder)?, reward_bps)
+        }
+        ExecuteMsg::RemoveFinder { finder } => {
+            execute_remove_finder(deps, env, info, api.addr_validate(&finder)?)
+        }
         ExecuteMsg::SetListingFee { fee } => execute_set_listing_fee(deps, env, info, fee),
+        ExecuteMsg::UpdateCollectionStatus { collection, status } => {
+            execute_update_collection_status(
+                deps,
+                env,
+                info,
+                api.addr_validate(&collection)?,
+                status,
+            )
+        }
+        ExecuteMsg::CancelDelistedOrders {
+            collection,
+            asks,
+            bids,
+            collection_bids,
+            trait_bids,
+            auctions,
+            bundles,
+            swaps,
+            counter_offers,
+        } => execute_cancel_delisted_orders(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            DelistedOrderIds {
+                asks,
+                bids,
+                collection_bids,
+                trait_bids,
+                auctions,
+                bundles,
+                swaps,
+                counter_offers,
+            },
+        ),
+        ExecuteMsg::UpdatePauseState {
+            collection,
+            pause_state,
+        } => execute_update_pause_state(deps, env, info, maybe_addr(api, collection)?, pause_state),
         ExecuteMsg::RemoveListingFee { denom } => {
             execute_remove_listing_fee(deps, env, info, denom)
         }
+        ExecuteMsg::AddAskHook { hook } => {
+            execute_add_hook(deps, env, info, ASK_HOOKS, "ask", api.addr_validate(&hook)?)
+        }
+        ExecuteMsg::RemoveAskHook { hook } => {
+            execute_remove_hook(deps, env, info, ASK_HOOKS, "ask", api.addr_validate(&hook)?)
+        }
+        ExecuteMsg::AddBidHook { hook } => {
+            execute_add_hook(deps, env, info, BID_HOOKS, "bid", api.addr_validate(&hook)?)
+        }
+        ExecuteMsg::RemoveBidHook { hook } => {
+            execute_remove_hook(deps, env, info, BID_HOOKS, "bid", api.addr_validate(&hook)?)
+        }
+        ExecuteMsg::AddCollectionBidHook { hook } => execute_add_hook(
+            deps,
+            env,
+            info,
+            COLLECTION_BID_HOOKS,
+            "collection-bid",
+            api.addr_validate(&hook)?,
+        ),
+        ExecuteMsg::RemoveCollectionBidHook { hook } => execute_remove_hook(
+            deps,
+            env,
+            info,
+            COLLECTION_BID_HOOKS,
+            "collection-bid",
+            api.addr_validate(&hook)?,
+        ),
+        ExecuteMsg::AddTraitBidHook { hook } => execute_add_hook(
+            deps,
+            env,
+            info,
+            TRAIT_BID_HOOKS,
+            "trait-bid",
+            api.addr_validate(&hook)?,
+        ),
+        ExecuteMsg::RemoveTraitBidHook { hook } => execute_remove_hook(
+            deps,
+            env,
+            info,
+            TRAIT_BID_HOOKS,
+            "trait-bid",
+            api.addr_validate(&hook)?,
+        ),
+        ExecuteMsg::AddSaleHook { hook } => execute_add_hook(
+            deps,
+            env,
+            info,
+            SALE_HOOKS,
+            "sale",
+            api.addr_validate(&hook)?,
+        ),
+        ExecuteMsg::RemoveSaleHook { hook } => execute_remove_hook(
+            deps,
+            env,
+            info,
+            SALE_HOOKS,
+            "sale",
+            api.addr_validate(&hook)?,
+        ),
         ExecuteMsg::SetAsk {
             collection,
             token_id,
             details,
+            non_custodial,
+            reserved_for,
+            dutch_auction,
         } => execute_set_ask(
             deps,
             env,
@@ -55,12 +244,16 @@ pub fn execute(
             api.addr_validate(&collection)?,
             token_id,
             details.str_to_addr(api)?,
+            non_custodial.unwrap_or(false),
+            validate_addrs(api, reserved_for.unwrap_or_default())?,
+            dutch_auction,
             false,
         ),
         ExecuteMsg::UpdateAsk { id, details } => {
             execute_update_ask(deps, env, info, id, details.str_to_addr(api)?)
         }
         ExecuteMsg::RemoveAsk { id } => execute_remove_ask(deps, env, info, id),
+        ExecuteMsg::SyncAsk { id } => execute_sync_ask(deps, env, info, id),
         ExecuteMsg::AcceptAsk { id, details } => {
             execute_accept_ask(deps, env, info, id, details.str_to_addr(api)?)
         }
@@ -84,19 +277,36 @@ pub fn execute(
         ExecuteMsg::AcceptBid { id, details } => {
             execute_accept_bid(deps, env, info, id, details.str_to_addr(api)?)
         }
+        ExecuteMsg::SetCounterOffer {
+            bid_id,
+            price,
+            expires,
+        } => execute_set_counter_offer(deps, env, info, bid_id, price, expires),
+        ExecuteMsg::RemoveCounterOffer { bid_id } => {
+            execute_remove_counter_offer(deps, env, info, bid_id)
+        }
+        ExecuteMsg::AcceptCounterOffer { bid_id } => {
+            execute_accept_counter_offer(deps, env, info, bid_id)
+        }
         ExecuteMsg::SetCollectionBid {
             collection,
             details,
+            quantity,
         } => execute_set_collection_bid(
             deps,
             env,
             info,
             api.addr_validate(&collection)?,
             details.str_to_addr(api)?,
+            quantity.unwrap_or(1),
             false,
         ),
-        ExecuteMsg::UpdateCollectionBid { id, details } => {
-            execute_update_collection_bid(deps, env, info, id, details.str_to_addr(api)?)
+        ExecuteMsg::UpdateCollectionBid {
+            id,
+            details,
+            quantity,
+        } => {
+            execute_update_collection_bid(deps, env, info, id, details.str_to_addr(api)?, quantity)
         }
         ExecuteMsg::RemoveCollectionBid { id } => {
             execute_remove_collection_bid(deps, env, info, id)
@@ -108,6 +318,29 @@ pub fn execute(
         } => {
             execute_accept_collection_bid(deps, env, info, id, token_id, details.str_to_addr(api)?)
         }
+        ExecuteMsg::SetTraitBid {
+            collection,
+            trait_type,
+            trait_value,
+            details,
+        } => execute_set_trait_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            trait_type,
+            trait_value,
+            details.str_to_addr(api)?,
+        ),
+        ExecuteMsg::UpdateTraitBid { id, details } => {
+            execute_update_trait_bid(deps, env, info, id, details.str_to_addr(api)?)
+        }
+        ExecuteMsg::RemoveTraitBid { id } => execute_remove_trait_bid(deps, env, info, id),
+        ExecuteMsg::AcceptTraitBid {
+            id,
+            token_id,
+            details,
+        } => execute_accept_trait_bid(deps, env, info, id, token_id, details.str_to_addr(api)?),
         ExecuteMsg::SellNft {
             collection,
             token_id,
@@ -119,6 +352,9 @@ pub fn execute(
             api.addr_validate(&collection)?,
             token_id,
             details.str_to_addr(api)?,
+            false,
+            vec![],
+            None,
             true,
         ),
         ExecuteMsg::BuySpecificNft {
@@ -143,8 +379,109 @@ pub fn execute(
             info,
             api.addr_validate(&collection)?,
             details.str_to_addr(api)?,
+            1,
             true,
         ),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::ReceiveNft(msg) => execute_receive_nft(deps, env, info, msg),
+        ExecuteMsg::Batch { msgs, best_effort } => {
+            execute_batch(deps, env, info, msgs, best_effort.unwrap_or(false))
+        }
+        ExecuteMsg::SweepCollection {
+            collection,
+            max_items,
+            max_price_per_item,
+            max_total,
+        } => execute_sweep_collection(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            max_items,
+            max_price_per_item,
+            max_total,
+        ),
+        ExecuteMsg::SetAuction {
+            collection,
+            token_id,
+            reserve_price,
+            end_time,
+            recipient,
+        } => execute_set_auction(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            reserve_price,
+            end_time,
+            maybe_addr(api, recipient)?,
+        ),
+        ExecuteMsg::RemoveAuction { id } => execute_remove_auction(deps, env, info, id),
+        ExecuteMsg::BidAuction { id, finder } => {
+            execute_bid_auction(deps, env, info, id, maybe_addr(api, finder)?)
+        }
+        ExecuteMsg::SettleAuction { id } => execute_settle_auction(deps, env, info, id),
+        ExecuteMsg::SetBundle { items, details } => execute_set_bundle(
+            deps,
+            env,
+            info,
+            items
+                .into_iter()
+                .map(|item| item.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            details.str_to_addr(api)?,
+        ),
+        ExecuteMsg::RemoveBundle { id } => execute_remove_bundle(deps, env, info, id),
+        ExecuteMsg::AcceptBundle { id, details } => {
+            execute_accept_bundle(deps, env, info, id, details.str_to_addr(api)?)
+        }
+        ExecuteMsg::SetSwap {
+            offered_nfts,
+            requested_nfts,
+            requested_funds,
+            recipient,
+            expires,
+        } => execute_set_swap(
+            deps,
+            env,
+            info,
+            offered_nfts
+                .into_iter()
+                .map(|nft| nft.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            requested_nfts
+                .into_iter()
+                .map(|request| request.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            requested_funds,
+            maybe_addr(api, recipient)?,
+            expires,
+        ),
+        ExecuteMsg::RemoveSwap { id } => execute_remove_swap(deps, env, info, id),
+        ExecuteMsg::AcceptSwap {
+            id,
+            nfts,
+            recipient,
+        } => execute_accept_swap(
+            deps,
+            env,
+            info,
+            id,
+            nfts.into_iter()
+                .map(|nft| nft.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            maybe_addr(api, recipient)?,
+        ),
+        ExecuteMsg::Claim { denoms } => execute_claim(deps, info, denoms),
+        ExecuteMsg::RemoveExpiredOrders { limit } => execute_remove_expired_orders(
+            deps,
+            env,
+            info,
+            limit
+                .unwrap_or(DEFAULT_REMOVAL_LIMIT)
+                .min(MAX_REMOVAL_LIMIT),
+        ),
     }
 }
 
@@ -173,17 +510,28 @@ pub fn execute_update_collection_denom(
     env: Env,
     info: MessageInfo,
     collection: Addr,
-    denom: Denom,
+    denom: Asset,
 ) -> Result<Response, ContractError> {
     only_contract_admin(&deps.querier, &env, &info)?;
 
+    let denom = match denom {
+        Asset::Native(denom) => {
+            ensure!(
+                !is_cw20_denom(&denom),
+                ContractError::InvalidInput("native denoms cannot use the cw20 prefix".to_string())
+            );
+            Asset::Native(denom)
+        }
+        Asset::Cw20(contract) => Asset::Cw20(deps.api.addr_validate(contract.as_str())?),
+    };
+
     COLLECTION_DENOMS.save(deps.storage, collection.clone(), &denom)?;
 
     let response = Response::new().add_event(
         CollectionDenomEvent {
             ty: "set-collection-denom",
             collection: collection.as_ref(),
-            denom: &denom,
+            denom: &denom.denom(),
         }
         .into(),
     );
@@ -191,21 +539,22 @@ pub fn execute_update_collection_denom(
     Ok(response)
 }
 
-pub fn execute_set_listing_fee(
+pub fn execute_update_collection_status(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    fee: Coin,
+    collection: Addr,
+    status: CollectionStatus,
 ) -> Result<Response, ContractError> {
     only_contract_admin(&deps.querier, &env, &info)?;
 
-    LISTING_FEES.save(deps.storage, fee.denom.clone(), &fee.amount)?;
+    COLLECTION_STATUSES.save(deps.storage, collection.clone(), &status)?;
 
     let response = Response::new().add_event(
-        ListingFeeEvent {
-            ty: "set-listing-fee",
-            denom: &fee.denom,
-            amount: &Some(fee.amount),
+        CollectionStatusEvent {
+            ty: "set-collection-status",
+            collection: collection.as_ref(),
+            status: &status,
         }
         .into(),
     );
@@ -213,222 +562,390 @@ pub fn execute_set_listing_fee(
     Ok(response)
 }
 
-pub fn execute_remove_listing_fee(
+/// The ids of the orders of a delisted collection to cancel
+pub struct DelistedOrderIds {
+    pub asks: Vec<OrderId>,
+    pub bids: Vec<OrderId>,
+    pub collection_bids: Vec<OrderId>,
+    pub trait_bids: Vec<OrderId>,
+    pub auctions: Vec<OrderId>,
+    pub bundles: Vec<OrderId>,
+    pub swaps: Vec<OrderId>,
+    /// The ids of the bids the counter-offers counter
+    pub counter_offers: Vec<OrderId>,
+}
+
+pub fn execute_cancel_delisted_orders(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    denom: Denom,
+    collection: Addr,
+    order_ids: DelistedOrderIds,
 ) -> Result<Response, ContractError> {
     only_contract_admin(&deps.querier, &env, &info)?;
 
-    LISTING_FEES.remove(deps.storage, denom.clone());
+    ensure!(
+        COLLECTION_STATUSES.may_load(deps.storage, collection.clone())?
+            == Some(CollectionStatus::Delisted),
+        ContractError::InvalidInput("collection is not delisted".to_string())
+    );
 
-    let response = Response::new().add_event(
-        ListingFeeEvent {
-            ty: "remove-listing-fee",
-            denom: &denom,
-            amount: &None,
-        }
-        .into(),
+    let DelistedOrderIds {
+        asks: ask_ids,
+        bids: bid_ids,
+        collection_bids: collection_bid_ids,
+        trait_bids: trait_bid_ids,
+        auctions: auction_ids,
+        bundles: bundle_ids,
+        swaps: swap_ids,
+        counter_offers: counter_offer_ids,
+    } = order_ids;
+
+    let num_orders = ask_ids.len()
+        + bid_ids.len()
+        + collection_bid_ids.len()
+        + trait_bid_ids.len()
+        + auction_ids.len()
+        + bundle_ids.len()
+        + swap_ids.len()
+        + counter_offer_ids.len();
+    ensure!(
+        num_orders > 0 && num_orders <= MAX_REMOVAL_LIMIT as usize,
+        ContractError::InvalidInput(format!(
+            "number of orders must be between 1 and {}",
+            MAX_REMOVAL_LIMIT
+        ))
     );
 
-    Ok(response)
-}
+    let wrong_collection =
+        |id: &OrderId| ContractError::InvalidInput(format!("order not in collection [{}]", id));
 
-pub fn execute_set_ask(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    collection: Addr,
-    token_id: TokenId,
-    details: OrderDetails<Addr>,
-    sell_now: bool,
-) -> Result<Response, ContractError> {
-    only_owner(&deps.querier, &info, &collection, &token_id)?;
-    only_tradable(&deps.querier, &env.block, &collection)?;
+    let mut response = Response::new();
 
-    let config = CONFIG.load(deps.storage)?;
-    // check agains collection denom
-    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
+    // Escrowed NFTs are returned, non-custodial NFTs never left the creator
+    for id in ask_ids {
+        let ask = asks()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
+        ensure_eq!(ask.collection, collection, wrong_collection(&id));
+
+        ask.remove(deps.storage)?;
+        response =
+            response.add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Delete)?);
+        if !ask.non_custodial {
+            response = transfer_nft(
+                &ask.collection,
+                &ask.token_id,
+                &ask.asset_recipient(),
+                response,
+            );
+        }
+        response = response.add_event(
+            AskEvent {
+                ty: "remove-delisted-ask",
+                ask: &ask,
+                attr_keys: vec!["id", "collection", "token_id"],
+            }
+            .into(),
+        );
+    }
 
-    // Check and collect listing fee
-    let listing_payment = one_coin(&info)?;
-    let listing_fee = LISTING_FEES.may_load(deps.storage, listing_payment.denom.clone())?;
-    if let Some(_listing_fee) = listing_fee {
-        ensure_eq!(
-            listing_payment.amount,
-            _listing_fee,
-            ContractError::InvalidInput("payment amount does not match listing fee".to_string())
+    // Escrowed bid funds are refunded to the creators in full
+    for id in bid_ids {
+        let bid = bids()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
+        ensure_eq!(bid.collection, collection, wrong_collection(&id));
+
+        bid.remove(deps.storage)?;
+        response =
+            response.add_submessages(prepare_bid_hook(deps.storage, &bid, HookAction::Delete)?);
+        response = transfer_asset(deps.api, bid.escrowed_funds()?, &bid.creator, response)?;
+        response = response.add_event(
+            BidEvent {
+                ty: "remove-delisted-bid",
+                bid: &bid,
+                attr_keys: vec!["id", "collection", "token_id"],
+            }
+            .into(),
         );
-    } else {
-        Err(ContractError::InvalidInput(format!(
-            "listing fee for {} not found",
-            listing_payment.denom
-        )))?;
     }
 
-    let mut response = transfer_coin(listing_payment, &config.fee_manager, Response::new());
+    for id in collection_bid_ids {
+        let collection_bid = collection_bids()
+            .load(deps.storage, id.clone())
+            .map_err(|_| {
+                ContractError::InvalidInput(format!("collection bid not found [{}]", id))
+            })?;
+        ensure_eq!(collection_bid.collection, collection, wrong_collection(&id));
 
-    let ask = Ask::new(info.sender.clone(), collection, token_id, details);
+        collection_bid.remove(deps.storage)?;
+        response = response.add_submessages(prepare_collection_bid_hook(
+            deps.storage,
+            &collection_bid,
+            HookAction::Delete,
+        )?);
+        response = transfer_asset(
+            deps.api,
+            collection_bid.escrowed_funds()?,
+            &collection_bid.creator,
+            response,
+        )?;
+        response = response.add_event(
+            CollectionBidEvent {
+                ty: "remove-delisted-collection-bid",
+                collection_bid: &collection_bid,
+                attr_keys: vec!["id", "collection"],
+            }
+            .into(),
+        );
+    }
 
-    let match_result = ask.match_with_bid(deps.as_ref())?;
+    for id in trait_bid_ids {
+        let trait_bid = trait_bids()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("trait bid not found [{}]", id)))?;
+        ensure_eq!(trait_bid.collection, collection, wrong_collection(&id));
 
-    if let Some(matching_bid) = match_result {
-        // If a match is found finalize the sale
-        response = finalize_sale(
-            deps,
-            &env,
-            &ask,
-            &config,
-            &matching_bid,
-            false,
-            "set-ask-match",
+        trait_bid.remove(deps.storage)?;
+        response = response.add_submessages(prepare_trait_bid_hook(
+            deps.storage,
+            &trait_bid,
+            HookAction::Delete,
+        )?);
+        response = transfer_asset(
+            deps.api,
+            trait_bid.escrowed_funds()?,
+            &trait_bid.creator,
             response,
         )?;
-    } else if sell_now {
-        // If no match is found and sell_now is true, abort transaction
-        Err(ContractError::NoMatchFound)?;
-    } else {
-        // If no match is found continue creating the ask.
-        // Ask creation should:
-        // * escrow the nft
-        // * store the ask
+        response = response.add_event(
+            TraitBidEvent {
+                ty: "remove-delisted-trait-bid",
+                trait_bid: &trait_bid,
+                attr_keys: vec!["id", "collection", "trait_type", "trait_value"],
+            }
+            .into(),
+        );
+    }
+
+    // Auctions return the escrowed NFT and refund the highest bid, even if they have ended
+    for id in auction_ids {
+        let auction = auctions()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("auction not found [{}]", id)))?;
+        ensure_eq!(auction.collection, collection, wrong_collection(&id));
 
+        auction.remove(deps.storage)?;
         response = transfer_nft(
-            &ask.collection,
-            &ask.token_id,
-            &env.contract.address,
+            &auction.collection,
+            &auction.token_id,
+            &auction.asset_recipient(),
             response,
         );
-
+        if let Some(high_bid) = &auction.high_bid {
+            response =
+                transfer_asset(deps.api, high_bid.price.clone(), &high_bid.bidder, response)?;
+        }
         response = response.add_event(
-            AskEvent {
-                ty: "set-ask",
-                ask: &ask,
-                attr_keys: vec![
-                    "id",
-                    "creator",
-                    "collection",
-                    "token_id",
-                    "price",
-                    "recipient",
-                    "finder",
-                ],
+            AuctionEvent {
+                ty: "remove-delisted-auction",
+                auction: &auction,
+                attr_keys: vec!["id", "collection", "token_id", "bidder"],
             }
             .into(),
         );
+    }
 
-        asks().update(deps.storage, ask.id.clone(), |existing| match existing {
-            Some(_) => Err(ContractError::InternalError("ask id collision".to_string())),
-            None => Ok(ask),
-        })?;
-    };
+    // Bundles and swaps are cancelled as a whole, returning the NFTs of every collection
+    for id in bundle_ids {
+        let bundle = bundles()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("bundle not found [{}]", id)))?;
+        ensure!(
+            bundle
+                .items
+                .iter()
+                .any(|item| item.collection == collection),
+            wrong_collection(&id)
+        );
 
-    Ok(response)
-}
+        bundle.remove(deps.storage)?;
+        for item in &bundle.items {
+            response = transfer_nft(
+                &item.collection,
+                &item.token_id,
+                &bundle.asset_recipient(),
+                response,
+            );
+        }
+        response = response.add_event(
+            BundleEvent {
+                ty: "remove-delisted-bundle",
+                bundle: &bundle,
+                attr_keys: vec!["id", "items"],
+            }
+            .into(),
+        );
+    }
 
-pub fn execute_update_ask(
+    for id in swap_ids {
+        let swap = swaps()
+            .load(deps.storage, id.clone())
+            .map_err(|_| ContractError::InvalidInput(format!("swap not found [{}]", id)))?;
+        ensure!(
+            swap.offered_nfts
+                .iter()
+                .any(|nft| nft.collection == collection)
+                || swap
+                    .requested_nfts
+                    .iter()
+                    .any(|request| *request.collection() == collection),
+            wrong_collection(&id)
+        );
+
+        swap.remove(deps.storage)?;
+        for nft in &swap.offered_nfts {
+            response = transfer_nft(
+                &nft.collection,
+                &nft.token_id,
+                &swap.asset_recipient(),
+                response,
+            );
+        }
+        if let Some(offered_funds) = &swap.offered_funds {
+            response = transfer_asset(deps.api, offered_funds.clone(), &swap.creator, response)?;
+        }
+        response = response.add_event(
+            SwapEvent {
+                ty: "remove-delisted-swap",
+                swap: &swap,
+                attr_keys: vec!["id", "offered_nfts"],
+            }
+            .into(),
+        );
+    }
+
+    // Counter-offers hold no escrow, the bid they counter keeps its funds
+    for bid_id in counter_offer_ids {
+        let counter_offer = COUNTER_OFFERS
+            .load(deps.storage, bid_id.clone())
+            .map_err(|_| {
+                ContractError::InvalidInput(format!("counter offer not found [{}]", bid_id))
+            })?;
+        ensure_eq!(
+            counter_offer.bid.collection,
+            collection,
+            wrong_collection(&bid_id)
+        );
+
+        COUNTER_OFFERS.remove(deps.storage, bid_id);
+        response = response.add_event(
+            CounterOfferEvent {
+                ty: "remove-delisted-counter-offer",
+                counter_offer: &counter_offer,
+                attr_keys: vec!["id", "bid", "ask"],
+            }
+            .into(),
+        );
+    }
+
+    Ok(response)
+}
+
+pub fn execute_update_pause_state(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: OrderId,
-    details: OrderDetails<Addr>,
+    collection: Option<Addr>,
+    pause_state: PauseState,
 ) -> Result<Response, ContractError> {
-    nonpayable(&info)?;
-
-    let config = CONFIG.load(deps.storage)?;
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-    let mut ask = asks()
-        .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
+    match &collection {
+        Some(collection) if pause_state == PauseState::default() => {
+            COLLECTION_PAUSE_STATES.remove(deps.storage, collection.clone())
+        }
+        Some(collection) => {
+            COLLECTION_PAUSE_STATES.save(deps.storage, collection.clone(), &pause_state)?
+        }
+        None => PAUSE_STATE.save(deps.storage, &pause_state)?,
+    }
 
-    ensure_eq!(
-        info.sender,
-        ask.creator,
-        MarketplaceStdError::Unauthorized(
-            "only the creator of ask can perform this action".to_string()
-        )
+    let response = Response::new().add_event(
+        PauseStateEvent {
+            ty: "set-pause-state",
+            collection: collection.as_ref().map(|collection| collection.as_str()),
+            pause_state: &pause_state,
+        }
+        .into(),
     );
 
-    // check agains collection denom
-    only_valid_price(deps.storage, &config, &ask.collection, &details.price, None)?;
-
-    ask.details = details;
-
-    let mut response = Response::new();
+    Ok(response)
+}
 
-    let match_result = ask.match_with_bid(deps.as_ref())?;
+pub fn execute_set_collection_fee_override(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    fee_override: FeeOverride,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-    if let Some(matching_bid) = match_result {
-        // If a match is found finalize the sale
-        response = finalize_sale(
-            deps,
-            &env,
-            &ask,
-            &config,
-            &matching_bid,
-            false,
-            "update-ask",
-            response,
-        )?;
-    } else {
-        // If no match is found continue updating the ask
-        ask.save(deps.storage)?;
+    fee_override.validate()?;
+    COLLECTION_FEE_OVERRIDES.save(deps.storage, collection.clone(), &fee_override)?;
 
-        response = response.add_event(
-            AskEvent {
-                ty: "update-ask",
-                ask: &ask,
-                attr_keys: vec![
-                    "id",
-                    "collection",
-                    "token_id",
-                    "price",
-                    "recipient",
-                    "finder",
-                ],
-            }
-            .into(),
-        );
-    };
+    let response = Response::new().add_event(
+        FeeOverrideEvent {
+            ty: "set-collection-fee-override",
+            collection: collection.as_ref(),
+            fee_override: Some(&fee_override),
+        }
+        .into(),
+    );
 
     Ok(response)
 }
 
-pub fn execute_remove_ask(
+pub fn execute_remove_collection_fee_override(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    id: OrderId,
+    collection: Addr,
 ) -> Result<Response, ContractError> {
-    nonpayable(&info)?;
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-    let ask = asks()
-        .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
+    COLLECTION_FEE_OVERRIDES.remove(deps.storage, collection.clone());
 
-    ensure_eq!(
-        info.sender,
-        ask.creator,
-        MarketplaceStdError::Unauthorized(
-            "only the creator of ask can perform this action".to_string()
-        )
+    let response = Response::new().add_event(
+        FeeOverrideEvent {
+            ty: "remove-collection-fee-override",
+            collection: collection.as_ref(),
+            fee_override: None,
+        }
+        .into(),
     );
 
-    let mut response = transfer_nft(
-        &ask.collection,
-        &ask.token_id,
-        &ask.asset_recipient(),
-        Response::new(),
-    );
+    Ok(response)
+}
 
-    ask.remove(deps.storage)?;
+pub fn execute_set_collection_royalty_policy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    royalty_policy: RoyaltyPolicy,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-    response = response.add_event(
-        AskEvent {
-            ty: "remove-ask",
-            ask: &ask,
-            attr_keys: vec!["id", "collection", "token_id"],
+    COLLECTION_ROYALTY_POLICIES.save(deps.storage, collection.clone(), &royalty_policy)?;
+
+    let response = Response::new().add_event(
+        RoyaltyPolicyEvent {
+            ty: "set-collection-royalty-policy",
+            collection: collection.as_ref(),
+            royalty_policy: Some(&royalty_policy),
         }
         .into(),
     );
@@ -436,141 +953,367 @@ pub fn execute_remove_ask(
     Ok(response)
 }
 
-pub fn execute_accept_ask(
+pub fn execute_remove_collection_royalty_policy(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: OrderId,
-    details: OrderDetails<Addr>,
+    collection: Addr,
 ) -> Result<Response, ContractError> {
-    let mut funds = NativeBalance(info.funds.clone());
-    funds.normalize();
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-    let ask = asks()
-        .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
+    COLLECTION_ROYALTY_POLICIES.remove(deps.storage, collection.clone());
 
-    ensure!(
-        has_coins(&[details.price.clone()], &ask.details.price),
-        ContractError::InvalidInput("ask price is greater than max input".to_string())
+    let response = Response::new().add_event(
+        RoyaltyPolicyEvent {
+            ty: "remove-collection-royalty-policy",
+            collection: collection.as_ref(),
+            royalty_policy: None,
+        }
+        .into(),
     );
 
-    funds = funds
-        .sub(ask.details.price.clone())
-        .map_err(|_| ContractError::InsufficientFunds)?;
-
-    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
-    NONCE.save(deps.storage, &nonce)?;
+    Ok(response)
+}
 
-    let bid = Bid::new(
-        info.sender.clone(),
-        ask.collection.clone(),
-        ask.token_id.clone(),
-        details,
-        env.block.height,
-        nonce,
-    );
+pub fn execute_set_collection_royalty(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: Addr,
+    collection_royalty: CollectionRoyalty<Addr>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    only_collection_admin(&deps.querier, &collection, &info.sender)?;
 
     let config = CONFIG.load(deps.storage)?;
-    let mut response = finalize_sale(
-        deps,
-        &env,
-        &ask,
-        &config,
-        &MatchingBid::Bid(bid),
-        true,
-        "accept-ask",
-        Response::new(),
-    )?;
+    collection_royalty.validate(&config)?;
+    COLLECTION_ROYALTIES.save(deps.storage, collection.clone(), &collection_royalty)?;
 
-    // Transfer remaining funds back to user
-    if !funds.is_empty() {
-        response = transfer_coins(funds.into_vec(), &info.sender, response);
-    }
+    let response = Response::new().add_event(
+        CollectionRoyaltyEvent {
+            ty: "set-collection-royalty",
+            collection: collection.as_ref(),
+            collection_royalty: Some(&collection_royalty),
+        }
+        .into(),
+    );
 
     Ok(response)
 }
 
-pub fn execute_set_bid(
+pub fn execute_remove_collection_royalty(
     deps: DepsMut,
-    env: Env,
     info: MessageInfo,
     collection: Addr,
-    token_id: TokenId,
-    details: OrderDetails<Addr>,
-    buy_now: bool,
 ) -> Result<Response, ContractError> {
-    only_tradable(&deps.querier, &env.block, &collection)?;
+    nonpayable(&info)?;
+    only_collection_admin(&deps.querier, &collection, &info.sender)?;
 
-    let config = CONFIG.load(deps.storage)?;
+    COLLECTION_ROYALTIES.remove(deps.storage, collection.clone());
 
-    let mut funds = NativeBalance(info.funds.clone());
-    funds.normalize();
+    let response = Response::new().add_event(
+        CollectionRoyaltyEvent {
+            ty: "remove-collection-royalty",
+            collection: collection.as_ref(),
+            collection_royalty: None,
+        }
+        .into(),
+    );
 
-    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
-    NONCE.save(deps.storage, &nonce)?;
+    Ok(response)
+}
 
-    let bid = Bid::new(
-        info.sender.clone(),
-        collection.clone(),
-        token_id,
-        details.clone(),
-        env.block.height,
-        nonce,
+pub fn execute_set_finder(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    finder: Addr,
+    reward_bps: u64,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
+
+    // The finder can be maker and taker of the same sale, or be paired with a default reward
+    let config = CONFIG.load(deps.storage)?;
+    let max_default_reward_bps = config.maker_reward_bps.max(config.taker_reward_bps);
+    ensure!(
+        reward_bps * 2 < MAX_BASIS_POINTS && reward_bps + max_default_reward_bps < MAX_BASIS_POINTS,
+        ContractError::InvalidInput(
+            "reward_bps must be less than 0.5 and less than 1 combined with the default rewards"
+                .to_string()
+        )
     );
+    FINDERS.save(deps.storage, finder.clone(), &reward_bps)?;
 
-    let matching_ask = bid.match_with_ask(deps.as_ref())?;
+    let response = Response::new().add_event(
+        FinderEvent {
+            ty: "set-finder",
+            finder: finder.as_ref(),
+            reward_bps: Some(reward_bps),
+        }
+        .into(),
+    );
 
-    let mut response = Response::new();
+    Ok(response)
+}
 
-    // bid could have a match with an existing ask without the need of buy_now
-    if let Some(ask) = matching_ask {
-        // check against the original ask price denom
-        only_valid_price(
-            deps.storage,
-            &config,
-            &collection,
-            &details.price,
-            Some(&ask.details.price.denom),
-        )?;
-        // If a matching ask is found perform the sale
-        funds = funds
-            .sub(ask.details.price.clone())
-            .map_err(|_| ContractError::InsufficientFunds)?;
+pub fn execute_remove_finder(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    finder: Addr,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
 
-        let config: Config<Addr> = CONFIG.load(deps.storage)?;
+    FINDERS.remove(deps.storage, finder.clone());
 
-        let mut action = "set-bid-match";
-        if buy_now {
-            action = "buy-now";
+    let response = Response::new().add_event(
+        FinderEvent {
+            ty: "remove-finder",
+            finder: finder.as_ref(),
+            reward_bps: None,
         }
-        response = finalize_sale(
-            deps,
-            &env,
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_set_listing_fee(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    fee: Coin,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
+    // Listing fees are paid alongside native messages, they cannot be paid in cw20 tokens
+    ensure!(
+        !is_cw20_denom(&fee.denom),
+        ContractError::InvalidInput("listing fees must be in a native denom".to_string())
+    );
+
+    LISTING_FEES.save(deps.storage, fee.denom.clone(), &fee.amount)?;
+
+    let response = Response::new().add_event(
+        ListingFeeEvent {
+            ty: "set-listing-fee",
+            denom: &fee.denom,
+            amount: &Some(fee.amount),
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_remove_listing_fee(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: Denom,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
+
+    LISTING_FEES.remove(deps.storage, denom.clone());
+
+    let response = Response::new().add_event(
+        ListingFeeEvent {
+            ty: "remove-listing-fee",
+            denom: &denom,
+            amount: &None,
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hooks: Hooks,
+    hook_type: &str,
+    hook: Addr,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
+
+    hooks.add_hook(deps.storage, hook.clone())?;
+
+    let response = Response::new().add_event(
+        HookEvent {
+            ty: "add-hook",
+            hook_type,
+            hook: hook.as_ref(),
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hooks: Hooks,
+    hook_type: &str,
+    hook: Addr,
+) -> Result<Response, ContractError> {
+    only_contract_admin(&deps.querier, &env, &info)?;
+
+    hooks.remove_hook(deps.storage, hook.clone())?;
+
+    let response = Response::new().add_event(
+        HookEvent {
+            ty: "remove-hook",
+            hook_type,
+            hook: hook.as_ref(),
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    details: OrderDetails<Addr>,
+    non_custodial: bool,
+    reserved_for: Vec<Addr>,
+    dutch_auction: Option<DutchAuction>,
+    sell_now: bool,
+) -> Result<Response, ContractError> {
+    only_owner(&deps.querier, &info, &collection, &token_id)?;
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // check agains collection denom
+    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
+    only_valid_reserved_for(&reserved_for)?;
+    if let Some(dutch_auction) = &dutch_auction {
+        only_valid_dutch_auction(&details.price, dutch_auction)?;
+    }
+
+    let response = collect_listing_fee(deps.as_ref(), &info, &config, Response::new())?;
+
+    let ask = Ask::new(
+        info.sender.clone(),
+        collection,
+        token_id,
+        details,
+        non_custodial,
+        reserved_for,
+        dutch_auction,
+    );
+
+    create_ask(deps, &env, &config, ask, sell_now, false, response)
+}
+
+/// Checks that the payment matches the listing fee of its denom and forwards it to the fee manager
+fn collect_listing_fee(
+    deps: Deps,
+    info: &MessageInfo,
+    config: &Config<Addr>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let listing_payment = one_coin(info)?;
+    let listing_fee = LISTING_FEES.may_load(deps.storage, listing_payment.denom.clone())?;
+    if let Some(_listing_fee) = listing_fee {
+        ensure_eq!(
+            listing_payment.amount,
+            _listing_fee,
+            ContractError::InvalidInput("payment amount does not match listing fee".to_string())
+        );
+    } else {
+        Err(ContractError::InvalidInput(format!(
+            "listing fee for {} not found",
+            listing_payment.denom
+        )))?;
+    }
+
+    Ok(transfer_asset(
+        deps.api,
+        listing_payment,
+        &config.fee_manager,
+        response,
+    )?)
+}
+
+/// Matches a new ask against the top bid, or stores it when no match is found.
+/// When escrowed is set the marketplace already holds the NFT.
+fn create_ask(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config<Addr>,
+    ask: Ask,
+    sell_now: bool,
+    escrowed: bool,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    only_without_royalty_tip(&ask.details)?;
+
+    // A non-custodial ask left behind by a previous owner of the NFT is replaced
+    if let Some(stale_ask) = asks().may_load(deps.storage, ask.id.clone())? {
+        if stale_ask.non_custodial && stale_ask.creator != ask.creator {
+            stale_ask.remove(deps.storage)?;
+            response = response.add_submessages(prepare_ask_hook(
+                deps.storage,
+                &stale_ask,
+                HookAction::Delete,
+            )?);
+        }
+    }
+
+    let match_result = ask.match_with_bid(deps.as_ref(), &env.block)?;
+
+    if let Some(matching_bid) = match_result {
+        // If a match is found finalize the sale
+        response = finalize_sale(
+            deps,
+            env,
             &ask,
-            &config,
-            &MatchingBid::Bid(bid),
-            true,
-            action,
+            config,
+            &matching_bid,
+            false,
+            "set-ask-match",
             response,
         )?;
-    } else if buy_now {
-        // If no match is found and buy_now is true, abort transaction
+    } else if sell_now {
+        // If no match is found and sell_now is true, abort transaction
         Err(ContractError::NoMatchFound)?;
     } else {
-        // If no match is found. Bid creation should:
-        // * store the bid
-        // * emit event
-        // * check against the collection denom
-        only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
-        funds = funds
-            .sub(bid.details.price.clone())
-            .map_err(|_| ContractError::InsufficientFunds)?;
+        // If no match is found continue creating the ask.
+        // Ask creation should:
+        // * escrow the nft, or check the approval for non-custodial asks
+        // * store the ask
+
+        if ask.non_custodial {
+            ensure!(
+                fetch_ask_custody(&deps.querier, &env.contract.address, &ask).has_approval,
+                ContractError::InvalidInput(
+                    "marketplace must be approved to transfer the NFT".to_string()
+                )
+            );
+        } else if !escrowed {
+            response = transfer_nft(
+                &ask.collection,
+                &ask.token_id,
+                &env.contract.address,
+                response,
+            );
+        }
 
         response = response.add_event(
-            BidEvent {
-                ty: "set-bid",
-                bid: &bid,
+            AskEvent {
+                ty: "set-ask",
+                ask: &ask,
                 attr_keys: vec![
                     "id",
                     "creator",
@@ -579,113 +1322,115 @@ pub fn execute_set_bid(
                     "price",
                     "recipient",
                     "finder",
+                    "non_custodial",
+                    "reserved_for",
+                    "end_price",
+                    "start_time",
+                    "end_time",
+                    "expires",
                 ],
             }
             .into(),
         );
 
-        bids().update(deps.storage, bid.id.clone(), |existing| match existing {
-            Some(_) => Err(ContractError::InternalError("bid id collision".to_string())),
-            None => Ok(bid),
-        })?;
-    }
+        response =
+            response.add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Create)?);
 
-    // Transfer remaining funds back to user
-    if !funds.is_empty() {
-        response = transfer_coins(funds.into_vec(), &info.sender, response);
-    }
+        ensure!(
+            !asks().has(deps.storage, ask.id.clone()),
+            ContractError::InternalError("ask id collision".to_string())
+        );
+        ask.save(deps.storage)?;
+    };
 
     Ok(response)
 }
 
-pub fn execute_update_bid(
+pub fn execute_update_ask(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: OrderId,
     details: OrderDetails<Addr>,
 ) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
     let config = CONFIG.load(deps.storage)?;
 
-    let mut bid = bids()
+    let mut ask = asks()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
 
     ensure_eq!(
         info.sender,
-        bid.creator,
+        ask.creator,
         MarketplaceStdError::Unauthorized(
-            "only the creator of bid can perform this action".to_string()
+            "only the creator of ask can perform this action".to_string()
         )
     );
+    only_unpaused(
+        deps.storage,
+        Some(&ask.collection),
+        PausableOperation::Orders,
+    )?;
+    only_listed_collection(deps.storage, &ask.collection)?;
+    ensure!(
+        ask.dutch_auction.is_none(),
+        ContractError::InvalidInput("dutch auction asks cannot be updated".to_string())
+    );
 
-    // check against collection denom
-    only_valid_price(deps.storage, &config, &bid.collection, &details.price, None)?;
-
-    let mut funds = NativeBalance(info.funds.clone());
-    funds.normalize();
-
-    // Add the previous price to the funds in context
-    funds = funds.add(bid.details.price.clone());
+    // check agains collection denom
+    only_valid_price(deps.storage, &config, &ask.collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
+    only_without_royalty_tip(&details)?;
 
-    bid.details = details;
+    ask.details = details;
 
     let mut response = Response::new();
 
-    let match_result = bid.match_with_ask(deps.as_ref())?;
+    let match_result = ask.match_with_bid(deps.as_ref(), &env.block)?;
 
-    if let Some(ask) = match_result {
+    if let Some(matching_bid) = match_result {
         // If a match is found finalize the sale
-        funds = funds
-            .sub(ask.details.price.clone())
-            .map_err(|_| ContractError::InsufficientFunds)?;
-
-        let config: Config<Addr> = CONFIG.load(deps.storage)?;
         response = finalize_sale(
             deps,
             &env,
             &ask,
             &config,
-            &MatchingBid::Bid(bid),
-            true,
-            "update-bid",
+            &matching_bid,
+            false,
+            "update-ask",
             response,
         )?;
     } else {
-        // If no match is found update the bid
-        funds = funds
-            .sub(bid.details.price.clone())
-            .map_err(|_| ContractError::InsufficientFunds)?;
+        // If no match is found continue updating the ask
+        ask.save(deps.storage)?;
 
-        bid.save(deps.storage)?;
+        response =
+            response.add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Update)?);
 
         response = response.add_event(
-            BidEvent {
-                ty: "update-bid",
-                bid: &bid,
+            AskEvent {
+                ty: "update-ask",
+                ask: &ask,
                 attr_keys: vec![
                     "id",
                     "collection",
                     "token_id",
                     "price",
-                    "creator",
                     "recipient",
                     "finder",
+                    "expires",
                 ],
             }
             .into(),
         );
     };
 
-    // Transfer remaining funds back to user
-    if !funds.is_empty() {
-        response = transfer_coins(funds.into_vec(), &info.sender, response);
-    }
-
     Ok(response)
 }
 
-pub fn execute_remove_bid(
+pub fn execute_remove_ask(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
@@ -693,28 +1438,37 @@ pub fn execute_remove_bid(
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    let bid = bids()
+    let ask = asks()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
 
     ensure_eq!(
         info.sender,
-        bid.creator,
+        ask.creator,
         MarketplaceStdError::Unauthorized(
-            "only the creator of bid can perform this action".to_string()
+            "only the creator of ask can perform this action".to_string()
         )
     );
 
-    let refund = bid.details.price.clone();
+    // Escrowed NFTs are returned, non-custodial NFTs never left the creator
+    let mut response = Response::new();
+    if !ask.non_custodial {
+        response = transfer_nft(
+            &ask.collection,
+            &ask.token_id,
+            &ask.asset_recipient(),
+            response,
+        );
+    }
 
-    bid.remove(deps.storage)?;
+    ask.remove(deps.storage)?;
 
-    let mut response = transfer_coin(refund, &info.sender, Response::new());
+    response = response.add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Delete)?);
 
     response = response.add_event(
-        BidEvent {
-            ty: "remove-bid",
-            bid: &bid,
+        AskEvent {
+            ty: "remove-ask",
+            ask: &ask,
             attr_keys: vec!["id", "collection", "token_id"],
         }
         .into(),
@@ -723,71 +1477,132 @@ pub fn execute_remove_bid(
     Ok(response)
 }
 
-pub fn execute_accept_bid(
+pub fn execute_sync_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let ask = asks()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?;
+
+    ensure!(
+        ask.non_custodial,
+        ContractError::InvalidInput("only non-custodial asks can be synced".to_string())
+    );
+
+    let custody = fetch_ask_custody(&deps.querier, &env.contract.address, &ask);
+    ensure!(
+        !custody.is_valid(),
+        ContractError::InvalidInput("ask is unchanged".to_string())
+    );
+
+    ask.remove(deps.storage)?;
+
+    let mut response =
+        Response::new().add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Delete)?);
+
+    response = response.add_event(
+        Event::from(AskEvent {
+            ty: "remove-stale-ask",
+            ask: &ask,
+            attr_keys: vec!["id", "collection", "token_id"],
+        })
+        .add_attribute("is_owner", custody.is_owner.to_string())
+        .add_attribute("has_approval", custody.has_approval.to_string()),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_ask(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: OrderId,
     details: OrderDetails<Addr>,
 ) -> Result<Response, ContractError> {
-    let bid: Bid = bids()
+    let api = deps.api;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let ask = asks()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("ask not found [{}]", id)))?
+        .with_price_at(&env.block);
 
     ensure!(
-        has_coins(&[bid.details.price.clone()], &details.price),
-        ContractError::InvalidInput("min output is greater than bid price".to_string())
+        !ask.details.is_expired(&env.block),
+        ContractError::InvalidInput("ask is expired".to_string())
     );
 
-    let ask_id = generate_id(vec![bid.collection.as_bytes(), bid.token_id.as_bytes()]);
-    let ask_option = asks().may_load(deps.storage, ask_id.clone())?;
+    ensure!(
+        ask.is_allowed_buyer(&info.sender),
+        MarketplaceStdError::Unauthorized("sender is not allowed to buy the ask".to_string())
+    );
 
-    // Check if the sender is the owner of the NFT, or if the creator of a valid ask
-    let ask = if let Some(ask) = ask_option {
-        if info.sender != ask.creator {
-            Err(MarketplaceStdError::Unauthorized(
-                "sender is not creator of ask".to_string(),
-            ))?;
-        }
-        ask
-    } else {
-        only_owner(&deps.querier, &info, &bid.collection, &bid.token_id)?;
-        Ask::new(
-            info.sender.clone(),
-            bid.collection.clone(),
-            bid.token_id.clone(),
-            details,
-        )
-    };
+    ensure!(
+        has_coins(&[details.price.clone()], &ask.details.price),
+        ContractError::InvalidInput("ask price is greater than max input".to_string())
+    );
 
-    let config: Config<Addr> = CONFIG.load(deps.storage)?;
-    let response = finalize_sale(
+    funds = funds
+        .sub(details.with_royalty_tip(&ask.details.price)?)
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    let bid = Bid::new(
+        info.sender.clone(),
+        ask.collection.clone(),
+        ask.token_id.clone(),
+        details,
+        env.block.height,
+        nonce,
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = finalize_sale(
         deps,
         &env,
         &ask,
         &config,
         &MatchingBid::Bid(bid),
-        false,
-        "accept-bid",
+        true,
+        "accept-ask",
         Response::new(),
     )?;
 
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
+    }
+
     Ok(response)
 }
 
-pub fn execute_set_collection_bid(
+pub fn execute_set_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     collection: Addr,
+    token_id: TokenId,
     details: OrderDetails<Addr>,
     buy_now: bool,
 ) -> Result<Response, ContractError> {
+    let api = deps.api;
+
     only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+    only_valid_expiration(&env.block, &details.expires)?;
 
     let config = CONFIG.load(deps.storage)?;
-    // check agains collection denom
-    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
 
     let mut funds = NativeBalance(info.funds.clone());
     funds.normalize();
@@ -795,126 +1610,148 @@ pub fn execute_set_collection_bid(
     let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
     NONCE.save(deps.storage, &nonce)?;
 
-    let collection_bid = CollectionBid::new(
+    let bid = Bid::new(
         info.sender.clone(),
-        collection,
-        details,
+        collection.clone(),
+        token_id,
+        details.clone(),
         env.block.height,
         nonce,
     );
 
-    let matching_ask = collection_bid.match_with_ask(deps.as_ref())?;
+    let matching_ask = bid.match_with_ask(deps.as_ref(), &env.block, &env.contract.address)?;
 
     let mut response = Response::new();
 
+    // bid could have a match with an existing ask without the need of buy_now
     if let Some(ask) = matching_ask {
+        // check against the original ask price denom
+        only_valid_price(
+            deps.storage,
+            &config,
+            &collection,
+            &details.price,
+            Some(&ask.details.price.denom),
+        )?;
         // If a matching ask is found perform the sale
         funds = funds
-            .sub(ask.details.price.clone())
+            .sub(details.with_royalty_tip(&ask.details.price)?)
             .map_err(|_| ContractError::InsufficientFunds)?;
 
         let config: Config<Addr> = CONFIG.load(deps.storage)?;
+
+        let mut action = "set-bid-match";
+        if buy_now {
+            action = "buy-now";
+        }
         response = finalize_sale(
             deps,
             &env,
             &ask,
             &config,
-            &MatchingBid::CollectionBid(collection_bid),
+            &MatchingBid::Bid(bid),
             true,
-            "set-collection-bid",
+            action,
             response,
         )?;
     } else if buy_now {
         // If no match is found and buy_now is true, abort transaction
         Err(ContractError::NoMatchFound)?;
     } else {
-        // If no match is found. Bid creation should store the bid
+        // If no match is found. Bid creation should:
+        // * store the bid
+        // * emit event
+        // * check against the collection denom
+        only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
         funds = funds
-            .sub(collection_bid.details.price.clone())
+            .sub(bid.escrowed_funds()?)
             .map_err(|_| ContractError::InsufficientFunds)?;
 
         response = response.add_event(
-            CollectionBidEvent {
-                ty: "set-collection-bid",
-                collection_bid: &collection_bid,
+            BidEvent {
+                ty: "set-bid",
+                bid: &bid,
                 attr_keys: vec![
                     "id",
                     "creator",
                     "collection",
+                    "token_id",
                     "price",
                     "recipient",
                     "finder",
+                    "expires",
                 ],
             }
             .into(),
         );
 
-        collection_bids().update(
-            deps.storage,
-            collection_bid.id.clone(),
-            |existing| match existing {
-                Some(_) => Err(ContractError::InternalError(
-                    "collection bid id collision".to_string(),
-                )),
-                None => Ok(collection_bid),
-            },
-        )?;
+        response =
+            response.add_submessages(prepare_bid_hook(deps.storage, &bid, HookAction::Create)?);
+
+        ensure!(
+            !bids().has(deps.storage, bid.id.clone()),
+            ContractError::InternalError("bid id collision".to_string())
+        );
+        bid.save(deps.storage)?;
     }
 
     // Transfer remaining funds back to user
     if !funds.is_empty() {
-        response = transfer_coins(funds.into_vec(), &info.sender, response);
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
     }
 
     Ok(response)
 }
 
-pub fn execute_update_collection_bid(
+pub fn execute_update_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: OrderId,
     details: OrderDetails<Addr>,
 ) -> Result<Response, ContractError> {
+    let api = deps.api;
+
     let config = CONFIG.load(deps.storage)?;
 
-    let mut collection_bid = collection_bids()
+    let mut bid = bids()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
 
     ensure_eq!(
         info.sender,
-        collection_bid.creator,
+        bid.creator,
         MarketplaceStdError::Unauthorized(
-            "only the creator of collection bid can perform this action".to_string()
+            "only the creator of bid can perform this action".to_string()
         )
     );
-
-    // check agains collection denom
-    only_valid_price(
+    only_unpaused(
         deps.storage,
-        &config,
-        &collection_bid.collection,
-        &details.price,
-        None,
+        Some(&bid.collection),
+        PausableOperation::Orders,
     )?;
+    only_listed_collection(deps.storage, &bid.collection)?;
+
+    // check against collection denom
+    only_valid_price(deps.storage, &config, &bid.collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
 
     let mut funds = NativeBalance(info.funds.clone());
     funds.normalize();
 
-    // Add the previous price to the funds in context
-    funds = funds.add(collection_bid.details.price.clone());
+    // Add the previously escrowed funds to the funds in context
+    funds = funds.add(bid.escrowed_funds()?);
 
-    collection_bid.details = details;
+    bid.details = details;
 
     let mut response = Response::new();
 
-    let match_result = collection_bid.match_with_ask(deps.as_ref())?;
+    let match_result = bid.match_with_ask(deps.as_ref(), &env.block, &env.contract.address)?;
 
     if let Some(ask) = match_result {
         // If a match is found finalize the sale
         funds = funds
-            .sub(ask.details.price.clone())
+            .sub(bid.details.with_royalty_tip(&ask.details.price)?)
             .map_err(|_| ContractError::InsufficientFunds)?;
 
         let config: Config<Addr> = CONFIG.load(deps.storage)?;
@@ -923,30 +1760,35 @@ pub fn execute_update_collection_bid(
             &env,
             &ask,
             &config,
-            &MatchingBid::CollectionBid(collection_bid),
+            &MatchingBid::Bid(bid),
             true,
-            "update-collection-bid",
+            "update-bid",
             response,
         )?;
     } else {
         // If no match is found update the bid
         funds = funds
-            .sub(collection_bid.details.price.clone())
+            .sub(bid.escrowed_funds()?)
             .map_err(|_| ContractError::InsufficientFunds)?;
 
-        collection_bid.save(deps.storage)?;
+        bid.save(deps.storage)?;
+
+        response =
+            response.add_submessages(prepare_bid_hook(deps.storage, &bid, HookAction::Update)?);
 
         response = response.add_event(
-            CollectionBidEvent {
-                ty: "update-collection-bid",
-                collection_bid: &collection_bid,
+            BidEvent {
+                ty: "update-bid",
+                bid: &bid,
                 attr_keys: vec![
                     "id",
                     "collection",
+                    "token_id",
                     "price",
                     "creator",
                     "recipient",
                     "finder",
+                    "expires",
                 ],
             }
             .into(),
@@ -955,13 +1797,13 @@ pub fn execute_update_collection_bid(
 
     // Transfer remaining funds back to user
     if !funds.is_empty() {
-        response = transfer_coins(funds.into_vec(), &info.sender, response);
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
     }
 
     Ok(response)
 }
 
-pub fn execute_remove_collection_bid(
+pub fn execute_remove_bid(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
@@ -969,29 +1811,31 @@ pub fn execute_remove_collection_bid(
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    let collection_bid = collection_bids()
+    let bid = bids()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
 
     ensure_eq!(
         info.sender,
-        collection_bid.creator,
+        bid.creator,
         MarketplaceStdError::Unauthorized(
-            "only the creator of collection bid can perform this action".to_string()
+            "only the creator of bid can perform this action".to_string()
         )
     );
 
-    let refund = collection_bid.details.price.clone();
+    let refund = bid.escrowed_funds()?;
 
-    collection_bid.remove(deps.storage)?;
+    bid.remove(deps.storage)?;
 
-    let mut response = transfer_coin(refund, &info.sender, Response::new());
+    let mut response = transfer_asset(deps.api, refund, &info.sender, Response::new())?;
+
+    response = response.add_submessages(prepare_bid_hook(deps.storage, &bid, HookAction::Delete)?);
 
     response = response.add_event(
-        CollectionBidEvent {
-            ty: "remove-collection-bid",
-            collection_bid: &collection_bid,
-            attr_keys: vec!["id", "collection"],
+        BidEvent {
+            ty: "remove-bid",
+            bid: &bid,
+            attr_keys: vec!["id", "collection", "token_id"],
         }
         .into(),
     );
@@ -999,27 +1843,28 @@ pub fn execute_remove_collection_bid(
     Ok(response)
 }
 
-pub fn execute_accept_collection_bid(
+pub fn execute_accept_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: OrderId,
-    token_id: TokenId,
     details: OrderDetails<Addr>,
 ) -> Result<Response, ContractError> {
-    let collection_bid = collection_bids()
+    let bid: Bid = bids()
         .load(deps.storage, id.clone())
-        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", id)))?;
 
     ensure!(
-        has_coins(&[collection_bid.details.price.clone()], &details.price),
-        ContractError::InvalidInput("min output is greater than collection bid price".to_string())
+        !bid.details.is_expired(&env.block),
+        ContractError::InvalidInput("bid is expired".to_string())
     );
 
-    let ask_id = generate_id(vec![
-        collection_bid.collection.as_bytes(),
-        token_id.as_bytes(),
-    ]);
+    ensure!(
+        has_coins(&[bid.details.price.clone()], &details.price),
+        ContractError::InvalidInput("min output is greater than bid price".to_string())
+    );
+
+    let ask_id = generate_id(vec![bid.collection.as_bytes(), bid.token_id.as_bytes()]);
     let ask_option = asks().may_load(deps.storage, ask_id.clone())?;
 
     // Check if the sender is the owner of the NFT, or if the creator of a valid ask
@@ -1031,12 +1876,15 @@ pub fn execute_accept_collection_bid(
         }
         ask
     } else {
-        only_owner(&deps.querier, &info, &collection_bid.collection, &token_id)?;
+        only_owner(&deps.querier, &info, &bid.collection, &bid.token_id)?;
         Ask::new(
             info.sender.clone(),
-            collection_bid.collection.clone(),
-            token_id.clone(),
+            bid.collection.clone(),
+            bid.token_id.clone(),
             details,
+            false,
+            vec![],
+            None,
         )
     };
 
@@ -1046,11 +1894,2306 @@ pub fn execute_accept_collection_bid(
         &env,
         &ask,
         &config,
-        &MatchingBid::CollectionBid(collection_bid),
+        &MatchingBid::Bid(bid),
         false,
-        "accept-collection-bid",
+        "accept-bid",
         Response::new(),
     )?;
 
     Ok(response)
 }
+
+pub fn execute_set_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bid_id: OrderId,
+    price: Coin,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let bid = bids()
+        .load(deps.storage, bid_id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("bid not found [{}]", bid_id)))?;
+
+    only_listed_collection(deps.storage, &bid.collection)?;
+    only_unpaused(
+        deps.storage,
+        Some(&bid.collection),
+        PausableOperation::Orders,
+    )?;
+    ensure!(
+        !bid.details.is_expired(&env.block),
+        ContractError::InvalidInput("bid is expired".to_string())
+    );
+    only_valid_expiration(&env.block, &expires)?;
+
+    // The creator of the ask counters for listed NFTs, otherwise the owner of the NFT
+    let ask_id = generate_id(vec![bid.collection.as_bytes(), bid.token_id.as_bytes()]);
+    let ask = asks().may_load(deps.storage, ask_id)?;
+    match &ask {
+        Some(ask) => ensure_eq!(
+            info.sender,
+            ask.creator,
+            MarketplaceStdError::Unauthorized("sender is not creator of ask".to_string())
+        ),
+        None => only_owner(&deps.querier, &info, &bid.collection, &bid.token_id)?,
+    }
+
+    ensure_eq!(
+        price.denom,
+        bid.details.price.denom,
+        ContractError::InvalidInput("invalid denom".to_string())
+    );
+    ensure!(
+        price.amount > bid.details.price.amount,
+        ContractError::InvalidInput("counter price must be greater than the bid price".to_string())
+    );
+
+    let counter_offer = CounterOffer::new(info.sender, bid, ask, price, expires);
+    COUNTER_OFFERS.save(deps.storage, bid_id, &counter_offer)?;
+
+    let response = Response::new().add_event(
+        CounterOfferEvent {
+            ty: "set-counter-offer",
+            counter_offer: &counter_offer,
+            attr_keys: vec![
+                "id",
+                "creator",
+                "bid",
+                "ask",
+                "collection",
+                "token_id",
+                "price",
+                "expires",
+            ],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_remove_counter_offer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    bid_id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let counter_offer = COUNTER_OFFERS
+        .load(deps.storage, bid_id.clone())
+        .map_err(|_| {
+            ContractError::InvalidInput(format!("counter offer not found [{}]", bid_id))
+        })?;
+
+    ensure_eq!(
+        info.sender,
+        counter_offer.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of counter offer can perform this action".to_string()
+        )
+    );
+
+    COUNTER_OFFERS.remove(deps.storage, bid_id);
+
+    let response = Response::new().add_event(
+        CounterOfferEvent {
+            ty: "remove-counter-offer",
+            counter_offer: &counter_offer,
+            attr_keys: vec!["id", "bid", "ask"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bid_id: OrderId,
+) -> Result<Response, ContractError> {
+    let api = deps.api;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let counter_offer = COUNTER_OFFERS
+        .load(deps.storage, bid_id.clone())
+        .map_err(|_| {
+            ContractError::InvalidInput(format!("counter offer not found [{}]", bid_id))
+        })?;
+
+    ensure_eq!(
+        info.sender,
+        counter_offer.bid.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of bid can perform this action".to_string()
+        )
+    );
+    ensure!(
+        !counter_offer.is_expired(&env.block),
+        ContractError::InvalidInput("counter offer is expired".to_string())
+    );
+    ensure!(
+        !counter_offer.bid.details.is_expired(&env.block),
+        ContractError::InvalidInput("bid is expired".to_string())
+    );
+    ensure!(
+        !counter_offer
+            .ask
+            .as_ref()
+            .is_some_and(|ask| ask.details.is_expired(&env.block)),
+        ContractError::InvalidInput("ask is expired".to_string())
+    );
+    ensure!(
+        !counter_offer.is_stale(deps.storage)?,
+        ContractError::InvalidInput("bid or ask changed since the counter offer".to_string())
+    );
+
+    // The escrowed bid is topped up to the counter price
+    let top_up = coin(
+        (counter_offer.price.amount - counter_offer.bid.details.price.amount).u128(),
+        &counter_offer.price.denom,
+    );
+    funds = funds
+        .sub(top_up)
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    COUNTER_OFFERS.remove(deps.storage, bid_id);
+
+    let mut bid = counter_offer.bid.clone();
+    bid.details.price = counter_offer.price.clone();
+
+    // NFTs without an ask are sold from the wallet of the owner, which requires an approval
+    let mut ask = match counter_offer.ask.clone() {
+        Some(ask) => ask,
+        None => Ask::new(
+            counter_offer.creator.clone(),
+            bid.collection.clone(),
+            bid.token_id.clone(),
+            OrderDetails {
+                price: counter_offer.price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+            true,
+            vec![],
+            None,
+        ),
+    };
+    ask.details.price = counter_offer.price.clone();
+
+    let mut response = Response::new().add_event(
+        CounterOfferEvent {
+            ty: "accept-counter-offer",
+            counter_offer: &counter_offer,
+            attr_keys: vec!["id", "bid", "ask", "collection", "token_id", "price"],
+        }
+        .into(),
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+    response = finalize_sale(
+        deps,
+        &env,
+        &ask,
+        &config,
+        &MatchingBid::Bid(bid),
+        true,
+        "accept-counter-offer",
+        response,
+    )?;
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_set_collection_bid(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    details: OrderDetails<Addr>,
+    quantity: u32,
+    buy_now: bool,
+) -> Result<Response, ContractError> {
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // check agains collection denom
+    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
+    only_valid_quantity(quantity)?;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    let mut collection_bid = CollectionBid::new(
+        info.sender.clone(),
+        collection,
+        details,
+        quantity,
+        env.block.height,
+        nonce,
+    );
+
+    let mut response = Response::new();
+
+    // Fill as many units of the collection bid as possible with matching asks
+    let mut num_filled = 0u32;
+    while collection_bid.quantity > 0 {
+        let Some(ask) =
+            collection_bid.match_with_ask(deps.as_ref(), &env.block, &env.contract.address)?
+        else {
+            break;
+        };
+
+        funds = funds
+            .sub(
+                collection_bid
+                    .details
+                    .with_royalty_tip(&ask.details.price)?,
+            )
+            .map_err(|_| ContractError::InsufficientFunds)?;
+
+        response = finalize_sale(
+            deps.branch(),
+            &env,
+            &ask,
+            &config,
+            &MatchingBid::CollectionBid(collection_bid.clone()),
+            true,
+            "set-collection-bid",
+            response,
+        )?;
+
+        collection_bid.quantity -= 1;
+        num_filled += 1;
+    }
+
+    if buy_now {
+        // If no match is found and buy_now is true, abort transaction
+        ensure!(num_filled > 0, ContractError::NoMatchFound);
+    } else if collection_bid.quantity > 0 {
+        // The unfilled portion of the bid is escrowed and stored
+        funds = funds
+            .sub(collection_bid.escrowed_funds()?)
+            .map_err(|_| ContractError::InsufficientFunds)?;
+
+        response = response.add_event(
+            CollectionBidEvent {
+                ty: "set-collection-bid",
+                collection_bid: &collection_bid,
+                attr_keys: vec![
+                    "id",
+                    "creator",
+                    "collection",
+                    "price",
+                    "quantity",
+                    "recipient",
+                    "finder",
+                    "expires",
+                ],
+            }
+            .into(),
+        );
+
+        response = response.add_submessages(prepare_collection_bid_hook(
+            deps.storage,
+            &collection_bid,
+            HookAction::Create,
+        )?);
+
+        ensure!(
+            !collection_bids().has(deps.storage, collection_bid.id.clone()),
+            ContractError::InternalError("collection bid id collision".to_string())
+        );
+        collection_bid.save(deps.storage)?;
+    }
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_update_collection_bid(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    details: OrderDetails<Addr>,
+    quantity: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut collection_bid = collection_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        collection_bid.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of collection bid can perform this action".to_string()
+        )
+    );
+    only_unpaused(
+        deps.storage,
+        Some(&collection_bid.collection),
+        PausableOperation::Orders,
+    )?;
+    only_listed_collection(deps.storage, &collection_bid.collection)?;
+
+    // check agains collection denom
+    only_valid_price(
+        deps.storage,
+        &config,
+        &collection_bid.collection,
+        &details.price,
+        None,
+    )?;
+    only_valid_expiration(&env.block, &details.expires)?;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    // Add the previously escrowed funds of the unfilled portion to the funds in context
+    funds = funds.add(collection_bid.escrowed_funds()?);
+
+    collection_bid.details = details;
+    if let Some(quantity) = quantity {
+        only_valid_quantity(quantity)?;
+        collection_bid.quantity = quantity;
+    }
+
+    let mut response = Response::new();
+
+    // Fill as many units of the collection bid as possible with matching asks,
+    // the stored collection bid is decremented or removed by finalize_sale
+    while collection_bid.quantity > 0 {
+        let Some(ask) =
+            collection_bid.match_with_ask(deps.as_ref(), &env.block, &env.contract.address)?
+        else {
+            break;
+        };
+
+        funds = funds
+            .sub(
+                collection_bid
+                    .details
+                    .with_royalty_tip(&ask.details.price)?,
+            )
+            .map_err(|_| ContractError::InsufficientFunds)?;
+
+        response = finalize_sale(
+            deps.branch(),
+            &env,
+            &ask,
+            &config,
+            &MatchingBid::CollectionBid(collection_bid.clone()),
+            true,
+            "update-collection-bid",
+            response,
+        )?;
+
+        collection_bid.quantity -= 1;
+    }
+
+    if collection_bid.quantity > 0 {
+        // The unfilled portion of the bid is escrowed and stored
+        funds = funds
+            .sub(collection_bid.escrowed_funds()?)
+            .map_err(|_| ContractError::InsufficientFunds)?;
+
+        collection_bid.save(deps.storage)?;
+
+        response = response.add_submessages(prepare_collection_bid_hook(
+            deps.storage,
+            &collection_bid,
+            HookAction::Update,
+        )?);
+
+        response = response.add_event(
+            CollectionBidEvent {
+                ty: "update-collection-bid",
+                collection_bid: &collection_bid,
+                attr_keys: vec![
+                    "id",
+                    "collection",
+                    "price",
+                    "quantity",
+                    "creator",
+                    "recipient",
+                    "finder",
+                    "expires",
+                ],
+            }
+            .into(),
+        );
+    };
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_remove_collection_bid(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let collection_bid = collection_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        collection_bid.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of collection bid can perform this action".to_string()
+        )
+    );
+
+    let refund = collection_bid.escrowed_funds()?;
+
+    collection_bid.remove(deps.storage)?;
+
+    let mut response = transfer_asset(deps.api, refund, &info.sender, Response::new())?;
+
+    response = response.add_submessages(prepare_collection_bid_hook(
+        deps.storage,
+        &collection_bid,
+        HookAction::Delete,
+    )?);
+
+    response = response.add_event(
+        CollectionBidEvent {
+            ty: "remove-collection-bid",
+            collection_bid: &collection_bid,
+            attr_keys: vec!["id", "collection"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_collection_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    token_id: TokenId,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    let collection_bid = collection_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("collection bid not found [{}]", id)))?;
+
+    ensure!(
+        !collection_bid.details.is_expired(&env.block),
+        ContractError::InvalidInput("collection bid is expired".to_string())
+    );
+
+    ensure!(
+        has_coins(&[collection_bid.details.price.clone()], &details.price),
+        ContractError::InvalidInput("min output is greater than collection bid price".to_string())
+    );
+
+    let ask_id = generate_id(vec![
+        collection_bid.collection.as_bytes(),
+        token_id.as_bytes(),
+    ]);
+    let ask_option = asks().may_load(deps.storage, ask_id.clone())?;
+
+    // Check if the sender is the owner of the NFT, or if the creator of a valid ask
+    let ask = if let Some(ask) = ask_option {
+        if info.sender != ask.creator {
+            Err(MarketplaceStdError::Unauthorized(
+                "sender is not creator of ask".to_string(),
+            ))?;
+        }
+        ask
+    } else {
+        only_owner(&deps.querier, &info, &collection_bid.collection, &token_id)?;
+        Ask::new(
+            info.sender.clone(),
+            collection_bid.collection.clone(),
+            token_id.clone(),
+            details,
+            false,
+            vec![],
+            None,
+        )
+    };
+
+    let config: Config<Addr> = CONFIG.load(deps.storage)?;
+    let response = finalize_sale(
+        deps,
+        &env,
+        &ask,
+        &config,
+        &MatchingBid::CollectionBid(collection_bid),
+        false,
+        "accept-collection-bid",
+        Response::new(),
+    )?;
+
+    Ok(response)
+}
+
+pub fn execute_sweep_collection(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    max_items: u32,
+    max_price_per_item: Coin,
+    max_total: Coin,
+) -> Result<Response, ContractError> {
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // check agains collection denom
+    only_valid_price(
+        deps.storage,
+        &config,
+        &collection,
+        &max_price_per_item,
+        None,
+    )?;
+    ensure_eq!(
+        max_total.denom,
+        max_price_per_item.denom,
+        ContractError::InvalidInput("invalid denom".to_string())
+    );
+    // Sweeps are paid with native funds, collections priced in cw20 tokens cannot be swept
+    ensure!(
+        !is_cw20_denom(&max_price_per_item.denom),
+        ContractError::InvalidInput("sweeps are only supported in native denoms".to_string())
+    );
+    ensure!(
+        max_items > 0 && max_items <= MAX_SWEEP_ITEMS,
+        ContractError::InvalidInput(format!(
+            "max items must be between 1 and {}",
+            MAX_SWEEP_ITEMS
+        ))
+    );
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    // Walk the asks from the cheapest current price up, until either limit is reached
+    let mut total = coin(0, &max_price_per_item.denom);
+    let mut sweep_asks: Vec<Ask> = vec![];
+    for ask in cheapest_asks(
+        deps.as_ref(),
+        &env.block,
+        &collection,
+        &max_price_per_item.denom,
+        Some(max_price_per_item.amount),
+        max_items as usize,
+        |ask| {
+            !ask.details.is_expired(&env.block)
+                && ask.is_allowed_buyer(&info.sender)
+                && ask.has_custody(&deps.querier, &env.contract.address)
+        },
+    )? {
+        let next_total = total.amount.checked_add(ask.details.price.amount)?;
+        if next_total > max_total.amount {
+            break;
+        }
+        total.amount = next_total;
+        sweep_asks.push(ask);
+    }
+
+    ensure!(!sweep_asks.is_empty(), ContractError::NoMatchFound);
+
+    funds = funds
+        .sub(total.clone())
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    // The sweep is executed as a collection bid that is never stored
+    let mut collection_bid = CollectionBid::new(
+        info.sender.clone(),
+        collection.clone(),
+        OrderDetails {
+            price: max_price_per_item,
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        sweep_asks.len() as u32,
+        env.block.height,
+        nonce,
+    );
+
+    let mut response = Response::new();
+    for ask in sweep_asks.iter() {
+        response = finalize_sale(
+            deps.branch(),
+            &env,
+            ask,
+            &config,
+            &MatchingBid::CollectionBid(collection_bid.clone()),
+            true,
+            "sweep-collection",
+            response,
+        )?;
+        collection_bid.quantity -= 1;
+    }
+
+    response = response.add_event(
+        SweepEvent {
+            ty: "sweep-collection",
+            collection: collection.as_str(),
+            num_items: sweep_asks.len() as u32,
+            total: &total,
+        }
+        .into(),
+    );
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_set_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    trait_type: String,
+    trait_value: String,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // check agains collection denom
+    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
+
+    ensure!(
+        !trait_type.is_empty() && !trait_value.is_empty(),
+        ContractError::InvalidInput("trait type and value must be set".to_string())
+    );
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    let trait_bid = TraitBid::new(
+        info.sender.clone(),
+        collection,
+        trait_type,
+        trait_value,
+        details,
+        env.block.height,
+        nonce,
+    );
+
+    // Trait bids are matched when a matching ask is set or when the bid is accepted
+    funds = funds
+        .sub(trait_bid.escrowed_funds()?)
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    let mut response = Response::new().add_event(
+        TraitBidEvent {
+            ty: "set-trait-bid",
+            trait_bid: &trait_bid,
+            attr_keys: vec![
+                "id",
+                "creator",
+                "collection",
+                "trait_type",
+                "trait_value",
+                "price",
+                "recipient",
+                "finder",
+                "expires",
+            ],
+        }
+        .into(),
+    );
+
+    ensure!(
+        !trait_bids().has(deps.storage, trait_bid.id.clone()),
+        ContractError::InternalError("trait bid id collision".to_string())
+    );
+    trait_bid.save(deps.storage)?;
+
+    response = response.add_submessages(prepare_trait_bid_hook(
+        deps.storage,
+        &trait_bid,
+        HookAction::Create,
+    )?);
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_update_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut trait_bid = trait_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("trait bid not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        trait_bid.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of trait bid can perform this action".to_string()
+        )
+    );
+    only_unpaused(
+        deps.storage,
+        Some(&trait_bid.collection),
+        PausableOperation::Orders,
+    )?;
+    only_listed_collection(deps.storage, &trait_bid.collection)?;
+
+    // check agains collection denom
+    only_valid_price(
+        deps.storage,
+        &config,
+        &trait_bid.collection,
+        &details.price,
+        None,
+    )?;
+    only_valid_expiration(&env.block, &details.expires)?;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    // Add the previously escrowed funds to the funds in context
+    funds = funds.add(trait_bid.escrowed_funds()?);
+
+    trait_bid.details = details;
+
+    funds = funds
+        .sub(trait_bid.escrowed_funds()?)
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    trait_bid.save(deps.storage)?;
+
+    let mut response = Response::new().add_event(
+        TraitBidEvent {
+            ty: "update-trait-bid",
+            trait_bid: &trait_bid,
+            attr_keys: vec![
+                "id",
+                "collection",
+                "trait_type",
+                "trait_value",
+                "price",
+                "creator",
+                "recipient",
+                "finder",
+                "expires",
+            ],
+        }
+        .into(),
+    );
+
+    response = response.add_submessages(prepare_trait_bid_hook(
+        deps.storage,
+        &trait_bid,
+        HookAction::Update,
+    )?);
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_remove_trait_bid(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let trait_bid = trait_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("trait bid not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        trait_bid.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of trait bid can perform this action".to_string()
+        )
+    );
+
+    let refund = trait_bid.escrowed_funds()?;
+
+    trait_bid.remove(deps.storage)?;
+
+    let mut response = Response::new().add_submessages(prepare_trait_bid_hook(
+        deps.storage,
+        &trait_bid,
+        HookAction::Delete,
+    )?);
+    response = transfer_asset(deps.api, refund, &info.sender, response)?;
+
+    response = response.add_event(
+        TraitBidEvent {
+            ty: "remove-trait-bid",
+            trait_bid: &trait_bid,
+            attr_keys: vec!["id", "collection", "trait_type", "trait_value"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    token_id: TokenId,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    let trait_bid = trait_bids()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("trait bid not found [{}]", id)))?;
+
+    ensure!(
+        !trait_bid.details.is_expired(&env.block),
+        ContractError::InvalidInput("trait bid is expired".to_string())
+    );
+
+    ensure!(
+        has_coins(&[trait_bid.details.price.clone()], &details.price),
+        ContractError::InvalidInput("min output is greater than trait bid price".to_string())
+    );
+
+    // The token must carry the trait in its on-chain metadata
+    only_trait(
+        &deps.querier,
+        &trait_bid.collection,
+        &token_id,
+        &trait_bid.trait_type,
+        &trait_bid.trait_value,
+    )?;
+
+    let ask_id = generate_id(vec![trait_bid.collection.as_bytes(), token_id.as_bytes()]);
+    let ask_option = asks().may_load(deps.storage, ask_id.clone())?;
+
+    // Check if the sender is the owner of the NFT, or if the creator of a valid ask
+    let ask = if let Some(ask) = ask_option {
+        if info.sender != ask.creator {
+            Err(MarketplaceStdError::Unauthorized(
+                "sender is not creator of ask".to_string(),
+            ))?;
+        }
+        ask
+    } else {
+        only_owner(&deps.querier, &info, &trait_bid.collection, &token_id)?;
+        Ask::new(
+            info.sender.clone(),
+            trait_bid.collection.clone(),
+            token_id.clone(),
+            details,
+            false,
+            vec![],
+            None,
+        )
+    };
+
+    let config: Config<Addr> = CONFIG.load(deps.storage)?;
+    let response = finalize_sale(
+        deps,
+        &env,
+        &ask,
+        &config,
+        &MatchingBid::TraitBid(trait_bid),
+        false,
+        "accept-trait-bid",
+        Response::new(),
+    )?;
+
+    Ok(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    reserve_price: Coin,
+    end_time: Timestamp,
+    recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+    only_owner(&deps.querier, &info, &collection, &token_id)?;
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    // check agains collection denom
+    only_valid_price(deps.storage, &config, &collection, &reserve_price, None)?;
+    only_valid_auction_end_time(&env.block, &end_time)?;
+
+    let mut response = collect_listing_fee(deps.as_ref(), &info, &config, Response::new())?;
+
+    let auction = Auction::new(
+        info.sender.clone(),
+        collection,
+        token_id,
+        reserve_price,
+        recipient,
+        end_time,
+    );
+
+    response = transfer_nft(
+        &auction.collection,
+        &auction.token_id,
+        &env.contract.address,
+        response,
+    );
+
+    response = response.add_event(
+        AuctionEvent {
+            ty: "set-auction",
+            auction: &auction,
+            attr_keys: vec![
+                "id",
+                "creator",
+                "collection",
+                "token_id",
+                "reserve_price",
+                "recipient",
+                "end_time",
+            ],
+        }
+        .into(),
+    );
+
+    auctions().update(
+        deps.storage,
+        auction.id.clone(),
+        |existing| match existing {
+            Some(_) => Err(ContractError::InternalError(
+                "auction id collision".to_string(),
+            )),
+            None => Ok(auction),
+        },
+    )?;
+
+    Ok(response)
+}
+
+pub fn execute_remove_auction(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let auction = auctions()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("auction not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        auction.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of auction can perform this action".to_string()
+        )
+    );
+    ensure!(
+        auction.high_bid.is_none(),
+        ContractError::InvalidInput("auction has bids".to_string())
+    );
+
+    auction.remove(deps.storage)?;
+
+    let mut response = transfer_nft(
+        &auction.collection,
+        &auction.token_id,
+        &auction.asset_recipient(),
+        Response::new(),
+    );
+
+    response = response.add_event(
+        AuctionEvent {
+            ty: "remove-auction",
+            auction: &auction,
+            attr_keys: vec!["id", "collection", "token_id"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_bid_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    finder: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let mut auction = auctions()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("auction not found [{}]", id)))?;
+
+    only_unpaused(
+        deps.storage,
+        Some(&auction.collection),
+        PausableOperation::Orders,
+    )?;
+    only_listed_collection(deps.storage, &auction.collection)?;
+    ensure!(
+        !auction.is_ended(&env.block),
+        ContractError::InvalidInput("auction has ended".to_string())
+    );
+    ensure!(
+        info.sender != auction.creator,
+        ContractError::InvalidInput("auction creator cannot bid".to_string())
+    );
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    // The bid is the amount sent in the denom of the auction, other funds are refunded
+    let min_bid_price = auction.min_bid_price(&config);
+    let price = funds
+        .0
+        .iter()
+        .find(|coin| coin.denom == min_bid_price.denom)
+        .cloned()
+        .ok_or_else(|| ContractError::InvalidInput("invalid denom".to_string()))?;
+    ensure!(
+        price.amount >= min_bid_price.amount,
+        ContractError::InvalidInput(format!("bid must be at least {}", min_bid_price))
+    );
+    funds = funds
+        .sub(price.clone())
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    // The previous highest bid is refunded
+    let mut response = Response::new();
+    if let Some(high_bid) = auction.high_bid.take() {
+        response = transfer_asset(deps.api, high_bid.price, &high_bid.bidder, response)?;
+    }
+    auction.high_bid = Some(AuctionBid {
+        bidder: info.sender.clone(),
+        price,
+        finder,
+    });
+
+    // Bids close to the end extend the auction so that other bidders can respond
+    let min_end_time = env
+        .block
+        .time
+        .plus_seconds(config.auction_extension_seconds);
+    if auction.end_time < min_end_time {
+        auction.end_time = min_end_time;
+    }
+
+    auction.save(deps.storage)?;
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    response = response.add_event(
+        AuctionEvent {
+            ty: "bid-auction",
+            auction: &auction,
+            attr_keys: vec![
+                "id",
+                "collection",
+                "token_id",
+                "bidder",
+                "price",
+                "end_time",
+            ],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_settle_auction(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    let api = deps.api;
+
+    nonpayable(&info)?;
+
+    let auction = auctions()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("auction not found [{}]", id)))?;
+
+    ensure!(
+        auction.is_ended(&env.block),
+        ContractError::InvalidInput("auction has not ended".to_string())
+    );
+
+    // Auctions of collections that can no longer be traded are unwound
+    let is_listed = only_listed_collection(deps.storage, &auction.collection).is_ok();
+
+    // While sales are paused the seller or the highest bidder can unwind the auction instead,
+    // so that the escrowed NFT and bid are not locked until sales resume
+    let is_sales_paused = is_paused(
+        deps.storage,
+        Some(&auction.collection),
+        PausableOperation::Sales,
+    )?;
+    if let Some(high_bid) = &auction.high_bid {
+        ensure!(
+            !is_listed
+                || !is_sales_paused
+                || info.sender == auction.creator
+                || info.sender == high_bid.bidder,
+            ContractError::Paused(PausableOperation::Sales.to_string())
+        );
+    }
+
+    auction.remove(deps.storage)?;
+
+    let mut response = Response::new().add_event(
+        AuctionEvent {
+            ty: "settle-auction",
+            auction: &auction,
+            attr_keys: vec!["id", "collection", "token_id", "bidder", "price"],
+        }
+        .into(),
+    );
+
+    match auction.high_bid.clone() {
+        Some(high_bid) if is_listed && !is_sales_paused => {
+            let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+            NONCE.save(deps.storage, &nonce)?;
+
+            // The auction is settled as an ask matched by a bid at the highest bid price
+            let ask = Ask::new(
+                auction.creator.clone(),
+                auction.collection.clone(),
+                auction.token_id.clone(),
+                OrderDetails {
+                    price: high_bid.price.clone(),
+                    recipient: auction.recipient.clone(),
+                    finder: None,
+                    expires: None,
+                    royalty_tip: None,
+                },
+                false,
+                vec![],
+                None,
+            );
+            let bid = Bid::new(
+                high_bid.bidder,
+                auction.collection,
+                auction.token_id,
+                OrderDetails {
+                    price: high_bid.price,
+                    recipient: None,
+                    finder: high_bid.finder,
+                    expires: None,
+                    royalty_tip: None,
+                },
+                env.block.height,
+                nonce,
+            );
+
+            let config = CONFIG.load(deps.storage)?;
+            response = finalize_sale(
+                deps,
+                &env,
+                &ask,
+                &config,
+                &MatchingBid::Bid(bid),
+                true,
+                "settle-auction",
+                response,
+            )?;
+        }
+        high_bid => {
+            // Without a sale the NFT is returned and the highest bid refunded
+            response = transfer_nft(
+                &auction.collection,
+                &auction.token_id,
+                &auction.asset_recipient(),
+                response,
+            );
+            if let Some(high_bid) = high_bid {
+                response = transfer_asset(api, high_bid.price, &high_bid.bidder, response)?;
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn execute_set_bundle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    items: Vec<BundleItem<Addr>>,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    only_valid_bundle_items(&items)?;
+    only_without_royalty_tip(&details)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    for item in &items {
+        only_owner(&deps.querier, &info, &item.collection, &item.token_id)?;
+        only_tradable(&deps.querier, &env.block, &item.collection)?;
+        only_listed_collection(deps.storage, &item.collection)?;
+        only_unpaused(
+            deps.storage,
+            Some(&item.collection),
+            PausableOperation::Orders,
+        )?;
+        // the bundle price must be valid for every collection in the bundle
+        only_valid_price(
+            deps.storage,
+            &config,
+            &item.collection,
+            &details.price,
+            None,
+        )?;
+    }
+    only_valid_expiration(&env.block, &details.expires)?;
+
+    let mut response = collect_listing_fee(deps.as_ref(), &info, &config, Response::new())?;
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    let bundle = Bundle::new(info.sender.clone(), items, details, env.block.height, nonce);
+
+    for item in &bundle.items {
+        response = transfer_nft(
+            &item.collection,
+            &item.token_id,
+            &env.contract.address,
+            response,
+        );
+    }
+
+    response = response.add_event(
+        BundleEvent {
+            ty: "set-bundle",
+            bundle: &bundle,
+            attr_keys: vec![
+                "id",
+                "creator",
+                "items",
+                "price",
+                "recipient",
+                "finder",
+                "expires",
+            ],
+        }
+        .into(),
+    );
+
+    bundles().update(deps.storage, bundle.id.clone(), |existing| match existing {
+        Some(_) => Err(ContractError::InternalError(
+            "bundle id collision".to_string(),
+        )),
+        None => Ok(bundle),
+    })?;
+
+    Ok(response)
+}
+
+pub fn execute_remove_bundle(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let bundle = bundles()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("bundle not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        bundle.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of bundle can perform this action".to_string()
+        )
+    );
+
+    bundle.remove(deps.storage)?;
+
+    let mut response = Response::new();
+    for item in &bundle.items {
+        response = transfer_nft(
+            &item.collection,
+            &item.token_id,
+            &bundle.asset_recipient(),
+            response,
+        );
+    }
+
+    response = response.add_event(
+        BundleEvent {
+            ty: "remove-bundle",
+            bundle: &bundle,
+            attr_keys: vec!["id", "items"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_bundle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    details: OrderDetails<Addr>,
+) -> Result<Response, ContractError> {
+    let api = deps.api;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let bundle = bundles()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("bundle not found [{}]", id)))?;
+
+    ensure!(
+        !bundle.details.is_expired(&env.block),
+        ContractError::InvalidInput("bundle is expired".to_string())
+    );
+
+    ensure!(
+        has_coins(&[details.price.clone()], &bundle.details.price),
+        ContractError::InvalidInput("bundle price is greater than max input".to_string())
+    );
+    only_without_royalty_tip(&details)?;
+
+    funds = funds
+        .sub(bundle.details.price.clone())
+        .map_err(|_| ContractError::InsufficientFunds)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = finalize_bundle_sale(
+        deps,
+        &env,
+        &bundle,
+        &config,
+        &info.sender,
+        &details,
+        "accept-bundle",
+        Response::new(),
+    )?;
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offered_nfts: Vec<SwapNft<Addr>>,
+    requested_nfts: Vec<SwapRequest<Addr>>,
+    requested_funds: Option<Coin>,
+    recipient: Option<Addr>,
+    expires: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    ensure!(
+        info.funds.len() <= 1,
+        ContractError::InvalidInput("swap can offer at most one coin".to_string())
+    );
+
+    let nonce = NONCE.load(deps.storage)?.wrapping_add(1);
+    NONCE.save(deps.storage, &nonce)?;
+
+    let swap = Swap::new(
+        info.sender.clone(),
+        offered_nfts,
+        info.funds.first().cloned(),
+        requested_nfts,
+        requested_funds,
+        recipient,
+        expires,
+        env.block.height,
+        nonce,
+    );
+
+    only_valid_swap(&swap)?;
+    only_valid_expiration(&env.block, &swap.expires)?;
+    for nft in &swap.offered_nfts {
+        only_owner(&deps.querier, &info, &nft.collection, &nft.token_id)?;
+        only_tradable(&deps.querier, &env.block, &nft.collection)?;
+        only_listed_collection(deps.storage, &nft.collection)?;
+        only_unpaused(
+            deps.storage,
+            Some(&nft.collection),
+            PausableOperation::Orders,
+        )?;
+    }
+    for request in &swap.requested_nfts {
+        only_listed_collection(deps.storage, request.collection())?;
+    }
+
+    let mut response = Response::new();
+    for nft in &swap.offered_nfts {
+        response = transfer_nft(
+            &nft.collection,
+            &nft.token_id,
+            &env.contract.address,
+            response,
+        );
+    }
+
+    response = response.add_event(
+        SwapEvent {
+            ty: "set-swap",
+            swap: &swap,
+            attr_keys: vec![
+                "id",
+                "creator",
+                "offered_nfts",
+                "offered_funds",
+                "requested_nfts",
+                "requested_funds",
+                "recipient",
+                "expires",
+            ],
+        }
+        .into(),
+    );
+
+    swaps().update(deps.storage, swap.id.clone(), |existing| match existing {
+        Some(_) => Err(ContractError::InternalError(
+            "swap id collision".to_string(),
+        )),
+        None => Ok(swap),
+    })?;
+
+    Ok(response)
+}
+
+pub fn execute_remove_swap(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: OrderId,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let swap = swaps()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("swap not found [{}]", id)))?;
+
+    ensure_eq!(
+        info.sender,
+        swap.creator,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of swap can perform this action".to_string()
+        )
+    );
+
+    swap.remove(deps.storage)?;
+
+    // Escrowed NFTs and funds are returned to the creator
+    let mut response = Response::new();
+    for nft in &swap.offered_nfts {
+        response = transfer_nft(
+            &nft.collection,
+            &nft.token_id,
+            &swap.asset_recipient(),
+            response,
+        );
+    }
+    if let Some(offered_funds) = &swap.offered_funds {
+        response = transfer_asset(deps.api, offered_funds.clone(), &swap.creator, response)?;
+    }
+
+    response = response.add_event(
+        SwapEvent {
+            ty: "remove-swap",
+            swap: &swap,
+            attr_keys: vec!["id", "offered_nfts"],
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_accept_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: OrderId,
+    nfts: Vec<SwapNft<Addr>>,
+    recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let api = deps.api;
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+
+    let swap = swaps()
+        .load(deps.storage, id.clone())
+        .map_err(|_| ContractError::InvalidInput(format!("swap not found [{}]", id)))?;
+
+    ensure!(
+        !swap.is_expired(&env.block),
+        ContractError::InvalidInput("swap is expired".to_string())
+    );
+    ensure!(
+        info.sender != swap.creator,
+        ContractError::InvalidInput("swap creator cannot accept the swap".to_string())
+    );
+    ensure_eq!(
+        nfts.len(),
+        swap.requested_nfts.len(),
+        ContractError::InvalidInput("number of nfts does not match the swap".to_string())
+    );
+
+    for (idx, (nft, request)) in nfts.iter().zip(swap.requested_nfts.iter()).enumerate() {
+        ensure!(
+            request.is_fulfilled_by(nft),
+            ContractError::InvalidInput(format!("nft does not match requested nft [{}]", request))
+        );
+        ensure!(
+            !nfts[..idx].contains(nft),
+            ContractError::InvalidInput(format!("duplicate nft [{}]", nft))
+        );
+        only_owner(&deps.querier, &info, &nft.collection, &nft.token_id)?;
+        only_tradable(&deps.querier, &env.block, &nft.collection)?;
+        only_listed_collection(deps.storage, &nft.collection)?;
+    }
+
+    if let Some(requested_funds) = &swap.requested_funds {
+        funds = funds
+            .sub(requested_funds.clone())
+            .map_err(|_| ContractError::InsufficientFunds)?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let taker_recipient = address_or(recipient.as_ref(), &info.sender);
+    let mut response = finalize_swap(
+        deps,
+        &env,
+        &swap,
+        &config,
+        &info.sender,
+        &nfts,
+        &taker_recipient,
+        Response::new(),
+    )?;
+
+    // Transfer remaining funds back to user
+    if !funds.is_empty() {
+        response = transfer_assets(api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    denoms: Vec<Denom>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // Claimable balances are kept per denom rather than per collection,
+    // so claims can only be paused marketplace wide
+    only_unpaused(deps.storage, None, PausableOperation::Withdrawals)?;
+
+    let mut claimed = NativeBalance(vec![]);
+    for denom in denoms {
+        let key = (info.sender.clone(), denom.clone());
+        if let Some(balance) = CLAIMABLE_BALANCES.may_load(deps.storage, key.clone())? {
+            CLAIMABLE_BALANCES.remove(deps.storage, key);
+            claimed += coin(balance.u128(), denom);
+        }
+    }
+    claimed.normalize();
+    ensure!(
+        !claimed.is_empty(),
+        ContractError::InvalidInput("no claimable balance in the given denoms".to_string())
+    );
+
+    let claimed = claimed.into_vec();
+    let mut response = transfer_assets(deps.api, claimed.clone(), &info.sender, Response::new())?;
+    response = response.add_event(
+        ClaimEvent {
+            ty: "claim",
+            claimant: info.sender.as_ref(),
+            funds: &claimed,
+        }
+        .into(),
+    );
+
+    Ok(response)
+}
+
+pub fn execute_remove_expired_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let removal_reward = Decimal::bps(config.removal_reward_bps);
+
+    // Orders are indexed by expiration in seconds, anything below the next second may be expired
+    let max_expiration = Some(Bound::exclusive((
+        env.block.time.seconds() + 1,
+        "".to_string(),
+    )));
+    let mut remaining = limit as usize;
+
+    let mut response = Response::new();
+    let mut rewards = NativeBalance(vec![]);
+
+    // Expired asks return the escrowed NFT, no reward is paid out
+    let expired_asks = asks()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration.clone(), Order::Ascending)
+        .map(|res| res.map(|(_, ask)| ask))
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |ask| ask.details.is_expired(&env.block))
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_asks.len();
+
+    for ask in expired_asks {
+        ask.remove(deps.storage)?;
+        response =
+            response.add_submessages(prepare_ask_hook(deps.storage, &ask, HookAction::Delete)?);
+        if !ask.non_custodial {
+            response = transfer_nft(
+                &ask.collection,
+                &ask.token_id,
+                &ask.asset_recipient(),
+                response,
+            );
+        }
+        response = response.add_event(
+            AskEvent {
+                ty: "remove-expired-ask",
+                ask: &ask,
+                attr_keys: vec!["id", "collection", "token_id", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    // Expired bids are refunded to the creator minus the removal reward
+    let expired_bids = bids()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration.clone(), Order::Ascending)
+        .map(|res| res.map(|(_, bid)| bid))
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |bid| bid.details.is_expired(&env.block))
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_bids.len();
+
+    for bid in expired_bids {
+        bid.remove(deps.storage)?;
+        response =
+            response.add_submessages(prepare_bid_hook(deps.storage, &bid, HookAction::Delete)?);
+        let (refund, reward) = split_removal_reward(
+            &bid.escrowed_funds()?,
+            removal_reward_for(deps.storage, &bid.collection, removal_reward)?,
+        );
+        if !refund.amount.is_zero() {
+            response = transfer_asset(deps.api, refund, &bid.creator, response)?;
+        }
+        if !reward.amount.is_zero() {
+            rewards = rewards.add(reward);
+        }
+        response = response.add_event(
+            BidEvent {
+                ty: "remove-expired-bid",
+                bid: &bid,
+                attr_keys: vec!["id", "collection", "token_id", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    // Expired collection bids are refunded to the creator minus the removal reward
+    let expired_collection_bids = collection_bids()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration.clone(), Order::Ascending)
+        .map(|res| res.map(|(_, collection_bid)| collection_bid))
+        .filter(|res| {
+            res.as_ref().map_or(true, |collection_bid| {
+                collection_bid.details.is_expired(&env.block)
+            })
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_collection_bids.len();
+
+    for collection_bid in expired_collection_bids {
+        collection_bid.remove(deps.storage)?;
+        response = response.add_submessages(prepare_collection_bid_hook(
+            deps.storage,
+            &collection_bid,
+            HookAction::Delete,
+        )?);
+        let (refund, reward) = split_removal_reward(
+            &collection_bid.escrowed_funds()?,
+            removal_reward_for(deps.storage, &collection_bid.collection, removal_reward)?,
+        );
+        if !refund.amount.is_zero() {
+            response = transfer_asset(deps.api, refund, &collection_bid.creator, response)?;
+        }
+        if !reward.amount.is_zero() {
+            rewards = rewards.add(reward);
+        }
+        response = response.add_event(
+            CollectionBidEvent {
+                ty: "remove-expired-collection-bid",
+                collection_bid: &collection_bid,
+                attr_keys: vec!["id", "collection", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    // Expired trait bids are refunded to the creator minus the removal reward
+    let expired_trait_bids = trait_bids()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration.clone(), Order::Ascending)
+        .map(|res| res.map(|(_, trait_bid)| trait_bid))
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |trait_bid| trait_bid.details.is_expired(&env.block))
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_trait_bids.len();
+
+    for trait_bid in expired_trait_bids {
+        trait_bid.remove(deps.storage)?;
+        response = response.add_submessages(prepare_trait_bid_hook(
+            deps.storage,
+            &trait_bid,
+            HookAction::Delete,
+        )?);
+        let (refund, reward) = split_removal_reward(
+            &trait_bid.escrowed_funds()?,
+            removal_reward_for(deps.storage, &trait_bid.collection, removal_reward)?,
+        );
+        if !refund.amount.is_zero() {
+            response = transfer_asset(deps.api, refund, &trait_bid.creator, response)?;
+        }
+        if !reward.amount.is_zero() {
+            rewards = rewards.add(reward);
+        }
+        response = response.add_event(
+            TraitBidEvent {
+                ty: "remove-expired-trait-bid",
+                trait_bid: &trait_bid,
+                attr_keys: vec!["id", "collection", "trait_type", "trait_value", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    // Expired bundles return the escrowed NFTs, no reward is paid out
+    let expired_bundles = bundles()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration.clone(), Order::Ascending)
+        .map(|res| res.map(|(_, bundle)| bundle))
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |bundle| bundle.details.is_expired(&env.block))
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_bundles.len();
+
+    for bundle in expired_bundles {
+        bundle.remove(deps.storage)?;
+        for item in &bundle.items {
+            response = transfer_nft(
+                &item.collection,
+                &item.token_id,
+                &bundle.asset_recipient(),
+                response,
+            );
+        }
+        response = response.add_event(
+            BundleEvent {
+                ty: "remove-expired-bundle",
+                bundle: &bundle,
+                attr_keys: vec!["id", "items", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    // Expired swaps return the escrowed NFTs and funds, no reward is paid out
+    let expired_swaps = swaps()
+        .idx
+        .expiration
+        .range(deps.storage, None, max_expiration, Order::Ascending)
+        .map(|res| res.map(|(_, swap)| swap))
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |swap| swap.is_expired(&env.block))
+        })
+        .take(remaining)
+        .collect::<StdResult<Vec<_>>>()?;
+    remaining -= expired_swaps.len();
+
+    for swap in expired_swaps {
+        swap.remove(deps.storage)?;
+        for nft in &swap.offered_nfts {
+            response = transfer_nft(
+                &nft.collection,
+                &nft.token_id,
+                &swap.asset_recipient(),
+                response,
+            );
+        }
+        if let Some(offered_funds) = &swap.offered_funds {
+            response = transfer_asset(deps.api, offered_funds.clone(), &swap.creator, response)?;
+        }
+        response = response.add_event(
+            SwapEvent {
+                ty: "remove-expired-swap",
+                swap: &swap,
+                attr_keys: vec!["id", "offered_nfts", "expires"],
+            }
+            .into(),
+        );
+    }
+
+    ensure!(
+        remaining < limit as usize,
+        ContractError::InvalidInput("no expired orders found".to_string())
+    );
+
+    rewards.normalize();
+    if !rewards.is_empty() {
+        response = transfer_assets(deps.api, rewards.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
+
+fn validate_addrs(api: &dyn Api, addrs: Vec<String>) -> StdResult<Vec<Addr>> {
+    addrs.iter().map(|addr| api.addr_validate(addr)).collect()
+}
+
+/// No removal reward is paid out while withdrawals are paused, the bid is refunded in full
+fn removal_reward_for(
+    storage: &dyn Storage,
+    collection: &Addr,
+    removal_reward: Decimal,
+) -> StdResult<Decimal> {
+    if is_paused(storage, Some(collection), PausableOperation::Withdrawals)? {
+        Ok(Decimal::zero())
+    } else {
+        Ok(removal_reward)
+    }
+}
+
+/// Splits the escrowed price of an expired bid into the refund and the removal reward
+fn split_removal_reward(price: &Coin, removal_reward: Decimal) -> (Coin, Coin) {
+    let reward_amount = price.amount.mul_floor(removal_reward);
+    (
+        coin((price.amount - reward_amount).u128(), &price.denom),
+        coin(reward_amount.u128(), &price.denom),
+    )
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let api = deps.api;
+
+    // The received tokens are accounted for as a coin with the cw20 denom of the sending
+    // contract, orders are only valid if that denom matches the collection denom
+    let info = MessageInfo {
+        sender: api.addr_validate(&msg.sender)?,
+        funds: vec![coin(msg.amount.u128(), Asset::Cw20(info.sender).denom())],
+    };
+
+    match from_json(&msg.msg)? {
+        ReceiveMsg::SetBid {
+            collection,
+            token_id,
+            details,
+        } => execute_set_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            details.str_to_addr(api)?,
+            false,
+        ),
+        ReceiveMsg::SetCollectionBid {
+            collection,
+            details,
+            quantity,
+        } => execute_set_collection_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            details.str_to_addr(api)?,
+            quantity.unwrap_or(1),
+            false,
+        ),
+        ReceiveMsg::AcceptAsk { id, details } => {
+            execute_accept_ask(deps, env, info, id, details.str_to_addr(api)?)
+        }
+        ReceiveMsg::BuySpecificNft {
+            collection,
+            token_id,
+            details,
+        } => execute_set_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            details.str_to_addr(api)?,
+            true,
+        ),
+        ReceiveMsg::BuyCollectionNft {
+            collection,
+            details,
+        } => execute_set_collection_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            details.str_to_addr(api)?,
+            1,
+            true,
+        ),
+        ReceiveMsg::BidAuction { id, finder } => {
+            execute_bid_auction(deps, env, info, id, maybe_addr(api, finder)?)
+        }
+        ReceiveMsg::AcceptCounterOffer { bid_id } => {
+            execute_accept_counter_offer(deps, env, info, bid_id)
+        }
+        ReceiveMsg::AcceptBundle { id, details } => {
+            execute_accept_bundle(deps, env, info, id, details.str_to_addr(api)?)
+        }
+        ReceiveMsg::SetSwap {
+            offered_nfts,
+            requested_nfts,
+            requested_funds,
+            recipient,
+            expires,
+        } => execute_set_swap(
+            deps,
+            env,
+            info,
+            offered_nfts
+                .into_iter()
+                .map(|nft| nft.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            requested_nfts
+                .into_iter()
+                .map(|request| request.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            requested_funds,
+            maybe_addr(api, recipient)?,
+            expires,
+        ),
+        ReceiveMsg::AcceptSwap {
+            id,
+            nfts,
+            recipient,
+        } => execute_accept_swap(
+            deps,
+            env,
+            info,
+            id,
+            nfts.into_iter()
+                .map(|nft| nft.str_to_addr(api))
+                .collect::<StdResult<Vec<_>>>()?,
+            maybe_addr(api, recipient)?,
+        ),
+    }
+}
+
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let api = deps.api;
+
+    // The NFT has already been transferred to the marketplace by the collection,
+    // so the sender of the NFT is the seller and no ownership check is needed
+    let collection = info.sender;
+    let seller = api.addr_validate(&msg.sender)?;
+    only_tradable(&deps.querier, &env.block, &collection)?;
+    only_listed_collection(deps.storage, &collection)?;
+
+    let (details, reserved_for, dutch_auction, sell_now) = match from_json(&msg.msg)? {
+        ReceiveNftMsg::SetAsk {
+            details,
+            reserved_for,
+            dutch_auction,
+        } => (
+            details,
+            reserved_for.unwrap_or_default(),
+            dutch_auction,
+            false,
+        ),
+        ReceiveNftMsg::SellNft { details } => (details, vec![], None, true),
+    };
+    only_unpaused(deps.storage, Some(&collection), PausableOperation::Orders)?;
+    let details = details.str_to_addr(api)?;
+    let reserved_for = validate_addrs(api, reserved_for)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    only_valid_price(deps.storage, &config, &collection, &details.price, None)?;
+    only_valid_expiration(&env.block, &details.expires)?;
+    only_valid_reserved_for(&reserved_for)?;
+    if let Some(dutch_auction) = &dutch_auction {
+        only_valid_dutch_auction(&details.price, dutch_auction)?;
+    }
+
+    let ask = Ask::new(
+        seller,
+        collection,
+        msg.token_id,
+        details,
+        false,
+        reserved_for,
+        dutch_auction,
+    );
+
+    create_ask(deps, &env, &config, ask, sell_now, true, Response::new())
+}
+
+pub fn execute_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msgs: Vec<BatchMsg>,
+    best_effort: bool,
+) -> Result<Response, ContractError> {
+    ensure!(
+        !msgs.is_empty(),
+        ContractError::InvalidInput("batch must contain at least one message".to_string())
+    );
+    ensure!(
+        msgs.len() <= MAX_BATCH_SIZE,
+        ContractError::InvalidInput(format!(
+            "batch cannot contain more than {} messages",
+            MAX_BATCH_SIZE
+        ))
+    );
+
+    // The funds sent with the batch must be exactly the funds forwarded to its messages
+    let mut msgs_funds = NativeBalance(vec![]);
+    for batch_msg in &msgs {
+        for msg_coin in &batch_msg.funds {
+            msgs_funds += msg_coin.clone();
+        }
+    }
+    msgs_funds.normalize();
+
+    let mut funds = NativeBalance(info.funds.clone());
+    funds.normalize();
+    funds = funds
+        .sub(msgs_funds.into_vec())
+        .map_err(|_| ContractError::InsufficientFunds)?;
+    ensure!(
+        funds.is_empty(),
+        ContractError::InvalidInput(
+            "batch funds must equal the sum of the funds of its messages".to_string()
+        )
+    );
+
+    let num_msgs = msgs.len();
+    let mut num_failed = 0;
+    let mut response = Response::new();
+
+    for (index, batch_msg) in msgs.into_iter().enumerate() {
+        ensure!(
+            !matches!(batch_msg.msg, ExecuteMsg::Batch { .. }),
+            ContractError::InvalidInput("batch messages cannot be nested".to_string())
+        );
+        ensure!(
+            !matches!(
+                batch_msg.msg,
+                ExecuteMsg::Receive(_) | ExecuteMsg::ReceiveNft(_)
+            ),
+            ContractError::InvalidInput("receive messages cannot be batched".to_string())
+        );
+
+        let mut msg_funds = NativeBalance(batch_msg.funds);
+        msg_funds.normalize();
+
+        let msg_info = MessageInfo {
+            sender: info.sender.clone(),
+            funds: msg_funds.clone().into_vec(),
+        };
+
+        let result = if best_effort {
+            // Storage writes are only committed when the message succeeds
+            let mut transaction = StorageTransaction::new(deps.storage);
+            let result = execute(
+                DepsMut {
+                    storage: &mut transaction,
+                    api: deps.api,
+                    querier: deps.querier,
+                },
+                env.clone(),
+                msg_info,
+                batch_msg.msg,
+            );
+            if result.is_ok() {
+                let writes = transaction.into_writes();
+                commit(deps.storage, writes);
+            }
+            result
+        } else {
+            execute(deps.branch(), env.clone(), msg_info, batch_msg.msg)
+        };
+
+        match result {
+            Ok(msg_response) => {
+                response = response
+                    .add_submessages(msg_response.messages)
+                    .add_attributes(msg_response.attributes)
+                    .add_events(msg_response.events);
+            }
+            Err(err) if best_effort => {
+                // Funds forwarded to a failed message are returned to the batch
+                funds = funds.add(msg_funds);
+                num_failed += 1;
+
+                response = response.add_event(
+                    BatchFailureEvent {
+                        ty: "batch-msg-failed",
+                        index,
+                        error: &err.to_string(),
+                    }
+                    .into(),
+                );
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    response = response.add_event(
+        BatchEvent {
+            ty: "batch",
+            num_msgs,
+            num_failed,
+        }
+        .into(),
+    );
+
+    // Transfer the funds of failed messages back to user
+    if !funds.is_empty() {
+        response = transfer_assets(deps.api, funds.into_vec(), &info.sender, response)?;
+    }
+
+    Ok(response)
+}
@@ -1,16 +1,40 @@
 use crate::{
-    orders::{Ask, MatchingBid},
-    state::{Config, TokenId, COLLECTION_DENOMS},
+    constants::{
+        MAX_AUCTION_DURATION, MAX_BASIS_POINTS, MAX_BUNDLE_ITEMS, MAX_COLLECTION_SALE_HISTORY,
+        MAX_PRICE_OBSERVATIONS, MAX_RESERVED_BUYERS, MAX_SWAP_ITEMS, MAX_TOKEN_SALE_HISTORY,
+        MIN_AUCTION_DURATION, MIN_BUNDLE_ITEMS,
+    },
+    hooks::{
+        prepare_ask_hook, prepare_bid_hook, prepare_bundle_sale_hook, prepare_collection_bid_hook,
+        prepare_sale_hook, prepare_swap_hook, prepare_trait_bid_hook,
+    },
+    msg::{BundleSaleHookMsg, HookAction, SaleHookMsg, SwapHookMsg},
+    orders::{Ask, Bundle, BundleItem, DutchAuction, MatchingBid, OrderDetails, Swap, SwapNft},
+    state::{
+        asks, bids, collection_bids, trait_bids, update_collection_stats, CollectionStatus, Config,
+        PausableOperation, PayoutMode, PriceObservation, RoyaltyPolicy, Sale, TokenId,
+        CLAIMABLE_BALANCES, COLLECTION_DENOMS, COLLECTION_FEE_OVERRIDES, COLLECTION_PAUSE_STATES,
+        COLLECTION_ROYALTIES, COLLECTION_ROYALTY_POLICIES, COLLECTION_SALES,
+        COLLECTION_SALE_COUNTS, COLLECTION_STATUSES, FINDERS, FINDER_VOLUMES, PAUSE_STATE,
+        PRICE_OBSERVATIONS, TOKEN_SALES,
+    },
     ContractError,
 };
 
 use blake2::{Blake2s256, Digest};
 use cosmwasm_std::{
-    ensure, ensure_eq, Addr, Coin, Decimal, DepsMut, Env, Event, MessageInfo, QuerierWrapper,
-    Response, Storage, Uint128,
+    ensure, ensure_eq, Addr, Api, BlockInfo, Coin, Decimal, Deps, DepsMut, Env, Event, MessageInfo,
+    QuerierWrapper, Response, StdResult, Storage, Timestamp, Uint128, Uint256,
 };
+use cw721::{ApprovalResponse, Cw721QueryMsg, NftInfoResponse, OperatorResponse, OwnerOfResponse};
+use serde::Deserialize;
 use sg_marketplace_common::{
-    nft::transfer_nft, royalties::fetch_or_set_royalties, sale::NftSaleProcessor,
+    address::address_or,
+    asset::transfer_asset,
+    nft::transfer_nft,
+    royalties::fetch_or_set_multi_royalties,
+    sale::{NftSaleProcessor, Payment},
+    types::MultiRoyaltyEntry,
     MarketplaceStdError,
 };
 use std::{cmp::min, ops::Sub};
@@ -20,6 +44,19 @@ pub fn build_collection_token_index_str(collection: &str, token_id: &TokenId) ->
     string_list.join("/")
 }
 
+pub fn build_collection_trait_index_str(
+    collection: &str,
+    trait_type: &str,
+    trait_value: &str,
+) -> String {
+    let string_list = [
+        collection.to_string(),
+        trait_type.to_string(),
+        trait_value.to_string(),
+    ];
+    string_list.join("/")
+}
+
 pub fn generate_id(components: Vec<&[u8]>) -> String {
     let mut hasher = Blake2s256::new();
     for component in components {
@@ -52,6 +89,24 @@ pub fn only_contract_admin(
     Ok(())
 }
 
+// only_collection_admin checks that the sender is the admin of the collection contract
+pub fn only_collection_admin(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    let contract_info_resp = querier.query_wasm_contract_info(collection)?;
+
+    ensure!(
+        contract_info_resp.admin.as_deref() == Some(sender.as_str()),
+        MarketplaceStdError::Unauthorized(
+            "only the admin of the collection can perform this action".to_string(),
+        )
+    );
+
+    Ok(())
+}
+
 // only_valid_price checks non zero amounts and enforces being in the collection denom or optional denom
 pub fn only_valid_price(
     storage: &dyn Storage,
@@ -73,7 +128,9 @@ pub fn only_valid_price(
         );
     } else {
         let query_result = COLLECTION_DENOMS.may_load(storage, collection.clone())?;
-        let collection_denom = query_result.unwrap_or(config.default_denom.clone());
+        let collection_denom = query_result
+            .map(|asset| asset.denom())
+            .unwrap_or(config.default_denom.clone());
         ensure_eq!(
             collection_denom,
             price.denom,
@@ -84,6 +141,316 @@ pub fn only_valid_price(
     Ok(())
 }
 
+/// An on-chain metadata attribute of an NFT, e.g. Background=Gold
+#[derive(Deserialize)]
+pub struct NftTrait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+// Only the attributes are read from the on-chain metadata, other fields are ignored
+#[derive(Deserialize)]
+struct NftTraitsExtension {
+    attributes: Option<Vec<NftTrait>>,
+}
+
+// fetch_nft_traits returns the on-chain traits of an NFT, or none if the metadata is unsupported
+pub fn fetch_nft_traits(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    token_id: &str,
+) -> Vec<NftTrait> {
+    querier
+        .query_wasm_smart::<NftInfoResponse<Option<NftTraitsExtension>>>(
+            collection,
+            &Cw721QueryMsg::NftInfo {
+                token_id: token_id.to_string(),
+            },
+        )
+        .ok()
+        .and_then(|nft_info| nft_info.extension)
+        .and_then(|extension| extension.attributes)
+        .unwrap_or_default()
+}
+
+// only_trait checks that the NFT has the trait in its on-chain metadata
+pub fn only_trait(
+    querier: &QuerierWrapper,
+    collection: &Addr,
+    token_id: &str,
+    trait_type: &str,
+    trait_value: &str,
+) -> Result<(), ContractError> {
+    ensure!(
+        fetch_nft_traits(querier, collection, token_id)
+            .iter()
+            .any(|nft_trait| nft_trait.trait_type == trait_type && nft_trait.value == trait_value),
+        ContractError::InvalidInput("token does not have the trait".to_string())
+    );
+
+    Ok(())
+}
+
+// only_without_royalty_tip checks that orders other than bids do not carry a royalty tip
+pub fn only_without_royalty_tip(details: &OrderDetails<Addr>) -> Result<(), ContractError> {
+    ensure!(
+        details.royalty_tip.is_none(),
+        ContractError::InvalidInput("royalty tips are only supported on bids".to_string())
+    );
+
+    Ok(())
+}
+
+// only_valid_expiration checks that an optional order expiration is in the future
+pub fn only_valid_expiration(
+    block: &BlockInfo,
+    expires: &Option<Timestamp>,
+) -> Result<(), ContractError> {
+    if let Some(expires) = expires {
+        ensure!(
+            *expires > block.time,
+            ContractError::InvalidInput("order expiration must be in the future".to_string())
+        );
+    }
+
+    Ok(())
+}
+
+// only_valid_quantity checks that a collection bid is for at least one NFT
+pub fn only_valid_quantity(quantity: u32) -> Result<(), ContractError> {
+    ensure!(
+        quantity > 0,
+        ContractError::InvalidInput("quantity must be greater than 0".to_string())
+    );
+
+    Ok(())
+}
+
+// only_valid_reserved_for checks that the allowed buyers of an ask are within limits
+pub fn only_valid_reserved_for(reserved_for: &[Addr]) -> Result<(), ContractError> {
+    ensure!(
+        reserved_for.len() <= MAX_RESERVED_BUYERS,
+        ContractError::InvalidInput(format!(
+            "ask cannot be reserved for more than {} buyers",
+            MAX_RESERVED_BUYERS
+        ))
+    );
+
+    Ok(())
+}
+
+// only_valid_dutch_auction checks that the price declines from the ask price to a positive
+// end price over a non empty period
+pub fn only_valid_dutch_auction(
+    price: &Coin,
+    dutch_auction: &DutchAuction,
+) -> Result<(), ContractError> {
+    ensure!(
+        dutch_auction.end_price > Uint128::zero(),
+        ContractError::InvalidInput("end price must be greater than 0".to_string())
+    );
+    ensure!(
+        dutch_auction.end_price < price.amount,
+        ContractError::InvalidInput("end price must be less than the ask price".to_string())
+    );
+    ensure!(
+        dutch_auction.start_time < dutch_auction.end_time,
+        ContractError::InvalidInput("start_time must be before end_time".to_string())
+    );
+
+    Ok(())
+}
+
+// only_valid_auction_end_time checks that the duration of an auction is within limits
+pub fn only_valid_auction_end_time(
+    block: &BlockInfo,
+    end_time: &Timestamp,
+) -> Result<(), ContractError> {
+    ensure!(
+        *end_time >= block.time.plus_seconds(MIN_AUCTION_DURATION)
+            && *end_time <= block.time.plus_seconds(MAX_AUCTION_DURATION),
+        ContractError::InvalidInput(format!(
+            "auction duration must be between {} and {} seconds",
+            MIN_AUCTION_DURATION, MAX_AUCTION_DURATION
+        ))
+    );
+
+    Ok(())
+}
+
+// only_valid_bundle_items checks that a bundle holds a limited number of distinct NFTs
+// with positive royalty weights
+pub fn only_valid_bundle_items(items: &[BundleItem<Addr>]) -> Result<(), ContractError> {
+    ensure!(
+        items.len() >= MIN_BUNDLE_ITEMS && items.len() <= MAX_BUNDLE_ITEMS,
+        ContractError::InvalidInput(format!(
+            "number of bundle items must be between {} and {}",
+            MIN_BUNDLE_ITEMS, MAX_BUNDLE_ITEMS
+        ))
+    );
+    for (idx, item) in items.iter().enumerate() {
+        ensure!(
+            item.royalty_weight > 0,
+            ContractError::InvalidInput("royalty weight must be greater than 0".to_string())
+        );
+        ensure!(
+            !items[..idx]
+                .iter()
+                .any(|other| other.collection == item.collection && other.token_id == item.token_id),
+            ContractError::InvalidInput(format!(
+                "duplicate bundle item [{}/{}]",
+                item.collection, item.token_id
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+// only_valid_swap checks that both sides of a swap exchange a limited number of NFTs
+// and that funds are only exchanged in one direction
+pub fn only_valid_swap(swap: &Swap) -> Result<(), ContractError> {
+    ensure!(
+        !swap.offered_nfts.is_empty() && swap.offered_nfts.len() <= MAX_SWAP_ITEMS,
+        ContractError::InvalidInput(format!(
+            "number of offered nfts must be between 1 and {}",
+            MAX_SWAP_ITEMS
+        ))
+    );
+    ensure!(
+        !swap.requested_nfts.is_empty() && swap.requested_nfts.len() <= MAX_SWAP_ITEMS,
+        ContractError::InvalidInput(format!(
+            "number of requested nfts must be between 1 and {}",
+            MAX_SWAP_ITEMS
+        ))
+    );
+    for (idx, nft) in swap.offered_nfts.iter().enumerate() {
+        ensure!(
+            !swap.offered_nfts[..idx].contains(nft),
+            ContractError::InvalidInput(format!("duplicate offered nft [{}]", nft))
+        );
+    }
+    ensure!(
+        swap.offered_funds.is_none() || swap.requested_funds.is_none(),
+        ContractError::InvalidInput("swap cannot both offer and request funds".to_string())
+    );
+    if let Some(requested_funds) = &swap.requested_funds {
+        ensure!(
+            !requested_funds.amount.is_zero(),
+            ContractError::InvalidInput("requested funds must be greater than 0".to_string())
+        );
+    }
+
+    Ok(())
+}
+
+// only_listed_collection checks that the collection is neither blocked nor delisted
+pub fn only_listed_collection(
+    storage: &dyn Storage,
+    collection: &Addr,
+) -> Result<(), ContractError> {
+    match COLLECTION_STATUSES.may_load(storage, collection.clone())? {
+        Some(CollectionStatus::Blocked) => Err(ContractError::InvalidInput(
+            "collection is blocked".to_string(),
+        )),
+        Some(CollectionStatus::Delisted) => Err(ContractError::InvalidInput(
+            "collection is delisted".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Whether an operation is paused for the whole marketplace or for the collection
+pub fn is_paused(
+    storage: &dyn Storage,
+    collection: Option<&Addr>,
+    operation: PausableOperation,
+) -> StdResult<bool> {
+    if PAUSE_STATE
+        .may_load(storage)?
+        .is_some_and(|pause_state| pause_state.is_paused(operation))
+    {
+        return Ok(true);
+    }
+
+    let Some(collection) = collection else {
+        return Ok(false);
+    };
+    Ok(COLLECTION_PAUSE_STATES
+        .may_load(storage, collection.clone())?
+        .is_some_and(|pause_state| pause_state.is_paused(operation)))
+}
+
+// only_unpaused checks that an operation is not paused for the marketplace or the collection
+pub fn only_unpaused(
+    storage: &dyn Storage,
+    collection: Option<&Addr>,
+    operation: PausableOperation,
+) -> Result<(), ContractError> {
+    ensure!(
+        !is_paused(storage, collection, operation)?,
+        ContractError::Paused(operation.to_string())
+    );
+
+    Ok(())
+}
+
+/// The custody state of the NFT listed by a non-custodial ask
+pub struct AskCustody {
+    pub is_owner: bool,
+    pub has_approval: bool,
+}
+
+impl AskCustody {
+    pub fn is_valid(&self) -> bool {
+        self.is_owner && self.has_approval
+    }
+}
+
+// fetch_ask_custody checks that the ask creator still owns the NFT and that the
+// marketplace still holds an approval for it, either for the token or as an operator
+// of the creator. An approval is removed when
+// 1 - There is a transfer or burn
+// 2 - The approval expired
+// 3 - The approval or operator was revoked
+pub fn fetch_ask_custody(querier: &QuerierWrapper, marketplace: &Addr, ask: &Ask) -> AskCustody {
+    let is_owner = querier
+        .query_wasm_smart::<OwnerOfResponse>(
+            &ask.collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: ask.token_id.to_string(),
+                include_expired: Some(false),
+            },
+        )
+        .is_ok_and(|res| res.owner == ask.creator);
+
+    let has_approval = querier
+        .query_wasm_smart::<ApprovalResponse>(
+            &ask.collection,
+            &Cw721QueryMsg::Approval {
+                token_id: ask.token_id.to_string(),
+                spender: marketplace.to_string(),
+                include_expired: Some(false),
+            },
+        )
+        .is_ok()
+        || querier
+            .query_wasm_smart::<OperatorResponse>(
+                &ask.collection,
+                &Cw721QueryMsg::Operator {
+                    owner: ask.creator.to_string(),
+                    operator: marketplace.to_string(),
+                    include_expired: Some(false),
+                },
+            )
+            .is_ok();
+
+    AskCustody {
+        is_owner,
+        has_approval,
+    }
+}
+
 #[derive(Debug)]
 pub struct ProtocolFees {
     pub protocol_fee: Decimal,
@@ -91,11 +458,20 @@ pub struct ProtocolFees {
     pub taker_reward: Decimal,
 }
 
+/// Divides the protocol fee between the protocol and the maker and taker rewards,
+/// a reward of 0 bps leaves its share with the protocol
 pub fn divide_protocol_fees(
     config: &Config<Addr>,
-    maker_exists: bool,
-    taker_exists: bool,
+    maker_reward_bps: u64,
+    taker_reward_bps: u64,
 ) -> Result<ProtocolFees, ContractError> {
+    ensure!(
+        (maker_reward_bps + taker_reward_bps) < MAX_BASIS_POINTS,
+        ContractError::InvalidInput(
+            "taker and maker reward bps must be less than 1 combined".to_string()
+        )
+    );
+
     let mut protocol_fees = ProtocolFees {
         protocol_fee: Decimal::bps(config.protocol_fee_bps),
         maker_reward: Decimal::zero(),
@@ -106,21 +482,156 @@ pub fn divide_protocol_fees(
         return Ok(protocol_fees);
     }
 
-    if maker_exists && config.maker_reward_bps > 0 {
-        protocol_fees.maker_reward = Decimal::bps(config.protocol_fee_bps)
-            .checked_mul(Decimal::bps(config.maker_reward_bps))?;
+    if maker_reward_bps > 0 {
+        protocol_fees.maker_reward =
+            Decimal::bps(config.protocol_fee_bps).checked_mul(Decimal::bps(maker_reward_bps))?;
         protocol_fees.protocol_fee = protocol_fees.protocol_fee.sub(protocol_fees.maker_reward);
     }
 
-    if taker_exists && config.taker_reward_bps > 0 {
-        protocol_fees.taker_reward = Decimal::bps(config.protocol_fee_bps)
-            .checked_mul(Decimal::bps(config.taker_reward_bps))?;
+    if taker_reward_bps > 0 {
+        protocol_fees.taker_reward =
+            Decimal::bps(config.protocol_fee_bps).checked_mul(Decimal::bps(taker_reward_bps))?;
         protocol_fees.protocol_fee = protocol_fees.protocol_fee.sub(protocol_fees.taker_reward);
     }
 
     Ok(protocol_fees)
 }
 
+/// The reward bps a finder earns, registered finders earn their own reward bps and
+/// unregistered finders the default unless the config only rewards registered finders
+pub fn finder_reward_bps(
+    storage: &dyn Storage,
+    fee_config: &Config<Addr>,
+    finder: &Option<Addr>,
+    default_reward_bps: u64,
+) -> StdResult<u64> {
+    let Some(finder) = finder else {
+        return Ok(0);
+    };
+    match FINDERS.may_load(storage, finder.clone())? {
+        Some(reward_bps) => Ok(reward_bps),
+        None if fee_config.registered_finders_only => Ok(0),
+        None => Ok(default_reward_bps),
+    }
+}
+
+/// Adds the sale price to the volume of the registered finders of a sale
+pub fn record_finder_volume(
+    storage: &mut dyn Storage,
+    finders: &[&Option<Addr>],
+    price: &Coin,
+) -> StdResult<()> {
+    let mut recorded: Vec<&Addr> = vec![];
+    for finder in finders.iter().filter_map(|finder| finder.as_ref()) {
+        if recorded.contains(&finder) || !FINDERS.has(storage, finder.clone()) {
+            continue;
+        }
+        recorded.push(finder);
+        FINDER_VOLUMES.update(
+            storage,
+            (finder.clone(), price.denom.clone()),
+            |volume| -> StdResult<_> { Ok(volume.unwrap_or_default() + price.amount) },
+        )?;
+    }
+    Ok(())
+}
+
+/// Pays out the payments of a sale, payments to recipient kinds in claimable payout mode
+/// accrue to the claimable balances of their recipients instead of being transferred
+pub fn payout(
+    api: &dyn Api,
+    storage: &mut dyn Storage,
+    config: &Config<Addr>,
+    payments: &[Payment],
+    mut response: Response,
+) -> StdResult<Response> {
+    for payment in payments {
+        let payout_mode = match payment.label.as_str() {
+            "protocol" => config.payout_modes.protocol,
+            "royalty" => config.payout_modes.royalty,
+            "maker" | "taker" => config.payout_modes.reward,
+            _ => PayoutMode::Transfer,
+        };
+        match payout_mode {
+            PayoutMode::Transfer => {
+                response =
+                    transfer_asset(api, payment.funds.clone(), &payment.recipient, response)?;
+            }
+            PayoutMode::Claimable => {
+                CLAIMABLE_BALANCES.update(
+                    storage,
+                    (payment.recipient.clone(), payment.funds.denom.clone()),
+                    |balance| -> StdResult<_> {
+                        Ok(balance.unwrap_or_default() + payment.funds.amount)
+                    },
+                )?;
+            }
+        }
+    }
+    Ok(response)
+}
+
+/// The config whose fees apply to sales of the collection, an active fee override of the
+/// collection replaces the fees of the config. Also returns whether an override is active.
+fn fee_config_for(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    config: &Config<Addr>,
+    collection: &Addr,
+) -> StdResult<(Config<Addr>, bool)> {
+    let fee_override = COLLECTION_FEE_OVERRIDES
+        .may_load(storage, collection.clone())?
+        .filter(|fee_override| fee_override.is_active(block));
+    Ok(match fee_override {
+        Some(fee_override) => (fee_override.apply(config), true),
+        None => (config.clone(), false),
+    })
+}
+
+/// Adds the protocol fee and the maker and taker rewards of a sale to the sale processor.
+/// The fees are charged on the weighted share of the sale price.
+fn add_protocol_fees(
+    storage: &dyn Storage,
+    nft_sale_processor: &mut NftSaleProcessor,
+    fee_config: &Config<Addr>,
+    maker: &Option<Addr>,
+    taker: &Option<Addr>,
+    weight: Decimal,
+) -> Result<(), ContractError> {
+    // Defaults raised after a finder was registered could push the combined rewards past the
+    // protocol fee, the taker reward is capped so sales never fail
+    let maker_reward_bps =
+        finder_reward_bps(storage, fee_config, maker, fee_config.maker_reward_bps)?;
+    let taker_reward_bps =
+        finder_reward_bps(storage, fee_config, taker, fee_config.taker_reward_bps)?
+            .min((MAX_BASIS_POINTS - 1).saturating_sub(maker_reward_bps));
+    let protocol_fees = divide_protocol_fees(fee_config, maker_reward_bps, taker_reward_bps)?;
+
+    if protocol_fees.protocol_fee > Decimal::zero() {
+        nft_sale_processor.add_fee(
+            "protocol".to_string(),
+            protocol_fees.protocol_fee.checked_mul(weight)?,
+            fee_config.fee_manager.clone(),
+        );
+    }
+    if protocol_fees.maker_reward > Decimal::zero() {
+        nft_sale_processor.add_fee(
+            "maker".to_string(),
+            protocol_fees.maker_reward.checked_mul(weight)?,
+            maker.clone().unwrap(),
+        );
+    }
+    if protocol_fees.taker_reward > Decimal::zero() {
+        nft_sale_processor.add_fee(
+            "taker".to_string(),
+            protocol_fees.taker_reward.checked_mul(weight)?,
+            taker.clone().unwrap(),
+        );
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn finalize_sale(
     deps: DepsMut,
@@ -132,12 +643,22 @@ pub fn finalize_sale(
     action: &str,
     response: Response,
 ) -> Result<Response, ContractError> {
-    let (nft_recipient, bid_details) = match &matching_bid {
-        MatchingBid::Bid(bid) => (bid.asset_recipient(), &bid.details),
-        MatchingBid::CollectionBid(collection_bid) => {
-            (collection_bid.asset_recipient(), &collection_bid.details)
-        }
-    };
+    only_listed_collection(deps.storage, &ask.collection)?;
+    only_unpaused(
+        deps.storage,
+        Some(&ask.collection),
+        PausableOperation::Sales,
+    )?;
+
+    // Non-custodial asks can only be sold while the marketplace is able to deliver the NFT
+    ensure!(
+        ask.has_custody(&deps.querier, &env.contract.address),
+        ContractError::InvalidInput(
+            "ask creator no longer owns or approved the marketplace for the NFT".to_string()
+        )
+    );
+
+    let (nft_recipient, bid_details) = (matching_bid.asset_recipient(), matching_bid.details());
 
     let (sale_price, maker, taker) = if ask_before_bid {
         (&ask.details.price, &ask.details.finder, &bid_details.finder)
@@ -149,66 +670,164 @@ pub fn finalize_sale(
     let mut nft_sale_processor =
         NftSaleProcessor::new(sale_price.clone(), seller_recipient.clone());
 
-    let protocol_fees = divide_protocol_fees(config, maker.is_some(), taker.is_some())?;
+    let (fee_config, fee_override) =
+        fee_config_for(deps.storage, &env.block, config, &ask.collection)?;
 
-    if protocol_fees.protocol_fee > Decimal::zero() {
-        nft_sale_processor.add_fee(
-            "protocol".to_string(),
-            protocol_fees.protocol_fee,
-            config.fee_manager.clone(),
-        );
-    }
-    if protocol_fees.maker_reward > Decimal::zero() {
-        nft_sale_processor.add_fee(
-            "maker".to_string(),
-            protocol_fees.maker_reward,
-            maker.clone().unwrap().clone(),
-        );
-    }
-    if protocol_fees.taker_reward > Decimal::zero() {
-        nft_sale_processor.add_fee(
-            "taker".to_string(),
-            protocol_fees.taker_reward,
-            taker.clone().unwrap().clone(),
-        );
-    }
+    add_protocol_fees(
+        deps.storage,
+        &mut nft_sale_processor,
+        &fee_config,
+        maker,
+        taker,
+        Decimal::one(),
+    )?;
 
-    let (royalty_entry_option, mut response) = fetch_or_set_royalties(
-        deps.as_ref(),
-        &config.royalty_registry,
+    let (royalty_entry_option, mut response) =
+        fetch_collection_royalty(deps.as_ref(), env, config, &ask.collection, response)?;
+
+    let (royalty_share, royalty_policy) = royalty_share_for(
+        deps.storage,
+        config,
         &ask.collection,
-        Some(&env.contract.address),
-        response,
+        royalty_entry_option
+            .as_ref()
+            .map_or(Decimal::zero(), |royalty_entry| royalty_entry.share),
     )?;
 
+    // The royalty tip of the buyer is paid on top of the sale price, or refunded when the
+    // collection has no optional royalty to tip
+    let royalty_tip = Coin {
+        denom: sale_price.denom.clone(),
+        amount: bid_details.royalty_tip.unwrap_or_default(),
+    };
+    let mut royalty_tip_paid = Uint128::zero();
     if let Some(royalty_entry) = royalty_entry_option {
-        nft_sale_processor.add_fee(
-            "royalty".to_string(),
-            min(
-                royalty_entry.share,
-                Decimal::bps(config.max_royalty_fee_bps),
-            ),
-            royalty_entry.recipient,
-        );
+        if !royalty_share.is_zero() {
+            nft_sale_processor.add_split_fee(
+                "royalty".to_string(),
+                royalty_share,
+                royalty_entry.weighted_recipients(),
+            );
+        }
+        if royalty_policy == RoyaltyPolicy::Optional && !royalty_tip.amount.is_zero() {
+            nft_sale_processor.add_tip(
+                "royalty".to_string(),
+                royalty_tip.amount,
+                royalty_entry.weighted_recipients(),
+            );
+            royalty_tip_paid = royalty_tip.amount;
+        }
+    }
+    if royalty_tip_paid.is_zero() && !royalty_tip.amount.is_zero() {
+        response = transfer_asset(
+            deps.api,
+            royalty_tip.clone(),
+            matching_bid.creator(),
+            response,
+        )?;
     }
 
     nft_sale_processor.build_payments()?;
-    response = nft_sale_processor.payout(response);
+    response = payout(
+        deps.api,
+        deps.storage,
+        config,
+        &nft_sale_processor.payments,
+        response,
+    )?;
+
+    let royalty_paid: Uint128 = nft_sale_processor
+        .payments
+        .iter()
+        .filter(|payment| payment.label == "royalty")
+        .map(|payment| payment.funds.amount)
+        .sum();
 
     // Transfer NFT to buyer
     response = transfer_nft(&ask.collection, &ask.token_id, &nft_recipient, response);
 
-    // Remove orders
-    ask.remove(deps.storage)?;
+    // Remove orders, stored orders are announced to the hooks as deleted
+    if asks().has(deps.storage, ask.id.clone()) {
+        ask.remove(deps.storage)?;
+        response =
+            response.add_submessages(prepare_ask_hook(deps.storage, ask, HookAction::Delete)?);
+    }
     match &matching_bid {
         MatchingBid::Bid(bid) => {
-            bid.remove(deps.storage)?;
+            if bids().has(deps.storage, bid.id.clone()) {
+                bid.remove(deps.storage)?;
+                response = response.add_submessages(prepare_bid_hook(
+                    deps.storage,
+                    bid,
+                    HookAction::Delete,
+                )?);
+            }
         }
         MatchingBid::CollectionBid(collection_bid) => {
-            collection_bid.remove(deps.storage)?;
+            // Collection bids are only removed once their last unit is filled
+            if collection_bids().has(deps.storage, collection_bid.id.clone()) {
+                if collection_bid.quantity > 1 {
+                    let mut collection_bid = collection_bid.clone();
+                    collection_bid.quantity -= 1;
+                    collection_bid.save(deps.storage)?;
+                    response = response.add_submessages(prepare_collection_bid_hook(
+                        deps.storage,
+                        &collection_bid,
+                        HookAction::Update,
+                    )?);
+                } else {
+                    collection_bid.remove(deps.storage)?;
+                    response = response.add_submessages(prepare_collection_bid_hook(
+                        deps.storage,
+                        collection_bid,
+                        HookAction::Delete,
+                    )?);
+                }
+            }
+        }
+        MatchingBid::TraitBid(trait_bid) => {
+            if trait_bids().has(deps.storage, trait_bid.id.clone()) {
+                trait_bid.remove(deps.storage)?;
+                response = response.add_submessages(prepare_trait_bid_hook(
+                    deps.storage,
+                    trait_bid,
+                    HookAction::Delete,
+                )?);
+            }
         }
     }
 
+    record_sale(
+        deps.storage,
+        Sale {
+            id: 0,
+            collection: ask.collection.clone(),
+            token_id: ask.token_id.clone(),
+            price: sale_price.clone(),
+            buyer: matching_bid.creator().clone(),
+            seller: ask.creator.clone(),
+            time: env.block.time,
+        },
+    )?;
+    update_collection_stats(deps.storage, &ask.collection, &sale_price.denom, |stats| {
+        stats.sale_count += 1;
+        stats.volume += sale_price.amount;
+    })?;
+    record_price_observation(deps.storage, &ask.collection, sale_price, env.block.time)?;
+    record_finder_volume(deps.storage, &[maker, taker], sale_price)?;
+
+    response = response.add_submessages(prepare_sale_hook(
+        deps.storage,
+        &SaleHookMsg {
+            ask: ask.clone(),
+            matching_bid: matching_bid.clone(),
+            price: sale_price.clone(),
+            seller_recipient: seller_recipient.clone(),
+            nft_recipient: nft_recipient.clone(),
+            payments: nft_sale_processor.payments.clone(),
+        },
+    )?);
+
     let mut sale_event = Event::new("finalize-sale")
         .add_attribute("collection", ask.collection.to_string())
         .add_attribute("token_id", ask.token_id.to_string())
@@ -217,7 +836,11 @@ pub fn finalize_sale(
         .add_attribute("seller_recipient", seller_recipient.to_string())
         .add_attribute("nft_recipient", nft_recipient.to_string())
         .add_attribute("ask", ask.id.to_string())
-        .add_attribute("marketplace_action", action.to_string());
+        .add_attribute("marketplace_action", action.to_string())
+        .add_attribute("fee_override", fee_override.to_string())
+        .add_attribute("royalty_policy", royalty_policy.to_string())
+        .add_attribute("royalty_paid", royalty_paid.to_string())
+        .add_attribute("royalty_tip", royalty_tip_paid.to_string());
 
     match &matching_bid {
         MatchingBid::Bid(bid) => {
@@ -226,6 +849,9 @@ pub fn finalize_sale(
         MatchingBid::CollectionBid(collection_bid) => {
             sale_event = sale_event.add_attribute("collection_bid", collection_bid.id.to_string());
         }
+        MatchingBid::TraitBid(trait_bid) => {
+            sale_event = sale_event.add_attribute("trait_bid", trait_bid.id.to_string());
+        }
     }
 
     for payment in nft_sale_processor.payments.iter() {
@@ -237,11 +863,426 @@ pub fn finalize_sale(
     Ok(response)
 }
 
+/// Sums the weights of the entries per collection, in the order the collections first appear
+pub fn sum_by_collection<'a>(entries: impl Iterator<Item = (&'a Addr, u64)>) -> Vec<(Addr, u64)> {
+    let mut sums: Vec<(Addr, u64)> = vec![];
+    for (collection, weight) in entries {
+        match sums.iter_mut().find(|(c, _)| *c == *collection) {
+            Some((_, sum)) => *sum += weight,
+            None => sums.push((collection.clone(), weight)),
+        }
+    }
+    sums
+}
+
+/// Divides the price between the weights, the rounding remainder is added to the first share
+pub fn split_by_weight(price: &Coin, weights: impl Iterator<Item = u64> + Clone) -> Vec<Coin> {
+    let total_weight: u64 = weights.clone().sum();
+    let mut amounts = weights
+        .map(|weight| price.amount.multiply_ratio(weight, total_weight))
+        .collect::<Vec<Uint128>>();
+    let allocated: Uint128 = amounts.iter().copied().sum();
+    if let Some(first) = amounts.first_mut() {
+        *first += price.amount - allocated;
+    }
+    amounts
+        .into_iter()
+        .map(|amount| Coin {
+            denom: price.denom.clone(),
+            amount,
+        })
+        .collect()
+}
+
+/// Fetches the royalty of a collection, a royalty set by the collection admin is paid instead
+/// of the royalty registry entry
+pub fn fetch_collection_royalty(
+    deps: Deps,
+    env: &Env,
+    config: &Config<Addr>,
+    collection: &Addr,
+    response: Response,
+) -> StdResult<(Option<MultiRoyaltyEntry>, Response)> {
+    if let Some(collection_royalty) =
+        COLLECTION_ROYALTIES.may_load(deps.storage, collection.clone())?
+    {
+        return Ok((Some(collection_royalty.into()), response));
+    }
+
+    fetch_or_set_multi_royalties(
+        deps,
+        &config.royalty_registry,
+        collection,
+        Some(&env.contract.address),
+        response,
+    )
+}
+
+/// The royalty share charged on a sale of the collection under its royalty policy,
+/// optional royalties are only paid through the royalty tips of buyers
+pub fn royalty_share_for(
+    storage: &dyn Storage,
+    config: &Config<Addr>,
+    collection: &Addr,
+    registry_share: Decimal,
+) -> StdResult<(Decimal, RoyaltyPolicy)> {
+    let royalty_policy = COLLECTION_ROYALTY_POLICIES
+        .may_load(storage, collection.clone())?
+        .unwrap_or_default();
+    let max_share = min(registry_share, Decimal::bps(config.max_royalty_fee_bps));
+    let royalty_share = match royalty_policy {
+        RoyaltyPolicy::Required => max_share,
+        RoyaltyPolicy::Optional | RoyaltyPolicy::Zero => Decimal::zero(),
+    };
+    Ok((royalty_share, royalty_policy))
+}
+
+/// Adds the royalty of each collection to the sale processor, charged on the share of the
+/// sale price given by the weight of the collection
+fn add_weighted_royalties(
+    deps: Deps,
+    env: &Env,
+    config: &Config<Addr>,
+    nft_sale_processor: &mut NftSaleProcessor,
+    collection_weights: Vec<(Addr, u64)>,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    let total_weight: u64 = collection_weights.iter().map(|(_, weight)| weight).sum();
+    for (collection, weight) in collection_weights {
+        let (royalty_entry_option, next_response) =
+            fetch_collection_royalty(deps, env, config, &collection, response)?;
+        response = next_response;
+
+        if let Some(royalty_entry) = royalty_entry_option {
+            let (royalty_share, _) =
+                royalty_share_for(deps.storage, config, &collection, royalty_entry.share)?;
+            if royalty_share.is_zero() {
+                continue;
+            }
+            let royalty_share =
+                royalty_share.checked_mul(Decimal::from_ratio(weight, total_weight))?;
+            nft_sale_processor.add_split_fee(
+                "royalty".to_string(),
+                royalty_share,
+                royalty_entry.weighted_recipients(),
+            );
+        }
+    }
+
+    Ok(response)
+}
+
+/// Pays out a bundle sale and transfers all NFTs of the bundle to the buyer.
+/// The fees and royalty of each collection are charged on the share of the price given by the
+/// royalty weights of its items, the same shares are recorded as the sale prices of the items.
+#[allow(clippy::too_many_arguments)]
+pub fn finalize_bundle_sale(
+    deps: DepsMut,
+    env: &Env,
+    bundle: &Bundle,
+    config: &Config<Addr>,
+    buyer: &Addr,
+    buyer_details: &OrderDetails<Addr>,
+    action: &str,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    let collection_royalty_weights = bundle.collection_royalty_weights();
+    for (collection, _) in &collection_royalty_weights {
+        only_listed_collection(deps.storage, collection)?;
+        only_unpaused(deps.storage, Some(collection), PausableOperation::Sales)?;
+    }
+
+    let sale_price = &bundle.details.price;
+    let seller_recipient = bundle.asset_recipient();
+    let nft_recipient = address_or(buyer_details.recipient.as_ref(), buyer);
+    let mut nft_sale_processor =
+        NftSaleProcessor::new(sale_price.clone(), seller_recipient.clone());
+
+    let total_weight: u64 = collection_royalty_weights
+        .iter()
+        .map(|(_, weight)| weight)
+        .sum();
+    let mut fee_override = false;
+    for (collection, weight) in &collection_royalty_weights {
+        let (fee_config, collection_fee_override) =
+            fee_config_for(deps.storage, &env.block, config, collection)?;
+        fee_override |= collection_fee_override;
+        add_protocol_fees(
+            deps.storage,
+            &mut nft_sale_processor,
+            &fee_config,
+            &bundle.details.finder,
+            &buyer_details.finder,
+            Decimal::from_ratio(*weight, total_weight),
+        )?;
+    }
+
+    response = add_weighted_royalties(
+        deps.as_ref(),
+        env,
+        config,
+        &mut nft_sale_processor,
+        collection_royalty_weights,
+        response,
+    )?;
+
+    nft_sale_processor.build_payments()?;
+    response = payout(
+        deps.api,
+        deps.storage,
+        config,
+        &nft_sale_processor.payments,
+        response,
+    )?;
+
+    for item in &bundle.items {
+        response = transfer_nft(&item.collection, &item.token_id, &nft_recipient, response);
+    }
+
+    bundle.remove(deps.storage)?;
+
+    for (item, item_price) in bundle.items.iter().zip(bundle.item_prices()) {
+        record_sale(
+            deps.storage,
+            Sale {
+                id: 0,
+                collection: item.collection.clone(),
+                token_id: item.token_id.clone(),
+                price: item_price.clone(),
+                buyer: buyer.clone(),
+                seller: bundle.creator.clone(),
+                time: env.block.time,
+            },
+        )?;
+        update_collection_stats(deps.storage, &item.collection, &item_price.denom, |stats| {
+            stats.sale_count += 1;
+            stats.volume += item_price.amount;
+        })?;
+        record_price_observation(deps.storage, &item.collection, &item_price, env.block.time)?;
+    }
+    record_finder_volume(
+        deps.storage,
+        &[&bundle.details.finder, &buyer_details.finder],
+        sale_price,
+    )?;
+
+    response = response.add_submessages(prepare_bundle_sale_hook(
+        deps.storage,
+        &BundleSaleHookMsg {
+            bundle: bundle.clone(),
+            buyer: buyer.clone(),
+            price: sale_price.clone(),
+            seller_recipient: seller_recipient.clone(),
+            nft_recipient: nft_recipient.clone(),
+            payments: nft_sale_processor.payments.clone(),
+        },
+    )?);
+
+    let mut sale_event = Event::new("finalize-bundle-sale")
+        .add_attribute("bundle", bundle.id.to_string())
+        .add_attribute("denom", sale_price.denom.to_string())
+        .add_attribute("price", sale_price.amount.to_string())
+        .add_attribute("seller_recipient", seller_recipient.to_string())
+        .add_attribute("nft_recipient", nft_recipient.to_string())
+        .add_attribute("marketplace_action", action.to_string())
+        .add_attribute("fee_override", fee_override.to_string());
+
+    for payment in nft_sale_processor.payments.iter() {
+        sale_event = sale_event.add_attribute(&payment.label, payment.funds.amount.to_string());
+    }
+
+    response = response.add_event(sale_event);
+
+    Ok(response)
+}
+
+/// Exchanges the NFTs of a swap and pays its funds to the side giving NFTs in return.
+/// Royalties are charged on the funds, split between the collections of the NFTs the funds pay for.
+/// The NFTs the funds pay for are recorded as sales, swaps are announced to the sale hooks.
+#[allow(clippy::too_many_arguments)]
+pub fn finalize_swap(
+    deps: DepsMut,
+    env: &Env,
+    swap: &Swap,
+    config: &Config<Addr>,
+    taker: &Addr,
+    taker_nfts: &[SwapNft<Addr>],
+    taker_recipient: &Addr,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    for nft in swap.offered_nfts.iter().chain(taker_nfts) {
+        only_listed_collection(deps.storage, &nft.collection)?;
+        only_unpaused(
+            deps.storage,
+            Some(&nft.collection),
+            PausableOperation::Sales,
+        )?;
+    }
+
+    let maker_recipient = swap.asset_recipient();
+
+    // The funds of a swap pay for the NFTs of the other side, bought by the side giving the funds
+    let coin_leg = match (&swap.offered_funds, &swap.requested_funds) {
+        (Some(funds), _) => Some((funds, taker_recipient, taker_nfts, &swap.creator, taker)),
+        (None, Some(funds)) => Some((
+            funds,
+            &maker_recipient,
+            swap.offered_nfts.as_slice(),
+            taker,
+            &swap.creator,
+        )),
+        (None, None) => None,
+    };
+
+    let mut payments = vec![];
+    if let Some((funds, funds_recipient, paid_nfts, buyer, seller)) = coin_leg {
+        let mut nft_sale_processor = NftSaleProcessor::new(funds.clone(), funds_recipient.clone());
+        response = add_weighted_royalties(
+            deps.as_ref(),
+            env,
+            config,
+            &mut nft_sale_processor,
+            sum_by_collection(paid_nfts.iter().map(|nft| (&nft.collection, 1))),
+            response,
+        )?;
+        nft_sale_processor.build_payments()?;
+        response = payout(
+            deps.api,
+            deps.storage,
+            config,
+            &nft_sale_processor.payments,
+            response,
+        )?;
+        payments = nft_sale_processor.payments;
+
+        // The paid NFTs are recorded as sales at an equal share of the funds
+        let nft_prices = split_by_weight(funds, paid_nfts.iter().map(|_| 1));
+        for (nft, nft_price) in paid_nfts.iter().zip(nft_prices) {
+            record_sale(
+                deps.storage,
+                Sale {
+                    id: 0,
+                    collection: nft.collection.clone(),
+                    token_id: nft.token_id.clone(),
+                    price: nft_price,
+                    buyer: buyer.clone(),
+                    seller: seller.clone(),
+                    time: env.block.time,
+                },
+            )?;
+        }
+    }
+
+    for nft in &swap.offered_nfts {
+        response = transfer_nft(&nft.collection, &nft.token_id, taker_recipient, response);
+    }
+    for nft in taker_nfts {
+        response = transfer_nft(&nft.collection, &nft.token_id, &maker_recipient, response);
+    }
+
+    swap.remove(deps.storage)?;
+
+    response = response.add_submessages(prepare_swap_hook(
+        deps.storage,
+        &SwapHookMsg {
+            swap: swap.clone(),
+            taker: taker.clone(),
+            taker_nfts: taker_nfts.to_vec(),
+            maker_recipient: maker_recipient.clone(),
+            taker_recipient: taker_recipient.clone(),
+            payments: payments.clone(),
+        },
+    )?);
+
+    let mut swap_event = Event::new("finalize-swap")
+        .add_attribute("swap", swap.id.to_string())
+        .add_attribute("taker", taker.to_string())
+        .add_attribute(
+            "taker_nfts",
+            taker_nfts
+                .iter()
+                .map(|nft| nft.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_attribute("maker_recipient", maker_recipient.to_string())
+        .add_attribute("taker_recipient", taker_recipient.to_string());
+
+    for payment in payments.iter() {
+        swap_event = swap_event.add_attribute(&payment.label, payment.funds.amount.to_string());
+    }
+
+    response = response.add_event(swap_event);
+
+    Ok(response)
+}
+
+/// Appends a sale to the bounded sale histories of its token and collection
+pub fn record_sale(storage: &mut dyn Storage, mut sale: Sale) -> StdResult<()> {
+    let sale_id = COLLECTION_SALE_COUNTS
+        .may_load(storage, sale.collection.clone())?
+        .unwrap_or_default();
+    COLLECTION_SALE_COUNTS.save(storage, sale.collection.clone(), &(sale_id + 1))?;
+    sale.id = sale_id;
+
+    if sale_id >= MAX_COLLECTION_SALE_HISTORY {
+        COLLECTION_SALES.remove(
+            storage,
+            (
+                sale.collection.clone(),
+                sale_id - MAX_COLLECTION_SALE_HISTORY,
+            ),
+        );
+    }
+    COLLECTION_SALES.save(storage, (sale.collection.clone(), sale_id), &sale)?;
+
+    let token_key = (sale.collection.clone(), sale.token_id.clone());
+    let mut token_sales = TOKEN_SALES
+        .may_load(storage, token_key.clone())?
+        .unwrap_or_default();
+    if token_sales.len() >= MAX_TOKEN_SALE_HISTORY {
+        token_sales.remove(0);
+    }
+    token_sales.push(sale);
+    TOKEN_SALES.save(storage, token_key, &token_sales)?;
+
+    Ok(())
+}
+
+/// Appends a sale price to the bounded price observations of the collection and denom
+pub fn record_price_observation(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    price: &Coin,
+    time: Timestamp,
+) -> StdResult<()> {
+    let key = (collection.clone(), price.denom.clone());
+    let mut observations = PRICE_OBSERVATIONS
+        .may_load(storage, key.clone())?
+        .unwrap_or_default();
+
+    let cumulative_price = observations
+        .last()
+        .map_or(Uint256::zero(), |last| last.cumulative_price_at(time));
+
+    if observations.len() >= MAX_PRICE_OBSERVATIONS {
+        observations.remove(0);
+    }
+    observations.push(PriceObservation {
+        time,
+        price: price.amount,
+        cumulative_price,
+    });
+    PRICE_OBSERVATIONS.save(storage, key, &observations)
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
     use super::*;
+    use crate::state::PayoutModes;
+    use cosmwasm_std::{coin, testing::MockStorage, Order};
 
     #[test]
     fn try_maker_and_taker_fees() {
@@ -253,12 +1294,53 @@ mod tests {
             maker_reward_bps: 4000,
             taker_reward_bps: 1000,
             default_denom: "ustars".to_string(),
+            removal_reward_bps: 0,
+            registered_finders_only: false,
+            payout_modes: PayoutModes::default(),
+            auction_min_bid_increment_bps: 500,
+            auction_extension_seconds: 600,
         };
 
-        let result = divide_protocol_fees(&config, true, true).unwrap();
+        let result =
+            divide_protocol_fees(&config, config.maker_reward_bps, config.taker_reward_bps)
+                .unwrap();
 
         assert_eq!(result.protocol_fee, Decimal::from_str("0.01").unwrap());
         assert_eq!(result.maker_reward, Decimal::from_str("0.008").unwrap());
         assert_eq!(result.taker_reward, Decimal::from_str("0.002").unwrap());
     }
+
+    #[test]
+    fn try_record_sale_bounds_history() {
+        let mut storage = MockStorage::new();
+        let collection = Addr::unchecked("collection");
+        let sale = Sale {
+            id: 0,
+            collection: collection.clone(),
+            token_id: "1".to_string(),
+            price: coin(100, "ustars"),
+            buyer: Addr::unchecked("buyer"),
+            seller: Addr::unchecked("seller"),
+            time: Timestamp::from_seconds(0),
+        };
+
+        let num_sales = MAX_COLLECTION_SALE_HISTORY + 5;
+        for _ in 0..num_sales {
+            record_sale(&mut storage, sale.clone()).unwrap();
+        }
+
+        let token_sales = TOKEN_SALES
+            .load(&storage, (collection.clone(), "1".to_string()))
+            .unwrap();
+        assert_eq!(token_sales.len(), MAX_TOKEN_SALE_HISTORY);
+        assert_eq!(token_sales.last().unwrap().id, num_sales - 1);
+
+        let collection_sales = COLLECTION_SALES
+            .prefix(collection)
+            .keys(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(collection_sales.len() as u64, MAX_COLLECTION_SALE_HISTORY);
+        assert_eq!(collection_sales[0], num_sales - MAX_COLLECTION_SALE_HISTORY);
+    }
 }
@@ -0,0 +1,144 @@
+use crate::{
+    msg::{
+        AskHookMsg, BidHookMsg, BundleSaleHookMsg, CollectionBidHookMsg, HookAction, SaleHookMsg,
+        SwapHookMsg, TraitBidHookMsg,
+    },
+    orders::{Ask, Bid, CollectionBid, TraitBid},
+    state::{ASK_HOOKS, BID_HOOKS, COLLECTION_BID_HOOKS, SALE_HOOKS, TRAIT_BID_HOOKS},
+    ContractError,
+};
+
+use cosmwasm_std::{StdResult, Storage, SubMsg, WasmMsg};
+
+pub enum HookReply {
+    Ask = 1,
+    Bid,
+    CollectionBid,
+    Sale,
+    TraitBid,
+}
+
+impl TryFrom<u64> for HookReply {
+    type Error = ContractError;
+
+    fn try_from(item: u64) -> Result<Self, Self::Error> {
+        match item {
+            1 => Ok(HookReply::Ask),
+            2 => Ok(HookReply::Bid),
+            3 => Ok(HookReply::CollectionBid),
+            4 => Ok(HookReply::Sale),
+            5 => Ok(HookReply::TraitBid),
+            _ => Err(ContractError::InternalError(format!(
+                "invalid reply id [{}]",
+                item
+            ))),
+        }
+    }
+}
+
+pub fn prepare_ask_hook(
+    storage: &dyn Storage,
+    ask: &Ask,
+    action: HookAction,
+) -> StdResult<Vec<SubMsg>> {
+    ASK_HOOKS.prepare_hooks(storage, |h| {
+        let msg = AskHookMsg { ask: ask.clone() };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Ask as u64))
+    })
+}
+
+pub fn prepare_bid_hook(
+    storage: &dyn Storage,
+    bid: &Bid,
+    action: HookAction,
+) -> StdResult<Vec<SubMsg>> {
+    BID_HOOKS.prepare_hooks(storage, |h| {
+        let msg = BidHookMsg { bid: bid.clone() };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Bid as u64))
+    })
+}
+
+pub fn prepare_collection_bid_hook(
+    storage: &dyn Storage,
+    collection_bid: &CollectionBid,
+    action: HookAction,
+) -> StdResult<Vec<SubMsg>> {
+    COLLECTION_BID_HOOKS.prepare_hooks(storage, |h| {
+        let msg = CollectionBidHookMsg {
+            collection_bid: collection_bid.clone(),
+        };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(
+            execute,
+            HookReply::CollectionBid as u64,
+        ))
+    })
+}
+
+pub fn prepare_trait_bid_hook(
+    storage: &dyn Storage,
+    trait_bid: &TraitBid,
+    action: HookAction,
+) -> StdResult<Vec<SubMsg>> {
+    TRAIT_BID_HOOKS.prepare_hooks(storage, |h| {
+        let msg = TraitBidHookMsg {
+            trait_bid: trait_bid.clone(),
+        };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::TraitBid as u64))
+    })
+}
+
+pub fn prepare_sale_hook(storage: &dyn Storage, msg: &SaleHookMsg) -> StdResult<Vec<SubMsg>> {
+    SALE_HOOKS.prepare_hooks(storage, |h| {
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.clone().into_json_binary()?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
+    })
+}
+
+pub fn prepare_swap_hook(storage: &dyn Storage, msg: &SwapHookMsg) -> StdResult<Vec<SubMsg>> {
+    SALE_HOOKS.prepare_hooks(storage, |h| {
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.clone().into_json_binary()?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
+    })
+}
+
+pub fn prepare_bundle_sale_hook(
+    storage: &dyn Storage,
+    msg: &BundleSaleHookMsg,
+) -> StdResult<Vec<SubMsg>> {
+    SALE_HOOKS.prepare_hooks(storage, |h| {
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.clone().into_json_binary()?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
+    })
+}
@@ -8,7 +8,10 @@ pub use error::ContractError;
 pub mod constants;
 pub mod events;
 pub mod helpers;
+pub mod hooks;
 pub mod instantiate;
 pub mod migrate;
 pub mod orders;
+pub mod reply;
 mod tests;
+pub mod transaction;
@@ -1,9 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{DepsMut, Env, Response};
+use cosmwasm_std::{Addr, DepsMut, Env, Order, Response};
 use cw2::set_contract_version;
+use cw_storage_plus::Map;
+use sg_marketplace_common::asset::Asset;
 
 use crate::{
     constants::{CONTRACT_NAME, CONTRACT_VERSION},
+    state::{trait_bids, update_collection_stats, Denom, COLLECTION_DENOMS},
     ContractError,
 };
 
@@ -17,6 +20,33 @@ use cosmwasm_std::entry_point;
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let response = Response::new();
 
+    // Collection denoms used to be stored as plain native denoms, entries that
+    // are already stored as an asset fail to deserialize and are skipped
+    let legacy_collection_denoms: Vec<(Addr, Denom)> = Map::<Addr, Denom>::new("D")
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|res| res.ok())
+        .collect();
+    for (collection, denom) in legacy_collection_denoms {
+        COLLECTION_DENOMS.save(deps.storage, collection, &Asset::Native(denom))?;
+    }
+
+    // Asks are only matched against trait bids while the collection stats count trait bids,
+    // the counters of trait bids stored before they were counted are rebuilt
+    let mut trait_bid_counts: Vec<((Addr, Denom), u64)> = vec![];
+    for trait_bid in trait_bids().range(deps.storage, None, None, Order::Ascending) {
+        let (_, trait_bid) = trait_bid?;
+        let key = (trait_bid.collection, trait_bid.details.price.denom);
+        match trait_bid_counts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, count)) => *count += 1,
+            None => trait_bid_counts.push((key, 1)),
+        }
+    }
+    for ((collection, denom), count) in trait_bid_counts {
+        update_collection_stats(deps.storage, &collection, &denom, |stats| {
+            stats.trait_bid_count = count
+        })?;
+    }
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(response)
@@ -1,11 +1,21 @@
 use crate::{
-    orders::{Ask, Bid, CollectionBid, OrderDetails},
-    state::{Config, Denom, OrderId, TokenId},
+    orders::{
+        Ask, Auction, Bid, Bundle, BundleItem, CollectionBid, CounterOffer, DutchAuction,
+        MatchingBid, OrderDetails, Swap, SwapNft, SwapRequest, TraitBid,
+    },
+    state::{
+        CollectionRoyalty, CollectionStats, CollectionStatus, Config, Denom, FeeOverride, OrderId,
+        PauseState, RoyaltyPolicy, Sale, TokenId,
+    },
 };
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, StdResult, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use sg_controllers::HooksResponse;
 use sg_index_query::QueryOptions;
+use sg_marketplace_common::{asset::Asset, sale::Payment};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -21,7 +31,40 @@ pub enum ExecuteMsg {
     },
     UpdateCollectionDenom {
         collection: String,
-        denom: Denom,
+        denom: Asset,
+    },
+    SetCollectionFeeOverride {
+        collection: String,
+        fee_override: FeeOverride,
+    },
+    RemoveCollectionFeeOverride {
+        collection: String,
+    },
+    /// Sets how royalties are charged on sales of a collection
+    SetCollectionRoyaltyPolicy {
+        collection: String,
+        royalty_policy: RoyaltyPolicy,
+    },
+    RemoveCollectionRoyaltyPolicy {
+        collection: String,
+    },
+    /// Splits the royalty of a collection between several recipients, paid instead of its
+    /// royalty registry entry. Only the admin of the collection contract can set it
+    SetCollectionRoyalty {
+        collection: String,
+        collection_royalty: CollectionRoyalty<String>,
+    },
+    /// Removes the royalty set by the collection admin, royalties fall back to the registry
+    RemoveCollectionRoyalty {
+        collection: String,
+    },
+    /// Registers a finder, replacing the maker and taker reward bps when it finds a sale
+    SetFinder {
+        finder: String,
+        reward_bps: u64,
+    },
+    RemoveFinder {
+        finder: String,
     },
     SetListingFee {
         fee: Coin,
@@ -29,15 +72,89 @@ pub enum ExecuteMsg {
     RemoveListingFee {
         denom: Denom,
     },
+    /// Sets the registry status of a collection
+    UpdateCollectionStatus {
+        collection: String,
+        status: CollectionStatus,
+    },
+    /// Cancels open orders of a delisted collection, refunding their creators. Bundles and swaps
+    /// are cancelled when any of their NFTs is of the collection, and counter-offers are keyed
+    /// by the id of the bid they counter.
+    CancelDelistedOrders {
+        collection: String,
+        asks: Vec<OrderId>,
+        bids: Vec<OrderId>,
+        collection_bids: Vec<OrderId>,
+        trait_bids: Vec<OrderId>,
+        auctions: Vec<OrderId>,
+        bundles: Vec<OrderId>,
+        swaps: Vec<OrderId>,
+        counter_offers: Vec<OrderId>,
+    },
+    /// Pauses marketplace operations, for a single collection when set
+    UpdatePauseState {
+        collection: Option<String>,
+        pause_state: PauseState,
+    },
+    /// Add a new hook to be informed of all asks
+    AddAskHook {
+        hook: String,
+    },
+    /// Remove an ask hook
+    RemoveAskHook {
+        hook: String,
+    },
+    /// Add a new hook to be informed of all bids
+    AddBidHook {
+        hook: String,
+    },
+    /// Remove a bid hook
+    RemoveBidHook {
+        hook: String,
+    },
+    /// Add a new hook to be informed of all collection bids
+    AddCollectionBidHook {
+        hook: String,
+    },
+    /// Remove a collection bid hook
+    RemoveCollectionBidHook {
+        hook: String,
+    },
+    /// Add a new hook to be informed of all trait bids
+    AddTraitBidHook {
+        hook: String,
+    },
+    /// Remove a trait bid hook
+    RemoveTraitBidHook {
+        hook: String,
+    },
+    /// Add a new hook to be informed of all sales
+    AddSaleHook {
+        hook: String,
+    },
+    /// Remove a sale hook
+    RemoveSaleHook {
+        hook: String,
+    },
     // Marketplace messages
     SetAsk {
         collection: String,
         token_id: TokenId,
         details: OrderDetails<String>,
+        /// When set the seller keeps custody of the NFT and approves the marketplace instead
+        non_custodial: Option<bool>,
+        /// When set only the given buyers can buy the NFT
+        reserved_for: Option<Vec<String>>,
+        /// When set the price declines from the ask price to the end price of the auction
+        dutch_auction: Option<DutchAuction>,
     },
     RemoveAsk {
         id: OrderId,
     },
+    /// Permissionless removal of a non-custodial ask whose owner or approval changed
+    SyncAsk {
+        id: OrderId,
+    },
     UpdateAsk {
         id: OrderId,
         details: OrderDetails<String>,
@@ -62,9 +179,25 @@ pub enum ExecuteMsg {
         id: OrderId,
         details: OrderDetails<String>,
     },
+    /// Offers the NFT to the bidder at a higher price, replacing a previous counter-offer.
+    /// The counter-offer lapses once the bid or the ask of the NFT changes.
+    SetCounterOffer {
+        bid_id: OrderId,
+        price: Coin,
+        expires: Option<Timestamp>,
+    },
+    RemoveCounterOffer {
+        bid_id: OrderId,
+    },
+    /// Buys the NFT at the counter-offer price, the sent funds top up the escrowed bid
+    AcceptCounterOffer {
+        bid_id: OrderId,
+    },
     SetCollectionBid {
         collection: String,
         details: OrderDetails<String>,
+        /// The number of NFTs to buy, defaults to 1
+        quantity: Option<u32>,
     },
     RemoveCollectionBid {
         id: OrderId,
@@ -72,12 +205,32 @@ pub enum ExecuteMsg {
     UpdateCollectionBid {
         id: OrderId,
         details: OrderDetails<String>,
+        /// The new number of NFTs still to buy, defaults to the remaining quantity
+        quantity: Option<u32>,
     },
     AcceptCollectionBid {
         id: OrderId,
         token_id: TokenId,
         details: OrderDetails<String>,
     },
+    SetTraitBid {
+        collection: String,
+        trait_type: String,
+        trait_value: String,
+        details: OrderDetails<String>,
+    },
+    RemoveTraitBid {
+        id: OrderId,
+    },
+    UpdateTraitBid {
+        id: OrderId,
+        details: OrderDetails<String>,
+    },
+    AcceptTraitBid {
+        id: OrderId,
+        token_id: TokenId,
+        details: OrderDetails<String>,
+    },
     SellNft {
         collection: String,
         token_id: TokenId,
@@ -92,6 +245,164 @@ pub enum ExecuteMsg {
         collection: String,
         details: OrderDetails<String>,
     },
+    /// Buys up to max_items of the cheapest asks of a collection, unused funds are refunded
+    SweepCollection {
+        collection: String,
+        max_items: u32,
+        max_price_per_item: Coin,
+        max_total: Coin,
+    },
+    /// Escrows the NFT in an auction accepting bids until the end time
+    SetAuction {
+        collection: String,
+        token_id: TokenId,
+        reserve_price: Coin,
+        end_time: Timestamp,
+        recipient: Option<String>,
+    },
+    /// Auctions can only be removed by their creator before the first bid
+    RemoveAuction {
+        id: OrderId,
+    },
+    /// Escrows the sent funds as the highest bid of the auction and refunds the previous one.
+    /// Bids placed shortly before the end extend the auction.
+    BidAuction {
+        id: OrderId,
+        finder: Option<String>,
+    },
+    /// Permissionless settlement of an ended auction, selling the NFT to the highest bidder.
+    /// While sales are paused, the seller or the highest bidder can settle it by returning
+    /// the NFT and refunding the highest bid.
+    SettleAuction {
+        id: OrderId,
+    },
+    /// Escrows several NFTs that are sold together for the price of the bundle
+    SetBundle {
+        items: Vec<BundleItem<String>>,
+        details: OrderDetails<String>,
+    },
+    RemoveBundle {
+        id: OrderId,
+    },
+    /// Buys all NFTs of the bundle, the price in the details is the maximum price paid
+    AcceptBundle {
+        id: OrderId,
+        details: OrderDetails<String>,
+    },
+    /// Escrows the offered NFTs and the sent funds in exchange for the requested NFTs and funds
+    SetSwap {
+        offered_nfts: Vec<SwapNft<String>>,
+        requested_nfts: Vec<SwapRequest<String>>,
+        requested_funds: Option<Coin>,
+        recipient: Option<String>,
+        expires: Option<Timestamp>,
+    },
+    RemoveSwap {
+        id: OrderId,
+    },
+    /// Fills the swap with the given NFTs, matched in order against the requested NFTs,
+    /// and the requested funds
+    AcceptSwap {
+        id: OrderId,
+        nfts: Vec<SwapNft<String>>,
+        recipient: Option<String>,
+    },
+    /// Pays out the claimable balances of the sender in the given denoms.
+    /// Only the marketplace wide withdrawals pause applies to claims.
+    Claim {
+        denoms: Vec<Denom>,
+    },
+    /// Permissionless removal of expired orders, the caller is rewarded a share of expired bids.
+    /// Expired asks, bundles and swaps return their escrowed NFTs and funds.
+    RemoveExpiredOrders {
+        limit: Option<u32>,
+    },
+    /// Entry point for orders paid with cw20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// Entry point for NFTs sent to the marketplace, listing or selling them in one step.
+    /// No listing fee is charged as cw721 transfers cannot carry funds.
+    ReceiveNft(Cw721ReceiveMsg),
+    /// Executes a list of messages on behalf of the sender. The batch is atomic unless
+    /// best_effort is set, in which case failed messages are reported in events and skipped.
+    /// Each message carries the funds forwarded to it, so that the funds of the batch can be
+    /// split between its messages, and the funds sent with the batch must equal their sum.
+    Batch {
+        msgs: Vec<BatchMsg>,
+        best_effort: Option<bool>,
+    },
+}
+
+/// Messages that can be sent along with cw20 tokens, the tokens are used as the funds of the order
+#[cw_serde]
+pub enum ReceiveMsg {
+    SetBid {
+        collection: String,
+        token_id: TokenId,
+        details: OrderDetails<String>,
+    },
+    SetCollectionBid {
+        collection: String,
+        details: OrderDetails<String>,
+        quantity: Option<u32>,
+    },
+    AcceptAsk {
+        id: OrderId,
+        details: OrderDetails<String>,
+    },
+    BuySpecificNft {
+        collection: String,
+        token_id: TokenId,
+        details: OrderDetails<String>,
+    },
+    BuyCollectionNft {
+        collection: String,
+        details: OrderDetails<String>,
+    },
+    BidAuction {
+        id: OrderId,
+        finder: Option<String>,
+    },
+    AcceptCounterOffer {
+        bid_id: OrderId,
+    },
+    AcceptBundle {
+        id: OrderId,
+        details: OrderDetails<String>,
+    },
+    SetSwap {
+        offered_nfts: Vec<SwapNft<String>>,
+        requested_nfts: Vec<SwapRequest<String>>,
+        requested_funds: Option<Coin>,
+        recipient: Option<String>,
+        expires: Option<Timestamp>,
+    },
+    AcceptSwap {
+        id: OrderId,
+        nfts: Vec<SwapNft<String>>,
+        recipient: Option<String>,
+    },
+}
+
+/// Messages that can be sent along with an NFT, the NFT is escrowed by the marketplace
+#[cw_serde]
+pub enum ReceiveNftMsg {
+    SetAsk {
+        details: OrderDetails<String>,
+        reserved_for: Option<Vec<String>>,
+        dutch_auction: Option<DutchAuction>,
+    },
+    SellNft {
+        details: OrderDetails<String>,
+    },
+}
+
+#[cw_serde]
+pub struct BatchMsg {
+    pub msg: ExecuteMsg,
+    /// The funds sent with the batch that are forwarded to the message, refunded if the
+    /// message fails in a best effort batch. Messages only see their own funds, so a
+    /// message cannot spend funds meant for another message of the batch.
+    pub funds: Vec<Coin>,
 }
 
 #[cw_serde]
@@ -99,12 +410,87 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Config<Addr>)]
     Config {},
-    #[returns(Option<Denom>)]
+    #[returns(Option<Asset>)]
     CollectionDenom { collection: String },
+    /// The last sale of a token, or of the collection when no token is set
+    #[returns(Option<Sale>)]
+    LastSale {
+        collection: String,
+        token_id: Option<TokenId>,
+    },
+    /// The recent sales of a collection, offsets are sale ids
+    #[returns(Vec<Sale>)]
+    SalesByCollection {
+        collection: String,
+        query_options: Option<QueryOptions<u64>>,
+    },
+    /// Order and sale counters of a collection in a denom
+    #[returns(CollectionStatsResponse)]
+    CollectionStats { collection: String, denom: Denom },
+    /// The time weighted average sale price of a collection in a denom,
+    /// windows longer than the stored observations start at the oldest observation
+    #[returns(PriceOracleResponse)]
+    CollectionPriceOracle {
+        collection: String,
+        denom: Denom,
+        window_seconds: u64,
+    },
+    #[returns(Option<FeeOverride>)]
+    CollectionFeeOverride { collection: String },
+    /// The royalty policy of a collection, collections without a policy require royalties
+    #[returns(RoyaltyPolicy)]
+    CollectionRoyaltyPolicy { collection: String },
+    /// The royalty set by the admin of a collection, paid instead of the royalty registry entry
+    #[returns(Option<CollectionRoyalty<Addr>>)]
+    CollectionRoyalty { collection: String },
+    /// A registered finder and the sale volume it took part in
+    #[returns(Option<FinderResponse>)]
+    Finder { finder: String },
+    #[returns(Vec<FinderResponse>)]
+    Finders {
+        query_options: Option<QueryOptions<String>>,
+    },
+    /// The balance an address can claim in a denom
+    #[returns(Coin)]
+    ClaimableBalance { address: String, denom: Denom },
+    /// The balances an address can claim, paginated by denom
+    #[returns(Vec<Coin>)]
+    ClaimableBalances {
+        address: String,
+        query_options: Option<QueryOptions<String>>,
+    },
+    /// The registry status of a collection, unregistered collections can be traded
+    #[returns(Option<CollectionStatus>)]
+    CollectionStatus { collection: String },
+    /// The marketplace wide pause state, or the pause state of the collection when set
+    #[returns(PauseState)]
+    PauseState { collection: Option<String> },
+    #[returns(Option<Auction>)]
+    Auction(String),
+    #[returns(Vec<Auction>)]
+    AuctionsByCollection {
+        collection: String,
+        query_options: Option<QueryOptions<String>>,
+    },
+    #[returns(Option<Bundle>)]
+    Bundle(String),
+    #[returns(Vec<Bundle>)]
+    BundlesByCreator {
+        creator: String,
+        query_options: Option<QueryOptions<String>>,
+    },
+    #[returns(Option<Swap>)]
+    Swap(String),
+    #[returns(Vec<Swap>)]
+    SwapsByCreator {
+        creator: String,
+        query_options: Option<QueryOptions<String>>,
+    },
     #[returns(Option<Ask>)]
     Ask(String),
     #[returns(Vec<Ask>)]
     Asks(Vec<String>),
+    /// Asks sorted by price, dutch auction asks are sorted by their end price
     #[returns(Vec<Ask>)]
     AsksByCollectionDenom {
         collection: String,
@@ -134,6 +520,9 @@ pub enum QueryMsg {
         collection: String,
         query_options: Option<QueryOptions<String>>,
     },
+    /// The counter-offer made against the bid, unless the bid or the ask changed since
+    #[returns(Option<CounterOffer>)]
+    CounterOffer { bid_id: String },
     #[returns(Option<CollectionBid>)]
     CollectionBid(String),
     #[returns(Vec<CollectionBid>)]
@@ -150,6 +539,61 @@ pub enum QueryMsg {
         collection: String,
         query_options: Option<QueryOptions<String>>,
     },
+    #[returns(Option<TraitBid>)]
+    TraitBid(String),
+    #[returns(Vec<TraitBid>)]
+    TraitBids(Vec<String>),
+    #[returns(Vec<TraitBid>)]
+    TraitBidsByPrice {
+        collection: String,
+        trait_type: String,
+        trait_value: String,
+        denom: Denom,
+        query_options: Option<QueryOptions<PriceOffset>>,
+    },
+    #[returns(Vec<TraitBid>)]
+    TraitBidsByCreatorCollection {
+        creator: String,
+        collection: String,
+        query_options: Option<QueryOptions<String>>,
+    },
+    #[returns(HooksResponse)]
+    AskHooks {},
+    #[returns(HooksResponse)]
+    BidHooks {},
+    #[returns(HooksResponse)]
+    CollectionBidHooks {},
+    #[returns(HooksResponse)]
+    TraitBidHooks {},
+    #[returns(HooksResponse)]
+    SaleHooks {},
+}
+
+#[cw_serde]
+pub struct PriceOracleResponse {
+    /// The time weighted average sale price over the window, zero without sales
+    pub twap: Uint128,
+    /// The number of sales within the window
+    pub samples: u64,
+    pub last_update: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct CollectionStatsResponse {
+    pub stats: CollectionStats,
+    /// The lowest priced ask of the collection in the denom, at its price at the block time
+    pub floor_ask: Option<Ask>,
+    /// The highest priced collection bid of the collection in the denom
+    pub top_collection_bid: Option<CollectionBid>,
+}
+
+#[cw_serde]
+pub struct FinderResponse {
+    pub finder: Addr,
+    /// The reward paid out to the finder. Reward is a percentage of the protocol fee
+    pub reward_bps: u64,
+    /// The sale volume per denom the finder took part in as maker or taker
+    pub volume: Vec<Coin>,
 }
 
 #[cw_serde]
@@ -157,3 +601,176 @@ pub struct PriceOffset {
     pub id: OrderId,
     pub amount: u128,
 }
+
+#[cw_serde]
+pub enum HookAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[cw_serde]
+pub struct AskHookMsg {
+    pub ask: Ask,
+}
+
+impl AskHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => AskHookExecuteMsg::AskCreatedHook(self),
+            HookAction::Update => AskHookExecuteMsg::AskUpdatedHook(self),
+            HookAction::Delete => AskHookExecuteMsg::AskDeletedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+pub enum AskHookExecuteMsg {
+    AskCreatedHook(AskHookMsg),
+    AskUpdatedHook(AskHookMsg),
+    AskDeletedHook(AskHookMsg),
+}
+
+#[cw_serde]
+pub struct BidHookMsg {
+    pub bid: Bid,
+}
+
+impl BidHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => BidHookExecuteMsg::BidCreatedHook(self),
+            HookAction::Update => BidHookExecuteMsg::BidUpdatedHook(self),
+            HookAction::Delete => BidHookExecuteMsg::BidDeletedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+pub enum BidHookExecuteMsg {
+    BidCreatedHook(BidHookMsg),
+    BidUpdatedHook(BidHookMsg),
+    BidDeletedHook(BidHookMsg),
+}
+
+#[cw_serde]
+pub struct CollectionBidHookMsg {
+    pub collection_bid: CollectionBid,
+}
+
+impl CollectionBidHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => CollectionBidHookExecuteMsg::CollectionBidCreatedHook(self),
+            HookAction::Update => CollectionBidHookExecuteMsg::CollectionBidUpdatedHook(self),
+            HookAction::Delete => CollectionBidHookExecuteMsg::CollectionBidDeletedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+pub enum CollectionBidHookExecuteMsg {
+    CollectionBidCreatedHook(CollectionBidHookMsg),
+    CollectionBidUpdatedHook(CollectionBidHookMsg),
+    CollectionBidDeletedHook(CollectionBidHookMsg),
+}
+
+#[cw_serde]
+pub struct TraitBidHookMsg {
+    pub trait_bid: TraitBid,
+}
+
+impl TraitBidHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => TraitBidHookExecuteMsg::TraitBidCreatedHook(self),
+            HookAction::Update => TraitBidHookExecuteMsg::TraitBidUpdatedHook(self),
+            HookAction::Delete => TraitBidHookExecuteMsg::TraitBidDeletedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cw_serde]
+pub enum TraitBidHookExecuteMsg {
+    TraitBidCreatedHook(TraitBidHookMsg),
+    TraitBidUpdatedHook(TraitBidHookMsg),
+    TraitBidDeletedHook(TraitBidHookMsg),
+}
+
+#[cw_serde]
+pub struct SaleHookMsg {
+    pub ask: Ask,
+    pub matching_bid: MatchingBid,
+    pub price: Coin,
+    pub seller_recipient: Addr,
+    pub nft_recipient: Addr,
+    /// The breakdown of the sale price into fees, royalties and the seller payment
+    pub payments: Vec<Payment>,
+}
+
+impl SaleHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = SaleHookExecuteMsg::SaleHook(self);
+        to_json_binary(&msg)
+    }
+}
+
+#[cw_serde]
+pub struct BundleSaleHookMsg {
+    pub bundle: Bundle,
+    pub buyer: Addr,
+    pub price: Coin,
+    pub seller_recipient: Addr,
+    pub nft_recipient: Addr,
+    /// The breakdown of the bundle price into fees, royalties and the seller payment
+    pub payments: Vec<Payment>,
+}
+
+impl BundleSaleHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = SaleHookExecuteMsg::BundleSaleHook(self);
+        to_json_binary(&msg)
+    }
+}
+
+#[cw_serde]
+pub struct SwapHookMsg {
+    pub swap: Swap,
+    pub taker: Addr,
+    /// The NFTs the taker gave in return for the offered NFTs
+    pub taker_nfts: Vec<SwapNft<Addr>>,
+    pub maker_recipient: Addr,
+    pub taker_recipient: Addr,
+    /// The breakdown of the swapped funds into royalties and the payment of the recipient
+    pub payments: Vec<Payment>,
+}
+
+impl SwapHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = SaleHookExecuteMsg::SwapHook(self);
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above messages
+#[cw_serde]
+pub enum SaleHookExecuteMsg {
+    SaleHook(SaleHookMsg),
+    BundleSaleHook(BundleSaleHookMsg),
+    SwapHook(SwapHookMsg),
+}
@@ -1,18 +1,23 @@
 use crate::{
-    helpers::generate_id,
-    msg::PriceOffset,
-    query::{
-        query_asks_by_collection_denom, query_bids_by_token_price, query_collection_bids_by_price,
+    helpers::{
+        build_collection_token_index_str, build_collection_trait_index_str, fetch_ask_custody,
+        fetch_nft_traits, generate_id, split_by_weight, sum_by_collection,
+    },
+    state::{
+        asks, auctions, bids, bundles, collection_bids, swaps, trait_bids, update_collection_stats,
+        CollectionStats, Config, Denom, TokenId, COLLECTION_STATS, COUNTER_OFFERS,
     },
-    state::{asks, bids, collection_bids, TokenId},
     ContractError,
 };
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{attr, has_coins, Addr, Api, Attribute, Coin, Deps, StdResult, Storage};
+use cosmwasm_std::{
+    attr, has_coins, Addr, Api, Attribute, BlockInfo, Coin, Decimal, Deps, Order, QuerierWrapper,
+    StdResult, Storage, Timestamp, Uint128,
+};
 use cw_address_like::AddressLike;
+use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
-use sg_index_query::{QueryBound, QueryOptions};
 use sg_marketplace_common::address::address_or;
 
 #[cw_serde]
@@ -20,6 +25,12 @@ pub struct OrderDetails<T: AddressLike> {
     pub price: Coin,
     pub recipient: Option<T>,
     pub finder: Option<T>,
+    /// The time after which the order can no longer be matched
+    pub expires: Option<Timestamp>,
+    /// Paid by the buyer for each NFT on top of the price, in the denom of the price, to the
+    /// royalty recipients of collections with an optional royalty policy. Refunded otherwise.
+    #[serde(default)]
+    pub royalty_tip: Option<Uint128>,
 }
 
 impl OrderDetails<String> {
@@ -28,13 +39,103 @@ impl OrderDetails<String> {
             price: self.price,
             recipient: maybe_addr(api, self.recipient)?,
             finder: maybe_addr(api, self.finder)?,
+            expires: self.expires,
+            royalty_tip: self.royalty_tip,
+        })
+    }
+}
+
+impl<T: AddressLike> OrderDetails<T> {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_some_and(|expires| expires <= block.time)
+    }
+
+    /// The funds a buyer pays for an NFT sold at the price, including the royalty tip
+    pub fn with_royalty_tip(&self, price: &Coin) -> StdResult<Coin> {
+        Ok(Coin {
+            denom: price.denom.clone(),
+            amount: price
+                .amount
+                .checked_add(self.royalty_tip.unwrap_or_default())?,
         })
     }
 }
 
+/// Orders without an expiration are indexed at the end of the expiration index
+pub fn expiration_index_key(expires: &Option<Timestamp>) -> u64 {
+    expires.map_or(u64::MAX, |expires| expires.seconds())
+}
+
+/// Asks without a dutch auction are indexed at the end of the dutch auction index
+pub fn dutch_auction_index_key(dutch_auction: &Option<DutchAuction>) -> u128 {
+    dutch_auction
+        .as_ref()
+        .map_or(u128::MAX, |dutch_auction| dutch_auction.end_price.u128())
+}
+
+/// Moves an order between the collection stats of its previous and its current denom
+fn track_order_stats(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    previous_denom: Option<&Denom>,
+    denom: Option<&Denom>,
+    counter: fn(&mut CollectionStats) -> &mut u64,
+) -> StdResult<()> {
+    if previous_denom == denom {
+        return Ok(());
+    }
+    if let Some(previous_denom) = previous_denom {
+        update_collection_stats(storage, collection, previous_denom, |stats| {
+            let count = counter(stats);
+            *count = count.saturating_sub(1);
+        })?;
+    }
+    if let Some(denom) = denom {
+        update_collection_stats(storage, collection, denom, |stats| *counter(stats) += 1)?;
+    }
+    Ok(())
+}
+
+#[cw_serde]
 pub enum MatchingBid {
     Bid(Bid),
     CollectionBid(CollectionBid),
+    TraitBid(TraitBid),
+}
+
+impl MatchingBid {
+    pub fn details(&self) -> &OrderDetails<Addr> {
+        match self {
+            MatchingBid::Bid(bid) => &bid.details,
+            MatchingBid::CollectionBid(collection_bid) => &collection_bid.details,
+            MatchingBid::TraitBid(trait_bid) => &trait_bid.details,
+        }
+    }
+
+    pub fn creator(&self) -> &Addr {
+        match self {
+            MatchingBid::Bid(bid) => &bid.creator,
+            MatchingBid::CollectionBid(collection_bid) => &collection_bid.creator,
+            MatchingBid::TraitBid(trait_bid) => &trait_bid.creator,
+        }
+    }
+
+    pub fn asset_recipient(&self) -> Addr {
+        match self {
+            MatchingBid::Bid(bid) => bid.asset_recipient(),
+            MatchingBid::CollectionBid(collection_bid) => collection_bid.asset_recipient(),
+            MatchingBid::TraitBid(trait_bid) => trait_bid.asset_recipient(),
+        }
+    }
+}
+
+/// A price declining linearly from the ask price at the start time
+/// to the end price at the end time
+#[cw_serde]
+pub struct DutchAuction {
+    pub end_price: Uint128,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
 }
 
 #[cw_serde]
@@ -44,6 +145,15 @@ pub struct Ask {
     pub collection: Addr,
     pub token_id: TokenId,
     pub details: OrderDetails<Addr>,
+    /// Whether the seller kept custody of the NFT and approved the marketplace
+    #[serde(default)]
+    pub non_custodial: bool,
+    /// The buyers allowed to buy the NFT, anyone can buy it when empty
+    #[serde(default)]
+    pub reserved_for: Vec<Addr>,
+    /// When set the ask price is the start price of a dutch auction
+    #[serde(default)]
+    pub dutch_auction: Option<DutchAuction>,
 }
 
 impl Ask {
@@ -52,6 +162,9 @@ impl Ask {
         collection: Addr,
         token_id: TokenId,
         details: OrderDetails<Addr>,
+        non_custodial: bool,
+        reserved_for: Vec<Addr>,
+        dutch_auction: Option<DutchAuction>,
     ) -> Self {
         Self {
             id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
@@ -59,69 +172,188 @@ impl Ask {
             collection,
             token_id,
             details,
+            non_custodial,
+            reserved_for,
+            dutch_auction,
+        }
+    }
+
+    /// The price of the ask at the block time
+    pub fn price_at(&self, block: &BlockInfo) -> Coin {
+        let Some(dutch_auction) = &self.dutch_auction else {
+            return self.details.price.clone();
+        };
+
+        let start_time = dutch_auction.start_time.seconds();
+        let end_time = dutch_auction.end_time.seconds();
+        let elapsed = block.time.seconds().clamp(start_time, end_time) - start_time;
+
+        let start_price = self.details.price.amount;
+        let decline =
+            (start_price - dutch_auction.end_price).multiply_ratio(elapsed, end_time - start_time);
+
+        Coin {
+            denom: self.details.price.denom.clone(),
+            amount: start_price - decline,
         }
     }
 
+    /// The ask with its price set to the price at the block time, used when selling the ask
+    pub fn with_price_at(mut self, block: &BlockInfo) -> Self {
+        self.details.price = self.price_at(block);
+        self
+    }
+
+    /// The lowest price the ask can be sold for
+    pub fn floor_price(&self) -> Uint128 {
+        self.dutch_auction
+            .as_ref()
+            .map_or(self.details.price.amount, |dutch_auction| {
+                dutch_auction.end_price
+            })
+    }
+
     pub fn asset_recipient(&self) -> Addr {
         address_or(self.details.recipient.as_ref(), &self.creator)
     }
 
+    pub fn is_allowed_buyer(&self, buyer: &Addr) -> bool {
+        self.reserved_for.is_empty() || self.reserved_for.contains(buyer)
+    }
+
+    /// Whether the marketplace is able to deliver the NFT of the ask. Escrowed NFTs
+    /// are always deliverable, non-custodial asks require the creator to still own
+    /// the NFT and the marketplace to still be approved.
+    pub fn has_custody(&self, querier: &QuerierWrapper, marketplace: &Addr) -> bool {
+        !self.non_custodial || fetch_ask_custody(querier, marketplace, self).is_valid()
+    }
+
     pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        let previous = asks().may_load(storage, self.id.clone())?;
         asks().save(storage, self.id.clone(), self)?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            previous.as_ref().map(|ask| &ask.details.price.denom),
+            Some(&self.details.price.denom),
+            |stats| &mut stats.listing_count,
+        )?;
         Ok(())
     }
 
     pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
         asks().remove(storage, self.id.clone())?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            Some(&self.details.price.denom),
+            None,
+            |stats| &mut stats.listing_count,
+        )?;
         Ok(())
     }
 
-    pub fn match_with_bid(&self, deps: Deps) -> Result<Option<MatchingBid>, ContractError> {
-        let top_bid = query_bids_by_token_price(
-            deps,
-            self.collection.clone(),
-            self.token_id.clone(),
-            self.details.price.denom.clone(),
-            QueryOptions {
-                descending: Some(true),
-                limit: Some(1),
-                min: Some(QueryBound::Inclusive(PriceOffset {
-                    id: "".to_string(),
-                    amount: self.details.price.amount.u128(),
-                })),
-                max: None,
-            },
-        )?
-        .pop();
+    pub fn match_with_bid(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+    ) -> Result<Option<MatchingBid>, ContractError> {
+        let min_price = Some(Bound::inclusive((
+            self.price_at(block).amount.u128(),
+            "".to_string(),
+        )));
 
-        let top_collection_bid = query_collection_bids_by_price(
-            deps,
-            self.collection.clone(),
-            self.details.price.denom.clone(),
-            QueryOptions {
-                descending: Some(true),
-                limit: Some(1),
-                min: Some(QueryBound::Inclusive(PriceOffset {
-                    id: "".to_string(),
-                    amount: self.details.price.amount.u128(),
-                })),
-                max: None,
-            },
-        )?
-        .pop();
+        let top_bid = bids()
+            .idx
+            .token_denom_price
+            .sub_prefix((
+                build_collection_token_index_str(self.collection.as_ref(), &self.token_id),
+                self.details.price.denom.clone(),
+            ))
+            .range(deps.storage, min_price.clone(), None, Order::Descending)
+            .map(|res| res.map(|(_, bid)| bid))
+            .find(|res| {
+                res.as_ref().map_or(true, |bid| {
+                    !bid.details.is_expired(block) && self.is_allowed_buyer(&bid.creator)
+                })
+            })
+            .transpose()?;
+
+        let top_collection_bid = collection_bids()
+            .idx
+            .collection_denom_price
+            .sub_prefix((self.collection.clone(), self.details.price.denom.clone()))
+            .range(deps.storage, min_price.clone(), None, Order::Descending)
+            .map(|res| res.map(|(_, collection_bid)| collection_bid))
+            .find(|res| {
+                res.as_ref().map_or(true, |collection_bid| {
+                    !collection_bid.details.is_expired(block)
+                        && self.is_allowed_buyer(&collection_bid.creator)
+                })
+            })
+            .transpose()?;
 
-        let result = match (top_bid, top_collection_bid) {
-            (Some(bid), Some(collection_bid)) => {
-                if bid.details.price.amount >= collection_bid.details.price.amount {
-                    Some(MatchingBid::Bid(bid))
-                } else {
-                    Some(MatchingBid::CollectionBid(collection_bid))
+        // Trait bids are matched against the on-chain traits of the token, which are only
+        // queried while the collection has trait bids in the denom
+        let has_trait_bids = COLLECTION_STATS
+            .may_load(
+                deps.storage,
+                (self.collection.clone(), self.details.price.denom.clone()),
+            )?
+            .is_some_and(|stats| stats.trait_bid_count > 0);
+        let nft_traits = if has_trait_bids {
+            fetch_nft_traits(&deps.querier, &self.collection, &self.token_id)
+        } else {
+            vec![]
+        };
+        let mut top_trait_bid: Option<TraitBid> = None;
+        for nft_trait in nft_traits {
+            let trait_bid = trait_bids()
+                .idx
+                .collection_trait_denom_price
+                .sub_prefix((
+                    build_collection_trait_index_str(
+                        self.collection.as_ref(),
+                        &nft_trait.trait_type,
+                        &nft_trait.value,
+                    ),
+                    self.details.price.denom.clone(),
+                ))
+                .range(deps.storage, min_price.clone(), None, Order::Descending)
+                .map(|res| res.map(|(_, trait_bid)| trait_bid))
+                .find(|res| {
+                    res.as_ref().map_or(true, |trait_bid| {
+                        !trait_bid.details.is_expired(block)
+                            && self.is_allowed_buyer(&trait_bid.creator)
+                    })
+                })
+                .transpose()?;
+
+            if let Some(trait_bid) = trait_bid {
+                if top_trait_bid.as_ref().map_or(true, |top_trait_bid| {
+                    trait_bid.details.price.amount > top_trait_bid.details.price.amount
+                }) {
+                    top_trait_bid = Some(trait_bid);
                 }
             }
-            (Some(bid), None) => Some(MatchingBid::Bid(bid)),
-            (None, Some(collection_bid)) => Some(MatchingBid::CollectionBid(collection_bid)),
-            (None, None) => None,
-        };
+        }
+
+        // Ties are resolved in favor of bids, then collection bids, then trait bids
+        let mut result: Option<MatchingBid> = None;
+        for matching_bid in [
+            top_bid.map(MatchingBid::Bid),
+            top_collection_bid.map(MatchingBid::CollectionBid),
+            top_trait_bid.map(MatchingBid::TraitBid),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if result.as_ref().map_or(true, |result| {
+                matching_bid.details().price.amount > result.details().price.amount
+            }) {
+                result = Some(matching_bid);
+            }
+        }
 
         Ok(result)
     }
@@ -129,26 +361,53 @@ impl Ask {
     pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
         let mut attributes = vec![];
         for attr_key in attr_keys {
-            let attr = match attr_key {
-                "id" => Some(attr("id", self.id.to_string())),
-                "creator" => Some(attr("creator", self.creator.to_string())),
-                "collection" => Some(attr("collection", self.collection.to_string())),
-                "token_id" => Some(attr("token_id", self.token_id.to_string())),
-                "price" => Some(attr("price", self.details.price.to_string())),
-                "recipient" => self
-                    .details
-                    .recipient
-                    .as_ref()
-                    .map(|recipient| attr("recipient", recipient.to_string())),
-                "finder" => self
-                    .details
-                    .finder
-                    .as_ref()
-                    .map(|finder| attr("finder", finder.to_string())),
-                &_ => {
-                    unreachable!("Invalid attr_key: {}", attr_key)
-                }
-            };
+            let attr =
+                match attr_key {
+                    "id" => Some(attr("id", self.id.to_string())),
+                    "creator" => Some(attr("creator", self.creator.to_string())),
+                    "collection" => Some(attr("collection", self.collection.to_string())),
+                    "token_id" => Some(attr("token_id", self.token_id.to_string())),
+                    "price" => Some(attr("price", self.details.price.to_string())),
+                    "recipient" => self
+                        .details
+                        .recipient
+                        .as_ref()
+                        .map(|recipient| attr("recipient", recipient.to_string())),
+                    "finder" => self
+                        .details
+                        .finder
+                        .as_ref()
+                        .map(|finder| attr("finder", finder.to_string())),
+                    "non_custodial" => Some(attr("non_custodial", self.non_custodial.to_string())),
+                    "end_price" => self.dutch_auction.as_ref().map(|dutch_auction| {
+                        attr("end_price", dutch_auction.end_price.to_string())
+                    }),
+                    "start_time" => self.dutch_auction.as_ref().map(|dutch_auction| {
+                        attr("start_time", dutch_auction.start_time.to_string())
+                    }),
+                    "end_time" => self
+                        .dutch_auction
+                        .as_ref()
+                        .map(|dutch_auction| attr("end_time", dutch_auction.end_time.to_string())),
+                    "reserved_for" => (!self.reserved_for.is_empty()).then(|| {
+                        attr(
+                            "reserved_for",
+                            self.reserved_for
+                                .iter()
+                                .map(|buyer| buyer.to_string())
+                                .collect::<Vec<_>>()
+                                .join(","),
+                        )
+                    }),
+                    "expires" => self
+                        .details
+                        .expires
+                        .as_ref()
+                        .map(|expires| attr("expires", expires.to_string())),
+                    &_ => {
+                        unreachable!("Invalid attr_key: {}", attr_key)
+                    }
+                };
             if let Some(value) = attr {
                 attributes.push(value);
             }
@@ -157,6 +416,74 @@ impl Ask {
     }
 }
 
+/// The cheapest asks of a collection in a denom at the block time, up to the limit and max price.
+/// The price index holds the stored price of asks, which dutch auction asks decline from, so
+/// dutch auction asks are priced at the block time and sorted in with the fixed price asks.
+/// Dutch auction asks are read by end price, until their end price can no longer beat the
+/// cheapest asks found.
+pub fn cheapest_asks(
+    deps: Deps,
+    block: &BlockInfo,
+    collection: &Addr,
+    denom: &Denom,
+    max_price: Option<Uint128>,
+    limit: usize,
+    filter: impl Fn(&Ask) -> bool,
+) -> StdResult<Vec<Ask>> {
+    let mut cheapest: Vec<Ask> = vec![];
+    if limit == 0 {
+        return Ok(cheapest);
+    }
+
+    let max = max_price.map(|price| Bound::exclusive((price.u128() + 1, "".to_string())));
+    for res in asks()
+        .idx
+        .collection_denom_price
+        .sub_prefix((collection.clone(), denom.clone()))
+        .range(deps.storage, None, max, Order::Ascending)
+    {
+        let ask = res?.1;
+        if ask.dutch_auction.is_some() || !filter(&ask) {
+            continue;
+        }
+        cheapest.push(ask);
+        if cheapest.len() == limit {
+            break;
+        }
+    }
+
+    // Asks without a dutch auction are indexed past any end price
+    let max = Bound::exclusive((
+        max_price.map_or(u128::MAX, |price| price.u128() + 1),
+        "".to_string(),
+    ));
+    for res in asks()
+        .idx
+        .collection_denom_dutch_auction
+        .sub_prefix((collection.clone(), denom.clone()))
+        .range(deps.storage, None, Some(max), Order::Ascending)
+    {
+        let ask = res?.1;
+        if cheapest.len() == limit && ask.floor_price() >= cheapest[limit - 1].details.price.amount
+        {
+            break;
+        }
+
+        let ask = ask.with_price_at(block);
+        if max_price.map_or(false, |price| ask.details.price.amount > price) || !filter(&ask) {
+            continue;
+        }
+
+        // Asks at the same price keep their order
+        let position = cheapest
+            .partition_point(|other| other.details.price.amount <= ask.details.price.amount);
+        cheapest.insert(position, ask);
+        cheapest.truncate(limit);
+    }
+
+    Ok(cheapest)
+}
+
 #[cw_serde]
 pub struct Bid {
     pub id: String,
@@ -193,23 +520,54 @@ impl Bid {
         address_or(self.details.recipient.as_ref(), &self.creator)
     }
 
+    /// The funds escrowed for the bid, the price and the royalty tip
+    pub fn escrowed_funds(&self) -> Result<Coin, ContractError> {
+        Ok(self.details.with_royalty_tip(&self.details.price)?)
+    }
+
     pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        let previous = bids().may_load(storage, self.id.clone())?;
         bids().save(storage, self.id.clone(), self)?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            previous.as_ref().map(|bid| &bid.details.price.denom),
+            Some(&self.details.price.denom),
+            |stats| &mut stats.bid_count,
+        )?;
         Ok(())
     }
 
     pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
         bids().remove(storage, self.id.clone())?;
+        COUNTER_OFFERS.remove(storage, self.id.clone());
+        track_order_stats(
+            storage,
+            &self.collection,
+            Some(&self.details.price.denom),
+            None,
+            |stats| &mut stats.bid_count,
+        )?;
         Ok(())
     }
 
-    pub fn match_with_ask(&self, deps: Deps) -> Result<Option<Ask>, ContractError> {
+    pub fn match_with_ask(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        marketplace: &Addr,
+    ) -> Result<Option<Ask>, ContractError> {
         let ask_id: String =
             generate_id(vec![self.collection.as_bytes(), self.token_id.as_bytes()]);
         let ask_option = asks().may_load(deps.storage, ask_id)?;
 
         if let Some(ask) = ask_option {
-            if has_coins(&[self.details.price.clone()], &ask.details.price) {
+            let ask = ask.with_price_at(block);
+            if has_coins(&[self.details.price.clone()], &ask.details.price)
+                && !ask.details.is_expired(block)
+                && ask.is_allowed_buyer(&self.creator)
+                && ask.has_custody(&deps.querier, marketplace)
+            {
                 return Ok(Some(ask));
             }
         };
@@ -236,6 +594,11 @@ impl Bid {
                     .finder
                     .as_ref()
                     .map(|finder| attr("finder", finder.to_string())),
+                "expires" => self
+                    .details
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
                 &_ => {
                     unreachable!("Invalid attr_key: {}", attr_key)
                 }
@@ -254,6 +617,13 @@ pub struct CollectionBid {
     pub creator: Addr,
     pub collection: Addr,
     pub details: OrderDetails<Addr>,
+    /// The number of NFTs that can still be bought by the collection bid
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+}
+
+fn default_quantity() -> u32 {
+    1
 }
 
 impl CollectionBid {
@@ -261,6 +631,7 @@ impl CollectionBid {
         creator: Addr,
         collection: Addr,
         details: OrderDetails<Addr>,
+        quantity: u32,
         height: u64,
         nonce: u64,
     ) -> Self {
@@ -273,6 +644,7 @@ impl CollectionBid {
             creator,
             collection,
             details,
+            quantity,
         }
     }
 
@@ -280,29 +652,61 @@ impl CollectionBid {
         address_or(self.details.recipient.as_ref(), &self.creator)
     }
 
+    /// The funds escrowed for the unfilled portion of the collection bid
+    pub fn escrowed_funds(&self) -> Result<Coin, ContractError> {
+        let unit_funds = self.details.with_royalty_tip(&self.details.price)?;
+        Ok(Coin {
+            denom: unit_funds.denom,
+            amount: unit_funds
+                .amount
+                .checked_mul(Uint128::from(self.quantity))?,
+        })
+    }
+
     pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        let previous = collection_bids().may_load(storage, self.id.clone())?;
         collection_bids().save(storage, self.id.clone(), self)?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            previous
+                .as_ref()
+                .map(|collection_bid| &collection_bid.details.price.denom),
+            Some(&self.details.price.denom),
+            |stats| &mut stats.collection_bid_count,
+        )?;
         Ok(())
     }
 
     pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
         collection_bids().remove(storage, self.id.clone())?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            Some(&self.details.price.denom),
+            None,
+            |stats| &mut stats.collection_bid_count,
+        )?;
         Ok(())
     }
 
-    pub fn match_with_ask(&self, deps: Deps) -> Result<Option<Ask>, ContractError> {
-        let top_ask = query_asks_by_collection_denom(
+    pub fn match_with_ask(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        marketplace: &Addr,
+    ) -> Result<Option<Ask>, ContractError> {
+        let top_ask = cheapest_asks(
             deps,
-            self.collection.clone(),
-            self.details.price.denom.clone(),
-            QueryOptions {
-                descending: Some(false),
-                limit: Some(1),
-                min: None,
-                max: Some(QueryBound::Exclusive(PriceOffset {
-                    id: "".to_string(),
-                    amount: self.details.price.amount.u128() + 1,
-                })),
+            block,
+            &self.collection,
+            &self.details.price.denom,
+            Some(self.details.price.amount),
+            1,
+            |ask| {
+                !ask.details.is_expired(block)
+                    && ask.is_allowed_buyer(&self.creator)
+                    && ask.has_custody(&deps.querier, marketplace)
             },
         )?
         .pop();
@@ -328,6 +732,624 @@ impl CollectionBid {
                     .finder
                     .as_ref()
                     .map(|finder| attr("finder", finder.to_string())),
+                "quantity" => Some(attr("quantity", self.quantity.to_string())),
+                "expires" => self
+                    .details
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
+                &_ => {
+                    unreachable!("Invalid attr_key: {}", attr_key)
+                }
+            };
+            if let Some(value) = attr {
+                attributes.push(value);
+            }
+        }
+        attributes
+    }
+}
+
+#[cw_serde]
+pub struct TraitBid {
+    pub id: String,
+    pub creator: Addr,
+    pub collection: Addr,
+    pub trait_type: String,
+    pub trait_value: String,
+    pub details: OrderDetails<Addr>,
+}
+
+impl TraitBid {
+    pub fn new(
+        creator: Addr,
+        collection: Addr,
+        trait_type: String,
+        trait_value: String,
+        details: OrderDetails<Addr>,
+        height: u64,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            id: generate_id(vec![
+                collection.as_bytes(),
+                trait_type.as_bytes(),
+                trait_value.as_bytes(),
+                height.to_be_bytes().as_ref(),
+                nonce.to_be_bytes().as_ref(),
+            ]),
+            creator,
+            collection,
+            trait_type,
+            trait_value,
+            details,
+        }
+    }
+
+    pub fn asset_recipient(&self) -> Addr {
+        address_or(self.details.recipient.as_ref(), &self.creator)
+    }
+
+    /// The funds escrowed for the bid, the price and the royalty tip
+    pub fn escrowed_funds(&self) -> Result<Coin, ContractError> {
+        Ok(self.details.with_royalty_tip(&self.details.price)?)
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        let previous = trait_bids().may_load(storage, self.id.clone())?;
+        trait_bids().save(storage, self.id.clone(), self)?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            previous
+                .as_ref()
+                .map(|trait_bid| &trait_bid.details.price.denom),
+            Some(&self.details.price.denom),
+            |stats| &mut stats.trait_bid_count,
+        )?;
+        Ok(())
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        trait_bids().remove(storage, self.id.clone())?;
+        track_order_stats(
+            storage,
+            &self.collection,
+            Some(&self.details.price.denom),
+            None,
+            |stats| &mut stats.trait_bid_count,
+        )?;
+        Ok(())
+    }
+
+    pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        for attr_key in attr_keys {
+            let attr = match attr_key {
+                "id" => Some(attr("id", self.id.to_string())),
+                "creator" => Some(attr("creator", self.creator.to_string())),
+                "collection" => Some(attr("collection", self.collection.to_string())),
+                "trait_type" => Some(attr("trait_type", self.trait_type.to_string())),
+                "trait_value" => Some(attr("trait_value", self.trait_value.to_string())),
+                "price" => Some(attr("price", self.details.price.to_string())),
+                "recipient" => self
+                    .details
+                    .recipient
+                    .as_ref()
+                    .map(|recipient| attr("recipient", recipient.to_string())),
+                "finder" => self
+                    .details
+                    .finder
+                    .as_ref()
+                    .map(|finder| attr("finder", finder.to_string())),
+                "expires" => self
+                    .details
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
+                &_ => {
+                    unreachable!("Invalid attr_key: {}", attr_key)
+                }
+            };
+            if let Some(value) = attr {
+                attributes.push(value);
+            }
+        }
+        attributes
+    }
+}
+
+#[cw_serde]
+pub struct AuctionBid {
+    pub bidder: Addr,
+    pub price: Coin,
+    pub finder: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct Auction {
+    pub id: String,
+    pub creator: Addr,
+    pub collection: Addr,
+    pub token_id: TokenId,
+    /// The minimum price of the first bid, its denom is the denom of all bids
+    pub reserve_price: Coin,
+    pub recipient: Option<Addr>,
+    /// The time after which no bids are accepted and the auction can be settled
+    pub end_time: Timestamp,
+    /// The escrowed highest bid
+    pub high_bid: Option<AuctionBid>,
+}
+
+impl Auction {
+    pub fn new(
+        creator: Addr,
+        collection: Addr,
+        token_id: TokenId,
+        reserve_price: Coin,
+        recipient: Option<Addr>,
+        end_time: Timestamp,
+    ) -> Self {
+        Self {
+            id: generate_id(vec![
+                collection.as_bytes(),
+                token_id.as_bytes(),
+                "auction".as_bytes(),
+            ]),
+            creator,
+            collection,
+            token_id,
+            reserve_price,
+            recipient,
+            end_time,
+            high_bid: None,
+        }
+    }
+
+    pub fn asset_recipient(&self) -> Addr {
+        address_or(self.recipient.as_ref(), &self.creator)
+    }
+
+    pub fn is_ended(&self, block: &BlockInfo) -> bool {
+        self.end_time <= block.time
+    }
+
+    /// The lowest price the next bid must offer
+    pub fn min_bid_price(&self, config: &Config<Addr>) -> Coin {
+        match &self.high_bid {
+            Some(high_bid) => Coin {
+                denom: high_bid.price.denom.clone(),
+                amount: high_bid.price.amount
+                    + high_bid
+                        .price
+                        .amount
+                        .mul_ceil(Decimal::bps(config.auction_min_bid_increment_bps)),
+            },
+            None => self.reserve_price.clone(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        auctions().save(storage, self.id.clone(), self)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        auctions().remove(storage, self.id.clone())?;
+        Ok(())
+    }
+
+    pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        for attr_key in attr_keys {
+            let attr = match attr_key {
+                "id" => Some(attr("id", self.id.to_string())),
+                "creator" => Some(attr("creator", self.creator.to_string())),
+                "collection" => Some(attr("collection", self.collection.to_string())),
+                "token_id" => Some(attr("token_id", self.token_id.to_string())),
+                "reserve_price" => Some(attr("reserve_price", self.reserve_price.to_string())),
+                "recipient" => self
+                    .recipient
+                    .as_ref()
+                    .map(|recipient| attr("recipient", recipient.to_string())),
+                "end_time" => Some(attr("end_time", self.end_time.to_string())),
+                "bidder" => self
+                    .high_bid
+                    .as_ref()
+                    .map(|high_bid| attr("bidder", high_bid.bidder.to_string())),
+                "price" => self
+                    .high_bid
+                    .as_ref()
+                    .map(|high_bid| attr("price", high_bid.price.to_string())),
+                &_ => {
+                    unreachable!("Invalid attr_key: {}", attr_key)
+                }
+            };
+            if let Some(value) = attr {
+                attributes.push(value);
+            }
+        }
+        attributes
+    }
+}
+
+#[cw_serde]
+pub struct BundleItem<T: AddressLike> {
+    pub collection: T,
+    pub token_id: TokenId,
+    /// The weight of the item when splitting the royalties of the bundle between its collections
+    pub royalty_weight: u64,
+}
+
+impl BundleItem<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> StdResult<BundleItem<Addr>> {
+        Ok(BundleItem {
+            collection: api.addr_validate(&self.collection)?,
+            token_id: self.token_id,
+            royalty_weight: self.royalty_weight,
+        })
+    }
+}
+
+#[cw_serde]
+pub struct Bundle {
+    pub id: String,
+    pub creator: Addr,
+    /// The escrowed NFTs, sold together for the price of the bundle
+    pub items: Vec<BundleItem<Addr>>,
+    pub details: OrderDetails<Addr>,
+}
+
+impl Bundle {
+    pub fn new(
+        creator: Addr,
+        items: Vec<BundleItem<Addr>>,
+        details: OrderDetails<Addr>,
+        height: u64,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            id: generate_id(vec![
+                creator.as_bytes(),
+                height.to_be_bytes().as_ref(),
+                nonce.to_be_bytes().as_ref(),
+                "bundle".as_bytes(),
+            ]),
+            creator,
+            items,
+            details,
+        }
+    }
+
+    pub fn asset_recipient(&self) -> Addr {
+        address_or(self.details.recipient.as_ref(), &self.creator)
+    }
+
+    /// The summed royalty weights of the items per collection, in the order of the items
+    pub fn collection_royalty_weights(&self) -> Vec<(Addr, u64)> {
+        sum_by_collection(
+            self.items
+                .iter()
+                .map(|item| (&item.collection, item.royalty_weight)),
+        )
+    }
+
+    /// The bundle price divided between the items by royalty weight, in the order of the items
+    pub fn item_prices(&self) -> Vec<Coin> {
+        split_by_weight(
+            &self.details.price,
+            self.items.iter().map(|item| item.royalty_weight),
+        )
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        bundles().save(storage, self.id.clone(), self)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        bundles().remove(storage, self.id.clone())?;
+        Ok(())
+    }
+
+    pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        for attr_key in attr_keys {
+            let attr = match attr_key {
+                "id" => Some(attr("id", self.id.to_string())),
+                "creator" => Some(attr("creator", self.creator.to_string())),
+                "items" => Some(attr(
+                    "items",
+                    self.items
+                        .iter()
+                        .map(|item| format!("{}/{}", item.collection, item.token_id))
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )),
+                "price" => Some(attr("price", self.details.price.to_string())),
+                "recipient" => self
+                    .details
+                    .recipient
+                    .as_ref()
+                    .map(|recipient| attr("recipient", recipient.to_string())),
+                "finder" => self
+                    .details
+                    .finder
+                    .as_ref()
+                    .map(|finder| attr("finder", finder.to_string())),
+                "expires" => self
+                    .details
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
+                &_ => {
+                    unreachable!("Invalid attr_key: {}", attr_key)
+                }
+            };
+            if let Some(value) = attr {
+                attributes.push(value);
+            }
+        }
+        attributes
+    }
+}
+
+#[cw_serde]
+pub struct SwapNft<T: AddressLike> {
+    pub collection: T,
+    pub token_id: TokenId,
+}
+
+impl SwapNft<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> StdResult<SwapNft<Addr>> {
+        Ok(SwapNft {
+            collection: api.addr_validate(&self.collection)?,
+            token_id: self.token_id,
+        })
+    }
+}
+
+impl<T: AddressLike> std::fmt::Display for SwapNft<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.collection, self.token_id)
+    }
+}
+
+/// An NFT requested by the creator of a swap
+#[cw_serde]
+pub enum SwapRequest<T: AddressLike> {
+    /// A specific token of the collection
+    Token { collection: T, token_id: TokenId },
+    /// Any token of the collection
+    Collection { collection: T },
+}
+
+impl SwapRequest<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> StdResult<SwapRequest<Addr>> {
+        Ok(match self {
+            SwapRequest::Token {
+                collection,
+                token_id,
+            } => SwapRequest::Token {
+                collection: api.addr_validate(&collection)?,
+                token_id,
+            },
+            SwapRequest::Collection { collection } => SwapRequest::Collection {
+                collection: api.addr_validate(&collection)?,
+            },
+        })
+    }
+}
+
+impl SwapRequest<Addr> {
+    pub fn collection(&self) -> &Addr {
+        match self {
+            SwapRequest::Token { collection, .. } => collection,
+            SwapRequest::Collection { collection } => collection,
+        }
+    }
+
+    pub fn is_fulfilled_by(&self, nft: &SwapNft<Addr>) -> bool {
+        match self {
+            SwapRequest::Token {
+                collection,
+                token_id,
+            } => *collection == nft.collection && *token_id == nft.token_id,
+            SwapRequest::Collection { collection } => *collection == nft.collection,
+        }
+    }
+}
+
+impl<T: AddressLike> std::fmt::Display for SwapRequest<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwapRequest::Token {
+                collection,
+                token_id,
+            } => write!(f, "{}/{}", collection, token_id),
+            SwapRequest::Collection { collection } => write!(f, "{}/*", collection),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Swap {
+    pub id: String,
+    pub creator: Addr,
+    /// The escrowed NFTs given to the taker
+    pub offered_nfts: Vec<SwapNft<Addr>>,
+    /// The escrowed funds given to the taker
+    pub offered_funds: Option<Coin>,
+    /// The NFTs the taker must give, matched in order against the NFTs sent by the taker
+    pub requested_nfts: Vec<SwapRequest<Addr>>,
+    /// The funds the taker must pay
+    pub requested_funds: Option<Coin>,
+    /// The recipient of the requested NFTs and funds, defaults to the creator
+    pub recipient: Option<Addr>,
+    /// The time after which the swap can no longer be accepted
+    pub expires: Option<Timestamp>,
+}
+
+impl Swap {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        creator: Addr,
+        offered_nfts: Vec<SwapNft<Addr>>,
+        offered_funds: Option<Coin>,
+        requested_nfts: Vec<SwapRequest<Addr>>,
+        requested_funds: Option<Coin>,
+        recipient: Option<Addr>,
+        expires: Option<Timestamp>,
+        height: u64,
+        nonce: u64,
+    ) -> Self {
+        Self {
+            id: generate_id(vec![
+                creator.as_bytes(),
+                height.to_be_bytes().as_ref(),
+                nonce.to_be_bytes().as_ref(),
+                "swap".as_bytes(),
+            ]),
+            creator,
+            offered_nfts,
+            offered_funds,
+            requested_nfts,
+            requested_funds,
+            recipient,
+            expires,
+        }
+    }
+
+    pub fn asset_recipient(&self) -> Addr {
+        address_or(self.recipient.as_ref(), &self.creator)
+    }
+
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_some_and(|expires| expires <= block.time)
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        swaps().save(storage, self.id.clone(), self)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage) -> Result<(), ContractError> {
+        swaps().remove(storage, self.id.clone())?;
+        Ok(())
+    }
+
+    pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        for attr_key in attr_keys {
+            let attr = match attr_key {
+                "id" => Some(attr("id", self.id.to_string())),
+                "creator" => Some(attr("creator", self.creator.to_string())),
+                "offered_nfts" => Some(attr(
+                    "offered_nfts",
+                    self.offered_nfts
+                        .iter()
+                        .map(|nft| nft.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )),
+                "offered_funds" => self
+                    .offered_funds
+                    .as_ref()
+                    .map(|funds| attr("offered_funds", funds.to_string())),
+                "requested_nfts" => Some(attr(
+                    "requested_nfts",
+                    self.requested_nfts
+                        .iter()
+                        .map(|request| request.to_string())
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )),
+                "requested_funds" => self
+                    .requested_funds
+                    .as_ref()
+                    .map(|funds| attr("requested_funds", funds.to_string())),
+                "recipient" => self
+                    .recipient
+                    .as_ref()
+                    .map(|recipient| attr("recipient", recipient.to_string())),
+                "expires" => self
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
+                &_ => {
+                    unreachable!("Invalid attr_key: {}", attr_key)
+                }
+            };
+            if let Some(value) = attr {
+                attributes.push(value);
+            }
+        }
+        attributes
+    }
+}
+
+#[cw_serde]
+pub struct CounterOffer {
+    pub id: String,
+    /// The owner of the NFT, or the creator of its ask
+    pub creator: Addr,
+    /// The bid and the ask of the NFT when the counter-offer was made,
+    /// the counter-offer can only be accepted while both orders are unchanged
+    pub bid: Bid,
+    pub ask: Option<Ask>,
+    /// The price the bidder has to pay, the difference to the bid price is paid when accepting
+    pub price: Coin,
+    pub expires: Option<Timestamp>,
+}
+
+impl CounterOffer {
+    pub fn new(
+        creator: Addr,
+        bid: Bid,
+        ask: Option<Ask>,
+        price: Coin,
+        expires: Option<Timestamp>,
+    ) -> Self {
+        Self {
+            id: generate_id(vec![bid.id.as_bytes(), "counter-offer".as_bytes()]),
+            creator,
+            bid,
+            ask,
+            price,
+            expires,
+        }
+    }
+
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_some_and(|expires| expires <= block.time)
+    }
+
+    /// A counter-offer is stale once its bid or the ask of the NFT changed
+    pub fn is_stale(&self, storage: &dyn Storage) -> StdResult<bool> {
+        let bid = bids().may_load(storage, self.bid.id.clone())?;
+        let ask_id = generate_id(vec![
+            self.bid.collection.as_bytes(),
+            self.bid.token_id.as_bytes(),
+        ]);
+        let ask = asks().may_load(storage, ask_id)?;
+        Ok(bid.as_ref() != Some(&self.bid) || ask != self.ask)
+    }
+
+    pub fn get_event_attrs(&self, attr_keys: Vec<&str>) -> Vec<Attribute> {
+        let mut attributes = vec![];
+        for attr_key in attr_keys {
+            let attr = match attr_key {
+                "id" => Some(attr("id", self.id.to_string())),
+                "creator" => Some(attr("creator", self.creator.to_string())),
+                "bid" => Some(attr("bid", self.bid.id.to_string())),
+                "ask" => self.ask.as_ref().map(|ask| attr("ask", ask.id.to_string())),
+                "collection" => Some(attr("collection", self.bid.collection.to_string())),
+                "token_id" => Some(attr("token_id", self.bid.token_id.to_string())),
+                "price" => Some(attr("price", self.price.to_string())),
+                "expires" => self
+                    .expires
+                    .as_ref()
+                    .map(|expires| attr("expires", expires.to_string())),
                 &_ => {
                     unreachable!("Invalid attr_key: {}", attr_key)
                 }
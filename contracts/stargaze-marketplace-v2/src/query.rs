@@ -1,18 +1,33 @@
 use crate::{
-    helpers::build_collection_token_index_str,
-    msg::{PriceOffset, QueryMsg},
-    orders::{Ask, Bid, CollectionBid},
-    state::{asks, bids, collection_bids, Config, Denom, OrderId, COLLECTION_DENOMS, CONFIG},
+    helpers::{build_collection_token_index_str, build_collection_trait_index_str},
+    msg::{CollectionStatsResponse, FinderResponse, PriceOffset, PriceOracleResponse, QueryMsg},
+    orders::{
+        cheapest_asks, Ask, Auction, Bid, Bundle, CollectionBid, CounterOffer, Swap, TraitBid,
+    },
+    state::{
+        asks, auctions, bids, bundles, collection_bids, swaps, trait_bids, CollectionRoyalty,
+        CollectionStatus, Config, Denom, FeeOverride, OrderId, PauseState, RoyaltyPolicy, Sale,
+        TokenId, ASK_HOOKS, BID_HOOKS, CLAIMABLE_BALANCES, COLLECTION_BID_HOOKS, COLLECTION_DENOMS,
+        COLLECTION_FEE_OVERRIDES, COLLECTION_PAUSE_STATES, COLLECTION_ROYALTIES,
+        COLLECTION_ROYALTY_POLICIES, COLLECTION_SALES, COLLECTION_STATS, COLLECTION_STATUSES,
+        CONFIG, COUNTER_OFFERS, FINDERS, FINDER_VOLUMES, PAUSE_STATE, PRICE_OBSERVATIONS,
+        SALE_HOOKS, TOKEN_SALES, TRAIT_BID_HOOKS,
+    },
 };
 
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, StdResult};
+use cosmwasm_std::{
+    coin, ensure, to_json_binary, Addr, Binary, BlockInfo, Coin, Deps, Env, Order, StdError,
+    StdResult, Timestamp, Uint128, Uint256,
+};
+use cw_utils::maybe_addr;
 use sg_index_query::{QueryOptions, QueryOptionsInternal};
+use sg_marketplace_common::asset::Asset;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let api = deps.api;
 
     match msg {
@@ -21,6 +36,103 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             deps,
             api.addr_validate(&collection)?,
         )?),
+        QueryMsg::CollectionStats { collection, denom } => to_json_binary(&query_collection_stats(
+            deps,
+            &env.block,
+            api.addr_validate(&collection)?,
+            denom,
+        )?),
+        QueryMsg::CollectionPriceOracle {
+            collection,
+            denom,
+            window_seconds,
+        } => to_json_binary(&query_collection_price_oracle(
+            deps,
+            &env,
+            api.addr_validate(&collection)?,
+            denom,
+            window_seconds,
+        )?),
+        QueryMsg::CollectionFeeOverride { collection } => to_json_binary(
+            &query_collection_fee_override(deps, api.addr_validate(&collection)?)?,
+        ),
+        QueryMsg::CollectionRoyaltyPolicy { collection } => to_json_binary(
+            &query_collection_royalty_policy(deps, api.addr_validate(&collection)?)?,
+        ),
+        QueryMsg::CollectionRoyalty { collection } => to_json_binary(&query_collection_royalty(
+            deps,
+            api.addr_validate(&collection)?,
+        )?),
+        QueryMsg::Finder { finder } => {
+            to_json_binary(&query_finder(deps, api.addr_validate(&finder)?)?)
+        }
+        QueryMsg::Finders { query_options } => to_json_binary(&query_finders(
+            deps,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::ClaimableBalance { address, denom } => to_json_binary(&query_claimable_balance(
+            deps,
+            api.addr_validate(&address)?,
+            denom,
+        )?),
+        QueryMsg::ClaimableBalances {
+            address,
+            query_options,
+        } => to_json_binary(&query_claimable_balances(
+            deps,
+            api.addr_validate(&address)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::CollectionStatus { collection } => to_json_binary(&query_collection_status(
+            deps,
+            api.addr_validate(&collection)?,
+        )?),
+        QueryMsg::PauseState { collection } => {
+            to_json_binary(&query_pause_state(deps, maybe_addr(api, collection)?)?)
+        }
+        QueryMsg::LastSale {
+            collection,
+            token_id,
+        } => to_json_binary(&query_last_sale(
+            deps,
+            api.addr_validate(&collection)?,
+            token_id,
+        )?),
+        QueryMsg::SalesByCollection {
+            collection,
+            query_options,
+        } => to_json_binary(&query_sales_by_collection(
+            deps,
+            api.addr_validate(&collection)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::Auction(id) => to_json_binary(&auctions().may_load(deps.storage, id)?),
+        QueryMsg::AuctionsByCollection {
+            collection,
+            query_options,
+        } => to_json_binary(&query_auctions_by_collection(
+            deps,
+            api.addr_validate(&collection)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::Bundle(id) => to_json_binary(&bundles().may_load(deps.storage, id)?),
+        QueryMsg::BundlesByCreator {
+            creator,
+            query_options,
+        } => to_json_binary(&query_bundles_by_creator(
+            deps,
+            api.addr_validate(&creator)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::Swap(id) => to_json_binary(&swaps().may_load(deps.storage, id)?),
+        QueryMsg::SwapsByCreator {
+            creator,
+            query_options,
+        } => to_json_binary(&query_swaps_by_creator(
+            deps,
+            api.addr_validate(&creator)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
         QueryMsg::Ask(id) => to_json_binary(&query_asks(deps, vec![id])?.pop()),
         QueryMsg::Asks(ids) => to_json_binary(&query_asks(deps, ids)?),
         QueryMsg::AsksByCollectionDenom {
@@ -67,6 +179,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             api.addr_validate(&collection)?,
             query_options.unwrap_or(QueryOptions::default()),
         )?),
+        QueryMsg::CounterOffer { bid_id } => to_json_binary(&query_counter_offer(deps, bid_id)?),
         QueryMsg::CollectionBid(id) => {
             to_json_binary(&query_collection_bids(deps, vec![id])?.pop())
         }
@@ -91,6 +204,37 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             api.addr_validate(&collection)?,
             query_options.unwrap_or(QueryOptions::default()),
         )?),
+        QueryMsg::TraitBid(id) => to_json_binary(&query_trait_bids(deps, vec![id])?.pop()),
+        QueryMsg::TraitBids(ids) => to_json_binary(&query_trait_bids(deps, ids)?),
+        QueryMsg::TraitBidsByPrice {
+            collection,
+            trait_type,
+            trait_value,
+            denom,
+            query_options,
+        } => to_json_binary(&query_trait_bids_by_price(
+            deps,
+            api.addr_validate(&collection)?,
+            trait_type,
+            trait_value,
+            denom,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::TraitBidsByCreatorCollection {
+            creator,
+            collection,
+            query_options,
+        } => to_json_binary(&query_trait_bids_by_creator_collection(
+            deps,
+            api.addr_validate(&creator)?,
+            api.addr_validate(&collection)?,
+            query_options.unwrap_or(QueryOptions::default()),
+        )?),
+        QueryMsg::AskHooks {} => to_json_binary(&ASK_HOOKS.query_hooks(deps)?),
+        QueryMsg::BidHooks {} => to_json_binary(&BID_HOOKS.query_hooks(deps)?),
+        QueryMsg::CollectionBidHooks {} => to_json_binary(&COLLECTION_BID_HOOKS.query_hooks(deps)?),
+        QueryMsg::TraitBidHooks {} => to_json_binary(&TRAIT_BID_HOOKS.query_hooks(deps)?),
+        QueryMsg::SaleHooks {} => to_json_binary(&SALE_HOOKS.query_hooks(deps)?),
     }
 }
 
@@ -98,10 +242,320 @@ pub fn query_config(deps: Deps) -> StdResult<Config<Addr>> {
     CONFIG.load(deps.storage)
 }
 
-pub fn query_collection_denom(deps: Deps, collection: Addr) -> StdResult<Option<Denom>> {
+pub fn query_collection_denom(deps: Deps, collection: Addr) -> StdResult<Option<Asset>> {
     COLLECTION_DENOMS.may_load(deps.storage, collection)
 }
 
+pub fn query_collection_stats(
+    deps: Deps,
+    block: &BlockInfo,
+    collection: Addr,
+    denom: Denom,
+) -> StdResult<CollectionStatsResponse> {
+    let stats = COLLECTION_STATS
+        .may_load(deps.storage, (collection.clone(), denom.clone()))?
+        .unwrap_or_default();
+
+    let floor_ask = cheapest_asks(deps, block, &collection, &denom, None, 1, |_| true)?.pop();
+
+    let top_collection_bid = collection_bids()
+        .idx
+        .collection_denom_price
+        .sub_prefix((collection, denom))
+        .range(deps.storage, None, None, Order::Descending)
+        .map(|res| res.map(|(_, collection_bid)| collection_bid))
+        .next()
+        .transpose()?;
+
+    Ok(CollectionStatsResponse {
+        stats,
+        floor_ask,
+        top_collection_bid,
+    })
+}
+
+pub fn query_collection_price_oracle(
+    deps: Deps,
+    env: &Env,
+    collection: Addr,
+    denom: Denom,
+    window_seconds: u64,
+) -> StdResult<PriceOracleResponse> {
+    ensure!(
+        window_seconds > 0,
+        StdError::generic_err("window_seconds must be greater than 0")
+    );
+
+    let observations = PRICE_OBSERVATIONS
+        .may_load(deps.storage, (collection, denom))?
+        .unwrap_or_default();
+
+    let (first, last) = match (observations.first(), observations.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            return Ok(PriceOracleResponse {
+                twap: Uint128::zero(),
+                samples: 0,
+                last_update: None,
+            })
+        }
+    };
+
+    let now = env.block.time;
+    let window_start = now
+        .seconds()
+        .saturating_sub(window_seconds)
+        .max(first.time.seconds());
+
+    // The observation whose price was in effect at the start of the window
+    let start_observation = observations
+        .iter()
+        .rev()
+        .find(|observation| observation.time.seconds() <= window_start)
+        .unwrap_or(first);
+
+    let elapsed = now.seconds().saturating_sub(window_start);
+    let twap = if elapsed == 0 {
+        last.price
+    } else {
+        let cumulative_price = last.cumulative_price_at(now)
+            - start_observation.cumulative_price_at(Timestamp::from_seconds(window_start));
+        (cumulative_price / Uint256::from(elapsed)).try_into()?
+    };
+
+    let samples = observations
+        .iter()
+        .filter(|observation| observation.time.seconds() >= window_start)
+        .count() as u64;
+
+    Ok(PriceOracleResponse {
+        twap,
+        samples,
+        last_update: Some(last.time),
+    })
+}
+
+pub fn query_collection_fee_override(
+    deps: Deps,
+    collection: Addr,
+) -> StdResult<Option<FeeOverride>> {
+    COLLECTION_FEE_OVERRIDES.may_load(deps.storage, collection)
+}
+
+pub fn query_collection_royalty_policy(deps: Deps, collection: Addr) -> StdResult<RoyaltyPolicy> {
+    Ok(COLLECTION_ROYALTY_POLICIES
+        .may_load(deps.storage, collection)?
+        .unwrap_or_default())
+}
+
+pub fn query_collection_royalty(
+    deps: Deps,
+    collection: Addr,
+) -> StdResult<Option<CollectionRoyalty<Addr>>> {
+    COLLECTION_ROYALTIES.may_load(deps.storage, collection)
+}
+
+fn finder_response(deps: Deps, finder: Addr, reward_bps: u64) -> StdResult<FinderResponse> {
+    let volume = FINDER_VOLUMES
+        .prefix(finder.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FinderResponse {
+        finder,
+        reward_bps,
+        volume,
+    })
+}
+
+pub fn query_finder(deps: Deps, finder: Addr) -> StdResult<Option<FinderResponse>> {
+    FINDERS
+        .may_load(deps.storage, finder.clone())?
+        .map(|reward_bps| finder_response(deps, finder, reward_bps))
+        .transpose()
+}
+
+pub fn query_finders(
+    deps: Deps,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<FinderResponse>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| Addr::unchecked(offset.clone())), None, None);
+
+    FINDERS
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.and_then(|(finder, reward_bps)| finder_response(deps, finder, reward_bps)))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_claimable_balance(deps: Deps, address: Addr, denom: Denom) -> StdResult<Coin> {
+    let balance = CLAIMABLE_BALANCES
+        .may_load(deps.storage, (address, denom.clone()))?
+        .unwrap_or_default();
+    Ok(coin(balance.u128(), denom))
+}
+
+pub fn query_claimable_balances(
+    deps: Deps,
+    address: Addr,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<Coin>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    CLAIMABLE_BALANCES
+        .prefix(address)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(denom, balance)| coin(balance.u128(), denom)))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_collection_status(
+    deps: Deps,
+    collection: Addr,
+) -> StdResult<Option<CollectionStatus>> {
+    COLLECTION_STATUSES.may_load(deps.storage, collection)
+}
+
+pub fn query_last_sale(
+    deps: Deps,
+    collection: Addr,
+    token_id: Option<TokenId>,
+) -> StdResult<Option<Sale>> {
+    match token_id {
+        Some(token_id) => Ok(TOKEN_SALES
+            .may_load(deps.storage, (collection, token_id))?
+            .and_then(|mut token_sales| token_sales.pop())),
+        None => COLLECTION_SALES
+            .prefix(collection)
+            .range(deps.storage, None, None, Order::Descending)
+            .map(|res| res.map(|(_, sale)| sale))
+            .next()
+            .transpose(),
+    }
+}
+
+pub fn query_sales_by_collection(
+    deps: Deps,
+    collection: Addr,
+    query_options: QueryOptions<u64>,
+) -> StdResult<Vec<Sale>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| *offset), None, None);
+
+    let results = COLLECTION_SALES
+        .prefix(collection)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, sale)| sale))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_pause_state(deps: Deps, collection: Option<Addr>) -> StdResult<PauseState> {
+    let pause_state = match collection {
+        Some(collection) => COLLECTION_PAUSE_STATES.may_load(deps.storage, collection)?,
+        None => PAUSE_STATE.may_load(deps.storage)?,
+    };
+    Ok(pause_state.unwrap_or_default())
+}
+
+pub fn query_auctions_by_collection(
+    deps: Deps,
+    collection: Addr,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<Auction>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    let results = auctions()
+        .idx
+        .collection
+        .prefix(collection)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, auction)| auction))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_counter_offer(deps: Deps, bid_id: OrderId) -> StdResult<Option<CounterOffer>> {
+    let counter_offer = COUNTER_OFFERS.may_load(deps.storage, bid_id)?;
+    match counter_offer {
+        Some(counter_offer) if !counter_offer.is_stale(deps.storage)? => Ok(Some(counter_offer)),
+        _ => Ok(None),
+    }
+}
+
+pub fn query_bundles_by_creator(
+    deps: Deps,
+    creator: Addr,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<Bundle>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    let results = bundles()
+        .idx
+        .creator
+        .prefix(creator)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, bundle)| bundle))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_swaps_by_creator(
+    deps: Deps,
+    creator: Addr,
+    query_options: QueryOptions<String>,
+) -> StdResult<Vec<Swap>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    let results = swaps()
+        .idx
+        .creator
+        .prefix(creator)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, swap)| swap))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
 pub fn query_asks(deps: Deps, ids: Vec<OrderId>) -> StdResult<Vec<Ask>> {
     let mut retval = vec![];
 
@@ -294,3 +748,71 @@ pub fn query_collection_bids_by_creator_collection(
 
     Ok(results)
 }
+
+pub fn query_trait_bids(deps: Deps, ids: Vec<OrderId>) -> StdResult<Vec<TraitBid>> {
+    let mut retval = vec![];
+
+    for id in ids {
+        let trait_bid = trait_bids().may_load(deps.storage, id)?;
+        if let Some(trait_bid) = trait_bid {
+            retval.push(trait_bid);
+        }
+    }
+
+    Ok(retval)
+}
+
+pub fn query_trait_bids_by_price(
+    deps: Deps,
+    collection: Addr,
+    trait_type: String,
+    trait_value: String,
+    denom: Denom,
+    query_options: QueryOptions<PriceOffset>,
+) -> StdResult<Vec<TraitBid>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| (offset.amount, offset.id.clone())), None, None);
+
+    let results = trait_bids()
+        .idx
+        .collection_trait_denom_price
+        .sub_prefix((
+            build_collection_trait_index_str(collection.as_ref(), &trait_type, &trait_value),
+            denom,
+        ))
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, trait_bid)| trait_bid))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
+
+pub fn query_trait_bids_by_creator_collection(
+    deps: Deps,
+    creator: Addr,
+    collection: Addr,
+    query_options: QueryOptions<OrderId>,
+) -> StdResult<Vec<TraitBid>> {
+    let QueryOptionsInternal {
+        limit,
+        order,
+        min,
+        max,
+    } = query_options.unpack(&(|offset| offset.clone()), None, None);
+
+    let results = trait_bids()
+        .idx
+        .creator_collection
+        .prefix((creator, collection))
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|(_, trait_bid)| trait_bid))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(results)
+}
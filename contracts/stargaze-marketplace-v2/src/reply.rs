@@ -0,0 +1,28 @@
+use crate::{error::ContractError, hooks::HookReply};
+
+use cosmwasm_std::{DepsMut, Env, Event, Reply, Response, SubMsgResult};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+// Hooks are dispatched with reply_on_error, a failing hook is recorded without reverting the trade
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let event_type = match HookReply::try_from(msg.id)? {
+        HookReply::Ask => "ask-hook-failed",
+        HookReply::Bid => "bid-hook-failed",
+        HookReply::CollectionBid => "collection-bid-hook-failed",
+        HookReply::Sale => "sale-hook-failed",
+        HookReply::TraitBid => "trait-bid-hook-failed",
+    };
+
+    let SubMsgResult::Err(error) = msg.result else {
+        return Err(ContractError::InternalError(
+            "hook reply without an error".to_string(),
+        ));
+    };
+
+    let response = Response::new().add_event(Event::new(event_type).add_attribute("error", error));
+
+    Ok(response)
+}
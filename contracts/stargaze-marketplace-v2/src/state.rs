@@ -1,12 +1,28 @@
-use crate::helpers::build_collection_token_index_str;
-use crate::orders::{Bid, CollectionBid};
+use crate::helpers::{build_collection_token_index_str, build_collection_trait_index_str};
+use crate::orders::{
+    dutch_auction_index_key, expiration_index_key, Auction, Bid, Bundle, CollectionBid,
+    CounterOffer, Swap, TraitBid,
+};
 use crate::ContractError;
-use crate::{constants::MAX_BASIS_POINTS, orders::Ask};
+use crate::{
+    constants::{
+        DEFAULT_AUCTION_EXTENSION_SECONDS, DEFAULT_AUCTION_MIN_BID_INCREMENT_BPS,
+        MAX_AUCTION_DURATION, MAX_BASIS_POINTS, MAX_ROYALTY_RECIPIENTS,
+    },
+    orders::Ask,
+};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{ensure, Addr, Api, Storage, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Api, BlockInfo, Coin, Decimal, StdResult, Storage, Timestamp, Uint128, Uint256,
+};
 use cw_address_like::AddressLike;
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use sg_controllers::Hooks;
+use sg_marketplace_common::{
+    asset::Asset,
+    types::{MultiRoyaltyEntry, RoyaltyRecipient},
+};
 
 pub type OrderId = String;
 pub type TokenId = String;
@@ -28,6 +44,30 @@ pub struct Config<T: AddressLike> {
     pub taker_reward_bps: u64,
     /// The default denom for all collections on the marketplace
     pub default_denom: Denom,
+    /// The reward paid out for removing an expired bid. Reward is a percentage of the bid price
+    #[serde(default)]
+    pub removal_reward_bps: u64,
+    /// When set only registered finders earn maker and taker rewards, otherwise unregistered
+    /// finders earn the default rewards
+    #[serde(default)]
+    pub registered_finders_only: bool,
+    /// How protocol fees, royalties and finder rewards are paid out, sellers are always paid directly
+    #[serde(default)]
+    pub payout_modes: PayoutModes,
+    /// Auction bids must raise the highest bid by at least this share of it
+    #[serde(default = "default_auction_min_bid_increment_bps")]
+    pub auction_min_bid_increment_bps: u64,
+    /// Bids placed shortly before the end of an auction extend it to this duration after the bid
+    #[serde(default = "default_auction_extension_seconds")]
+    pub auction_extension_seconds: u64,
+}
+
+fn default_auction_min_bid_increment_bps() -> u64 {
+    DEFAULT_AUCTION_MIN_BID_INCREMENT_BPS
+}
+
+fn default_auction_extension_seconds() -> u64 {
+    DEFAULT_AUCTION_EXTENSION_SECONDS
 }
 
 impl Config<String> {
@@ -40,6 +80,11 @@ impl Config<String> {
             maker_reward_bps: self.maker_reward_bps,
             taker_reward_bps: self.taker_reward_bps,
             default_denom: self.default_denom,
+            removal_reward_bps: self.removal_reward_bps,
+            registered_finders_only: self.registered_finders_only,
+            payout_modes: self.payout_modes,
+            auction_min_bid_increment_bps: self.auction_min_bid_increment_bps,
+            auction_extension_seconds: self.auction_extension_seconds,
         })
     }
 }
@@ -56,6 +101,22 @@ impl Config<Addr> {
                 "taker and maker reward bps must be less than 1 combined".to_string()
             )
         );
+        ensure!(
+            self.removal_reward_bps < MAX_BASIS_POINTS,
+            ContractError::InvalidInput("removal_reward_bps must be less than 1".to_string())
+        );
+        ensure!(
+            self.auction_min_bid_increment_bps <= MAX_BASIS_POINTS,
+            ContractError::InvalidInput(
+                "auction_min_bid_increment_bps must be at most 1".to_string()
+            )
+        );
+        ensure!(
+            self.auction_extension_seconds <= MAX_AUCTION_DURATION,
+            ContractError::InvalidInput(
+                "auction_extension_seconds must be at most the max auction duration".to_string()
+            )
+        );
 
         CONFIG.save(storage, self)?;
         Ok(())
@@ -64,23 +125,360 @@ impl Config<Addr> {
 
 pub const CONFIG: Item<Config<Addr>> = Item::new("C");
 
-pub const COLLECTION_DENOMS: Map<Addr, Denom> = Map::new("D");
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum PayoutMode {
+    /// Payments are transferred to the recipient as part of the sale
+    #[default]
+    Transfer,
+    /// Payments accrue to the claimable balance of the recipient
+    Claimable,
+}
+
+impl std::fmt::Display for PayoutMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayoutMode::Transfer => write!(f, "transfer"),
+            PayoutMode::Claimable => write!(f, "claimable"),
+        }
+    }
+}
+
+/// The payout mode of each kind of sale payment recipient
+#[cw_serde]
+#[derive(Default)]
+pub struct PayoutModes {
+    /// The fee manager receiving the protocol fee
+    pub protocol: PayoutMode,
+    /// The royalty recipients of collections
+    pub royalty: PayoutMode,
+    /// The finders receiving maker and taker rewards
+    pub reward: PayoutMode,
+}
+
+/// The balances recipients can claim, accrued from payments in claimable payout mode
+pub const CLAIMABLE_BALANCES: Map<(Addr, Denom), Uint128> = Map::new("cb");
+
+#[cw_serde]
+pub struct FeeOverride {
+    /// Protocol fee charged on sales of the collection
+    pub protocol_fee_bps: u64,
+    /// The reward paid out to the market maker. Reward is a percentage of the protocol fee
+    pub maker_reward_bps: u64,
+    /// The reward paid out to the market taker. Reward is a percentage of the protocol fee
+    pub taker_reward_bps: u64,
+    /// The time from which the override applies, applies immediately when unset
+    pub start_time: Option<Timestamp>,
+    /// The time from which the override no longer applies, never ends when unset
+    pub end_time: Option<Timestamp>,
+}
+
+impl FeeOverride {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        ensure!(
+            self.protocol_fee_bps < MAX_BASIS_POINTS,
+            ContractError::InvalidInput("protocol_fee_bps must be less than 1".to_string())
+        );
+        ensure!(
+            (self.maker_reward_bps + self.taker_reward_bps) < MAX_BASIS_POINTS,
+            ContractError::InvalidInput(
+                "taker and maker reward bps must be less than 1 combined".to_string()
+            )
+        );
+        if let (Some(start_time), Some(end_time)) = (self.start_time, self.end_time) {
+            ensure!(
+                start_time < end_time,
+                ContractError::InvalidInput("start_time must be before end_time".to_string())
+            );
+        }
+        Ok(())
+    }
+
+    pub fn is_active(&self, block: &BlockInfo) -> bool {
+        self.start_time
+            .map_or(true, |start_time| start_time <= block.time)
+            && self.end_time.map_or(true, |end_time| block.time < end_time)
+    }
+
+    /// The config with the fees of the override applied
+    pub fn apply(&self, config: &Config<Addr>) -> Config<Addr> {
+        Config {
+            protocol_fee_bps: self.protocol_fee_bps,
+            maker_reward_bps: self.maker_reward_bps,
+            taker_reward_bps: self.taker_reward_bps,
+            ..config.clone()
+        }
+    }
+}
+
+/// Fee overrides of partner collections, consulted instead of the config fees while active
+pub const COLLECTION_FEE_OVERRIDES: Map<Addr, FeeOverride> = Map::new("F");
+
+/// How royalties are charged on sales of a collection, capped by `max_royalty_fee_bps`
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum RoyaltyPolicy {
+    /// The royalty of the royalty registry is paid on every sale
+    #[default]
+    Required,
+    /// No royalty is charged, buyers may tip the royalty recipients on top of the price
+    Optional,
+    /// No royalty is paid
+    Zero,
+}
+
+impl std::fmt::Display for RoyaltyPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoyaltyPolicy::Required => write!(f, "required"),
+            RoyaltyPolicy::Optional => write!(f, "optional"),
+            RoyaltyPolicy::Zero => write!(f, "zero"),
+        }
+    }
+}
+
+/// Royalty policies of collections, collections without an entry require royalties
+pub const COLLECTION_ROYALTY_POLICIES: Map<Addr, RoyaltyPolicy> = Map::new("RP");
+
+/// A royalty split between several recipients, set by the admin of a collection contract
+#[cw_serde]
+pub struct CollectionRoyalty<T: AddressLike> {
+    /// The share of the sale price paid to all recipients together
+    pub share: Decimal,
+    /// The recipients and their weights, the first recipient receives the rounding remainder
+    pub recipients: Vec<(T, u64)>,
+}
+
+impl CollectionRoyalty<String> {
+    pub fn str_to_addr(self, api: &dyn Api) -> Result<CollectionRoyalty<Addr>, ContractError> {
+        Ok(CollectionRoyalty {
+            share: self.share,
+            recipients: self
+                .recipients
+                .into_iter()
+                .map(|(recipient, weight)| Ok((api.addr_validate(&recipient)?, weight)))
+                .collect::<StdResult<Vec<_>>>()?,
+        })
+    }
+}
+
+impl CollectionRoyalty<Addr> {
+    pub fn validate(&self, config: &Config<Addr>) -> Result<(), ContractError> {
+        ensure!(
+            self.share <= Decimal::bps(config.max_royalty_fee_bps),
+            ContractError::InvalidInput(
+                "royalty share must not exceed the max royalty fee".to_string()
+            )
+        );
+        ensure!(
+            !self.recipients.is_empty() && self.recipients.len() <= MAX_ROYALTY_RECIPIENTS,
+            ContractError::InvalidInput(format!(
+                "royalty must have between 1 and {} recipients",
+                MAX_ROYALTY_RECIPIENTS
+            ))
+        );
+        ensure!(
+            self.recipients.iter().all(|(_, weight)| *weight > 0),
+            ContractError::InvalidInput("royalty weights must be greater than 0".to_string())
+        );
+        Ok(())
+    }
+}
+
+impl From<CollectionRoyalty<Addr>> for MultiRoyaltyEntry {
+    fn from(collection_royalty: CollectionRoyalty<Addr>) -> Self {
+        MultiRoyaltyEntry {
+            recipients: collection_royalty
+                .recipients
+                .into_iter()
+                .map(|(recipient, weight)| RoyaltyRecipient { recipient, weight })
+                .collect(),
+            share: collection_royalty.share,
+            updated: None,
+        }
+    }
+}
+
+/// Royalties set by collection admins, paid instead of the royalty registry entry
+pub const COLLECTION_ROYALTIES: Map<Addr, CollectionRoyalty<Addr>> = Map::new("CR");
+
+/// The reward bps of registered finders, replacing the maker and taker reward bps of the config
+/// when they find a sale. Reward is a percentage of the protocol fee
+pub const FINDERS: Map<Addr, u64> = Map::new("fi");
+
+/// The sale volume registered finders took part in as maker or taker
+pub const FINDER_VOLUMES: Map<(Addr, Denom), Uint128> = Map::new("fv");
+
+/// The asset orders for a collection are priced in, collections without an entry use the default denom
+pub const COLLECTION_DENOMS: Map<Addr, Asset> = Map::new("D");
 
 pub const LISTING_FEES: Map<Denom, Uint128> = Map::new("L");
 
+#[cw_serde]
+pub enum CollectionStatus {
+    /// Collection was reviewed and can be traded
+    Allowed,
+    /// New orders and sales are rejected, open orders can still be cancelled
+    Blocked,
+    /// Like blocked, and open orders can be cancelled in bulk by the admin
+    Delisted,
+}
+
+/// The registry status of collections, collections without an entry can be traded
+pub const COLLECTION_STATUSES: Map<Addr, CollectionStatus> = Map::new("S");
+
+#[cw_serde]
+pub struct Sale {
+    /// The sequence number of the sale within the collection
+    pub id: u64,
+    pub collection: Addr,
+    pub token_id: TokenId,
+    pub price: Coin,
+    pub buyer: Addr,
+    pub seller: Addr,
+    pub time: Timestamp,
+}
+
+/// The most recent sales of a token, oldest first
+pub const TOKEN_SALES: Map<(Addr, TokenId), Vec<Sale>> = Map::new("ts");
+
+/// The most recent sales of a collection keyed by sale id
+pub const COLLECTION_SALES: Map<(Addr, u64), Sale> = Map::new("cs");
+
+/// The number of sales recorded for a collection, used as the next sale id
+pub const COLLECTION_SALE_COUNTS: Map<Addr, u64> = Map::new("csc");
+
+#[cw_serde]
+pub struct PriceObservation {
+    pub time: Timestamp,
+    pub price: Uint128,
+    /// The sum of prices weighted by the seconds they were the last sale price,
+    /// accumulated up to the time of the observation
+    pub cumulative_price: Uint256,
+}
+
+impl PriceObservation {
+    /// The cumulative price at a time at or after the observation
+    pub fn cumulative_price_at(&self, time: Timestamp) -> Uint256 {
+        let elapsed = time.seconds().saturating_sub(self.time.seconds());
+        self.cumulative_price + Uint256::from(self.price) * Uint256::from(elapsed)
+    }
+}
+
+/// The most recent sale prices of a collection in a denom, oldest first
+pub const PRICE_OBSERVATIONS: Map<(Addr, Denom), Vec<PriceObservation>> = Map::new("po");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionStats {
+    pub listing_count: u64,
+    pub bid_count: u64,
+    pub collection_bid_count: u64,
+    #[serde(default)]
+    pub trait_bid_count: u64,
+    pub sale_count: u64,
+    pub volume: Uint128,
+}
+
+/// Aggregate order and sale counters per collection and denom,
+/// orders stored before the counters were introduced are not counted
+pub const COLLECTION_STATS: Map<(Addr, Denom), CollectionStats> = Map::new("cst");
+
+pub fn update_collection_stats(
+    storage: &mut dyn Storage,
+    collection: &Addr,
+    denom: &Denom,
+    action: impl FnOnce(&mut CollectionStats),
+) -> StdResult<()> {
+    let key = (collection.clone(), denom.clone());
+    let mut stats = COLLECTION_STATS
+        .may_load(storage, key.clone())?
+        .unwrap_or_default();
+    action(&mut stats);
+    COLLECTION_STATS.save(storage, key, &stats)
+}
+
+/// The marketplace operations that can be paused by the admin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PausableOperation {
+    Orders,
+    Sales,
+    Withdrawals,
+}
+
+impl std::fmt::Display for PausableOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PausableOperation::Orders => write!(f, "orders"),
+            PausableOperation::Sales => write!(f, "sales"),
+            PausableOperation::Withdrawals => write!(f, "withdrawals"),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseState {
+    /// Creating and updating orders is paused
+    pub orders: bool,
+    /// Matching orders and finalizing sales is paused
+    pub sales: bool,
+    /// Paying out removal rewards and claiming balances is paused, refunds are still made in full.
+    /// Claims are only paused by the marketplace wide pause state.
+    pub withdrawals: bool,
+}
+
+impl PauseState {
+    pub fn is_paused(&self, operation: PausableOperation) -> bool {
+        match operation {
+            PausableOperation::Orders => self.orders,
+            PausableOperation::Sales => self.sales,
+            PausableOperation::Withdrawals => self.withdrawals,
+        }
+    }
+}
+
+/// The marketplace wide pause state, cancelling orders is never paused
+pub const PAUSE_STATE: Item<PauseState> = Item::new("P");
+
+/// The pause state of individual collections, applied on top of the marketplace wide state
+pub const COLLECTION_PAUSE_STATES: Map<Addr, PauseState> = Map::new("PC");
+
 pub const NONCE: Item<u64> = Item::new("N");
 
+/// Counter-offers of NFT owners, keyed by the bid they counter
+pub const COUNTER_OFFERS: Map<OrderId, CounterOffer> = Map::new("co");
+
+pub const ASK_HOOKS: Hooks = Hooks::new("ah");
+
+pub const BID_HOOKS: Hooks = Hooks::new("bh");
+
+pub const COLLECTION_BID_HOOKS: Hooks = Hooks::new("ch");
+
+pub const TRAIT_BID_HOOKS: Hooks = Hooks::new("th");
+
+pub const SALE_HOOKS: Hooks = Hooks::new("sh");
+
 /// Defines indices for accessing Asks
 pub struct AskIndices<'a> {
     // Index Asks by collection and denom price
     pub collection_denom_price: MultiIndex<'a, (Addr, Denom, u128), Ask, OrderId>,
+    // Index Asks by collection and denom end price of their dutch auction
+    pub collection_denom_dutch_auction: MultiIndex<'a, (Addr, Denom, u128), Ask, OrderId>,
     // Index Asks by creator and collection
     pub creator_collection: MultiIndex<'a, (Addr, Addr), Ask, OrderId>,
+    // Index Asks by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, Ask, OrderId>,
 }
 
 impl<'a> IndexList<Ask> for AskIndices<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Ask>> + '_> {
-        let v: Vec<&dyn Index<Ask>> = vec![&self.collection_denom_price, &self.creator_collection];
+        let v: Vec<&dyn Index<Ask>> = vec![
+            &self.collection_denom_price,
+            &self.collection_denom_dutch_auction,
+            &self.creator_collection,
+            &self.expiration,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -98,11 +496,27 @@ pub fn asks<'a>() -> IndexedMap<'a, OrderId, Ask, AskIndices<'a>> {
             "a",
             "a_p",
         ),
+        collection_denom_dutch_auction: MultiIndex::new(
+            |_pk: &[u8], a: &Ask| {
+                (
+                    a.collection.clone(),
+                    a.details.price.denom.clone(),
+                    dutch_auction_index_key(&a.dutch_auction),
+                )
+            },
+            "a",
+            "a_d",
+        ),
         creator_collection: MultiIndex::new(
             |_pk: &[u8], a: &Ask| (a.creator.clone(), a.collection.clone()),
             "a",
             "a_c",
         ),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], a: &Ask| expiration_index_key(&a.details.expires),
+            "a",
+            "a_e",
+        ),
     };
     IndexedMap::new("a", indexes)
 }
@@ -113,11 +527,17 @@ pub struct BidIndices<'a> {
     pub token_denom_price: MultiIndex<'a, (TokenId, Denom, u128), Bid, OrderId>,
     // Index bids by creator and collection
     pub creator_collection: MultiIndex<'a, (Addr, Addr), Bid, OrderId>,
+    // Index bids by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, Bid, OrderId>,
 }
 
 impl<'a> IndexList<Bid> for BidIndices<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bid>> + '_> {
-        let v: Vec<&dyn Index<Bid>> = vec![&self.token_denom_price, &self.creator_collection];
+        let v: Vec<&dyn Index<Bid>> = vec![
+            &self.token_denom_price,
+            &self.creator_collection,
+            &self.expiration,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -140,6 +560,11 @@ pub fn bids<'a>() -> IndexedMap<'a, OrderId, Bid, BidIndices<'a>> {
             "o",
             "o_c",
         ),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], o: &Bid| expiration_index_key(&o.details.expires),
+            "o",
+            "o_e",
+        ),
     };
     IndexedMap::new("o", indexes)
 }
@@ -150,12 +575,17 @@ pub struct CollectionBidIndices<'a> {
     pub collection_denom_price: MultiIndex<'a, (Addr, Denom, u128), CollectionBid, OrderId>,
     // Index collection bids by creator
     pub creator_collection: MultiIndex<'a, (Addr, Addr), CollectionBid, OrderId>,
+    // Index collection bids by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, CollectionBid, OrderId>,
 }
 
 impl<'a> IndexList<CollectionBid> for CollectionBidIndices<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<CollectionBid>> + '_> {
-        let v: Vec<&dyn Index<CollectionBid>> =
-            vec![&self.collection_denom_price, &self.creator_collection];
+        let v: Vec<&dyn Index<CollectionBid>> = vec![
+            &self.collection_denom_price,
+            &self.creator_collection,
+            &self.expiration,
+        ];
         Box::new(v.into_iter())
     }
 }
@@ -178,6 +608,137 @@ pub fn collection_bids<'a>() -> IndexedMap<'a, OrderId, CollectionBid, Collectio
             "c",
             "c_c",
         ),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], co: &CollectionBid| expiration_index_key(&co.details.expires),
+            "c",
+            "c_e",
+        ),
     };
     IndexedMap::new("c", indexes)
 }
+
+/// Defines incides for accessing trait bids
+pub struct TraitBidIndices<'a> {
+    // Index trait bids by collection and trait, sorted by denom price
+    pub collection_trait_denom_price: MultiIndex<'a, (String, Denom, u128), TraitBid, OrderId>,
+    // Index trait bids by creator and collection
+    pub creator_collection: MultiIndex<'a, (Addr, Addr), TraitBid, OrderId>,
+    // Index trait bids by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, TraitBid, OrderId>,
+}
+
+impl<'a> IndexList<TraitBid> for TraitBidIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TraitBid>> + '_> {
+        let v: Vec<&dyn Index<TraitBid>> = vec![
+            &self.collection_trait_denom_price,
+            &self.creator_collection,
+            &self.expiration,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn trait_bids<'a>() -> IndexedMap<'a, OrderId, TraitBid, TraitBidIndices<'a>> {
+    let indexes = TraitBidIndices {
+        collection_trait_denom_price: MultiIndex::new(
+            |_pk: &[u8], tb: &TraitBid| {
+                (
+                    build_collection_trait_index_str(
+                        tb.collection.as_ref(),
+                        &tb.trait_type,
+                        &tb.trait_value,
+                    ),
+                    tb.details.price.denom.clone(),
+                    tb.details.price.amount.u128(),
+                )
+            },
+            "t",
+            "t_p",
+        ),
+        creator_collection: MultiIndex::new(
+            |_pk: &[u8], tb: &TraitBid| (tb.creator.clone(), tb.collection.clone()),
+            "t",
+            "t_c",
+        ),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], tb: &TraitBid| expiration_index_key(&tb.details.expires),
+            "t",
+            "t_e",
+        ),
+    };
+    IndexedMap::new("t", indexes)
+}
+
+/// Defines indices for accessing Auctions
+pub struct AuctionIndices<'a> {
+    // Index Auctions by collection
+    pub collection: MultiIndex<'a, Addr, Auction, OrderId>,
+}
+
+impl<'a> IndexList<Auction> for AuctionIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![&self.collection];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn auctions<'a>() -> IndexedMap<'a, OrderId, Auction, AuctionIndices<'a>> {
+    let indexes = AuctionIndices {
+        collection: MultiIndex::new(|_pk: &[u8], au: &Auction| au.collection.clone(), "u", "u_c"),
+    };
+    IndexedMap::new("u", indexes)
+}
+
+/// Defines indices for accessing Bundles
+pub struct BundleIndices<'a> {
+    // Index Bundles by creator
+    pub creator: MultiIndex<'a, Addr, Bundle, OrderId>,
+    // Index Bundles by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, Bundle, OrderId>,
+}
+
+impl<'a> IndexList<Bundle> for BundleIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bundle>> + '_> {
+        let v: Vec<&dyn Index<Bundle>> = vec![&self.creator, &self.expiration];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn bundles<'a>() -> IndexedMap<'a, OrderId, Bundle, BundleIndices<'a>> {
+    let indexes = BundleIndices {
+        creator: MultiIndex::new(|_pk: &[u8], bu: &Bundle| bu.creator.clone(), "bn", "bn_c"),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], bu: &Bundle| expiration_index_key(&bu.details.expires),
+            "bn",
+            "bn_e",
+        ),
+    };
+    IndexedMap::new("bn", indexes)
+}
+
+/// Defines indices for accessing Swaps
+pub struct SwapIndices<'a> {
+    // Index Swaps by creator
+    pub creator: MultiIndex<'a, Addr, Swap, OrderId>,
+    // Index Swaps by expiration in seconds
+    pub expiration: MultiIndex<'a, u64, Swap, OrderId>,
+}
+
+impl<'a> IndexList<Swap> for SwapIndices<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Swap>> + '_> {
+        let v: Vec<&dyn Index<Swap>> = vec![&self.creator, &self.expiration];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn swaps<'a>() -> IndexedMap<'a, OrderId, Swap, SwapIndices<'a>> {
+    let indexes = SwapIndices {
+        creator: MultiIndex::new(|_pk: &[u8], sw: &Swap| sw.creator.clone(), "w", "w_c"),
+        expiration: MultiIndex::new(
+            |_pk: &[u8], sw: &Swap| expiration_index_key(&sw.expires),
+            "w",
+            "w_e",
+        ),
+    };
+    IndexedMap::new("w", indexes)
+}
@@ -1,7 +1,9 @@
 use crate::{
     msg::ExecuteMsg,
     orders::OrderDetails,
-    tests::setup::setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+    tests::setup::setup_contracts::{
+        Trait, TraitExtension, TraitMetadata, LISTING_FEE, NATIVE_DENOM,
+    },
 };
 
 use cosmwasm_std::{coin, Addr, Empty};
@@ -19,6 +21,35 @@ pub fn mint(app: &mut App, creator: &Addr, owner: &Addr, collection: &Addr, toke
     assert!(response.is_ok());
 }
 
+pub fn mint_with_traits(
+    app: &mut App,
+    creator: &Addr,
+    owner: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    traits: Vec<(&str, &str)>,
+) {
+    let mint_msg = Cw721ExecuteMsg::<TraitExtension, Empty>::Mint {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: Some(TraitMetadata {
+            attributes: Some(
+                traits
+                    .into_iter()
+                    .map(|(trait_type, value)| Trait {
+                        display_type: None,
+                        trait_type: trait_type.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect(),
+            ),
+        }),
+    };
+    let response = app.execute_contract(creator.clone(), collection.clone(), &mint_msg, &[]);
+    assert!(response.is_ok());
+}
+
 pub fn approve(app: &mut App, owner: &Addr, collection: &Addr, spender: &Addr, token_id: &str) {
     let approve_msg = Cw721ExecuteMsg::<Empty, Empty>::Approve {
         spender: spender.to_string(),
@@ -46,6 +77,9 @@ pub fn mint_and_set_ask(
         collection: collection.to_string(),
         token_id: token_id.to_string(),
         details,
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
 
     let response = app.execute_contract(
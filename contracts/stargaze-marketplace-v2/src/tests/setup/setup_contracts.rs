@@ -1,11 +1,20 @@
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg},
-    state::Config,
+    state::{Config, PayoutModes},
     ContractError,
 };
 
-use cosmwasm_std::{Addr, Coin, Decimal, Empty, Uint128};
-use cw721_base::InstantiateMsg as Cw721InstantiateMsg;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    Uint128,
+};
+use cw20::Cw20Coin;
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw721_base::{
+    msg::{ExecuteMsg as Cw721ExecuteMsg, QueryMsg as Cw721QueryMsg},
+    ContractError as Cw721ContractError, Cw721Contract, InstantiateMsg as Cw721InstantiateMsg,
+};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 use stargaze_royalty_registry::msg::InstantiateMsg as RoyaltyRegistryInstantiateMsg;
 use stargaze_royalty_registry::state::Config as RoyaltyRegistryConfig;
@@ -37,12 +46,120 @@ pub fn setup_cw721(app: &mut App, creator: &Addr) -> Result<Addr, ContractError>
             },
             &[],
             "CW721",
-            None,
+            Some(creator.to_string()),
         )
         .unwrap();
     Ok(royalty_registry)
 }
 
+pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn setup_cw20(
+    app: &mut App,
+    creator: &Addr,
+    initial_balances: Vec<(&Addr, u128)>,
+) -> Result<Addr, ContractError> {
+    let code_id = app.store_code(contract_cw20());
+    let token = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw20InstantiateMsg {
+                name: "Test Token".to_string(),
+                symbol: "TTK".to_string(),
+                decimals: 6,
+                initial_balances: initial_balances
+                    .into_iter()
+                    .map(|(address, amount)| Cw20Coin {
+                        address: address.to_string(),
+                        amount: Uint128::from(amount),
+                    })
+                    .collect(),
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "CW20",
+            None,
+        )
+        .unwrap();
+    Ok(token)
+}
+
+#[cw_serde]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct TraitMetadata {
+    pub attributes: Option<Vec<Trait>>,
+}
+
+pub type TraitExtension = Option<TraitMetadata>;
+
+type Cw721TraitContract<'a> = Cw721Contract<'a, TraitExtension, Empty, Empty, Empty>;
+
+fn cw721_trait_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721InstantiateMsg,
+) -> StdResult<Response> {
+    Cw721TraitContract::default().instantiate(deps, env, info, msg)
+}
+
+fn cw721_trait_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ExecuteMsg<TraitExtension, Empty>,
+) -> Result<Response, Cw721ContractError> {
+    Cw721TraitContract::default().execute(deps, env, info, msg)
+}
+
+fn cw721_trait_query(deps: Deps, env: Env, msg: Cw721QueryMsg<Empty>) -> StdResult<Binary> {
+    Cw721TraitContract::default().query(deps, env, msg)
+}
+
+pub fn contract_cw721_with_traits() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw721_trait_execute,
+        cw721_trait_instantiate,
+        cw721_trait_query,
+    );
+    Box::new(contract)
+}
+
+// Collection with on-chain metadata attributes, used for trait bids
+pub fn setup_cw721_with_traits(app: &mut App, creator: &Addr) -> Result<Addr, ContractError> {
+    let code_id = app.store_code(contract_cw721_with_traits());
+    let collection = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw721InstantiateMsg {
+                name: "Trait Collection".to_string(),
+                symbol: "TRC".to_string(),
+                minter: creator.to_string(),
+            },
+            &[],
+            "CW721",
+            None,
+        )
+        .unwrap();
+    Ok(collection)
+}
+
 pub fn contract_royalty_registry() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         stargaze_royalty_registry::execute::execute,
@@ -58,7 +175,8 @@ pub fn contract_marketplace() -> Box<dyn Contract<Empty>> {
         crate::instantiate::instantiate,
         crate::query::query,
     )
-    .with_migrate(crate::migrate::migrate);
+    .with_migrate(crate::migrate::migrate)
+    .with_reply(crate::reply::reply);
     Box::new(contract)
 }
 
@@ -98,6 +216,11 @@ pub fn setup_marketplace(
             maker_reward_bps: 4000,
             taker_reward_bps: 1000,
             default_denom: NATIVE_DENOM.to_string(),
+            removal_reward_bps: 100,
+            registered_finders_only: false,
+            payout_modes: PayoutModes::default(),
+            auction_min_bid_increment_bps: 500,
+            auction_extension_seconds: 600,
         },
     };
     let marketplace = app
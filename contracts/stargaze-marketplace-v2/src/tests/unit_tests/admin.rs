@@ -1,7 +1,7 @@
 use crate::{
     msg::{ExecuteMsg, QueryMsg},
     orders::OrderDetails,
-    state::Config,
+    state::{Config, PayoutModes},
     tests::{
         helpers::{
             marketplace::{approve, mint},
@@ -18,7 +18,7 @@ use crate::{
 
 use cosmwasm_std::{coin, Addr};
 use cw_multi_test::Executor;
-use sg_marketplace_common::MarketplaceStdError;
+use sg_marketplace_common::{asset::Asset, MarketplaceStdError};
 
 #[test]
 fn try_admin_update_config() {
@@ -40,6 +40,8 @@ fn try_admin_update_config() {
     let max_royalty_fee_bps = config.max_royalty_fee_bps + delta;
     let maker_reward_bps = config.maker_reward_bps + delta;
     let taker_reward_bps = config.taker_reward_bps + delta;
+    let auction_min_bid_increment_bps = config.auction_min_bid_increment_bps + delta;
+    let auction_extension_seconds = config.auction_extension_seconds + delta;
 
     let update_config_msg = ExecuteMsg::UpdateConfig {
         config: Config {
@@ -50,6 +52,11 @@ fn try_admin_update_config() {
             maker_reward_bps,
             taker_reward_bps,
             default_denom: NATIVE_DENOM.to_string(),
+            removal_reward_bps: 0,
+            registered_finders_only: false,
+            payout_modes: PayoutModes::default(),
+            auction_min_bid_increment_bps,
+            auction_extension_seconds,
         },
     };
 
@@ -72,6 +79,11 @@ fn try_admin_update_config() {
             maker_reward_bps: 5000,
             taker_reward_bps: 6000,
             default_denom: NATIVE_DENOM.to_string(),
+            removal_reward_bps: 0,
+            registered_finders_only: false,
+            payout_modes: PayoutModes::default(),
+            auction_min_bid_increment_bps,
+            auction_extension_seconds,
         },
     };
     // config must be checked on update
@@ -101,6 +113,11 @@ fn try_admin_update_config() {
     assert_eq!(config.max_royalty_fee_bps, max_royalty_fee_bps);
     assert_eq!(config.maker_reward_bps, maker_reward_bps);
     assert_eq!(config.taker_reward_bps, taker_reward_bps);
+    assert_eq!(
+        config.auction_min_bid_increment_bps,
+        auction_min_bid_increment_bps
+    );
+    assert_eq!(config.auction_extension_seconds, auction_extension_seconds);
 }
 
 #[test]
@@ -134,6 +151,8 @@ fn try_admin_update_collection_denom() {
             price: bid_price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -149,7 +168,7 @@ fn try_admin_update_collection_denom() {
 
     let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
         collection: collection.to_string(),
-        denom: ATOM_DENOM.to_string(),
+        denom: Asset::Native(ATOM_DENOM.to_string()),
     };
 
     // None admin cannot update config
@@ -185,6 +204,8 @@ fn try_admin_update_collection_denom() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &accept_bid, &[]);
@@ -199,6 +220,8 @@ fn try_admin_update_collection_denom() {
             price: bid_price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response =
@@ -217,6 +240,8 @@ fn try_admin_update_collection_denom() {
             price: bid_price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
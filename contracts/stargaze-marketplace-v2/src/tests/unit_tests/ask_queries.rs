@@ -43,6 +43,8 @@ fn try_query_asks_by_collection() {
                 price,
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         );
     }
@@ -132,6 +134,8 @@ fn try_query_asks_by_creator() {
                 price,
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         );
     }
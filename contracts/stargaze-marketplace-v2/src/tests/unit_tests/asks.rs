@@ -55,7 +55,12 @@ fn try_set_ask() {
             price: coin(1_000_000, NATIVE_DENOM),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(bidder, marketplace.clone(), &set_ask, &[]);
     assert_error(response, "Unauthorized: sender is not owner".to_string());
@@ -73,7 +78,12 @@ fn try_set_ask() {
             price: coin(1_000_000, JUNO_DENOM),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &set_ask, &[]);
     assert_error(
@@ -90,7 +100,12 @@ fn try_set_ask() {
             price: coin(0, NATIVE_DENOM),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &set_ask, &[]);
     assert_error(
@@ -109,7 +124,12 @@ fn try_set_ask() {
             price: price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &set_ask, &[]);
     assert_error(response, "No funds sent".to_string());
@@ -125,7 +145,12 @@ fn try_set_ask() {
             price: price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -149,7 +174,12 @@ fn try_set_ask() {
             price: price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -174,7 +204,12 @@ fn try_set_ask() {
             price: price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -238,6 +273,8 @@ pub fn try_update_ask() {
                 price: coin(1000000 + idx as u128, NATIVE_DENOM),
                 recipient: Some(recipient.to_string()),
                 finder: Some(finder.to_string()),
+                expires: None,
+                royalty_tip: None,
             },
         );
         token_ids.push(token_id.clone());
@@ -251,6 +288,8 @@ pub fn try_update_ask() {
             price: coin(1000000, NATIVE_DENOM).clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(bidder.clone(), marketplace.clone(), &update_ask, &[]);
@@ -271,6 +310,8 @@ pub fn try_update_ask() {
             price: new_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &update_ask, &[]);
@@ -320,6 +361,8 @@ pub fn try_remove_ask() {
                 price: coin(1000000 + idx as u128, NATIVE_DENOM),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         );
         token_ids.push(token_id.clone());
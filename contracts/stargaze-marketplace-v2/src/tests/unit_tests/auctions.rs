@@ -0,0 +1,552 @@
+use crate::{
+    constants::MIN_AUCTION_DURATION,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::Auction,
+    state::{Config, PauseState},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::{setup_additional_account, TestAccounts, INITIAL_BALANCE},
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Timestamp};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+const RESERVE_PRICE: u128 = 1_000_000;
+
+fn query_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+fn query_auction(app: &App, marketplace: &Addr, id: &str) -> Option<Auction> {
+    app.wrap()
+        .query_wasm_smart(marketplace, &QueryMsg::Auction(id.to_string()))
+        .unwrap()
+}
+
+fn set_auction(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    end_time: Timestamp,
+) -> String {
+    mint(
+        app,
+        &accounts.creator,
+        &accounts.owner,
+        collection,
+        token_id,
+    );
+    approve(app, &accounts.owner, collection, marketplace, token_id);
+
+    let set_auction = ExecuteMsg::SetAuction {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        reserve_price: coin(RESERVE_PRICE, NATIVE_DENOM),
+        end_time,
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    find_attrs(response.unwrap(), "wasm-set-auction", "id")
+        .pop()
+        .unwrap()
+}
+
+#[test]
+fn try_set_auction() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+
+    // Auctions must run for at least the minimum duration
+    let block_time = app.block_info().time;
+    let set_auction = ExecuteMsg::SetAuction {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        reserve_price: coin(RESERVE_PRICE, NATIVE_DENOM),
+        end_time: block_time.plus_seconds(1),
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response
+        .unwrap_err()
+        .source()
+        .unwrap()
+        .to_string()
+        .starts_with("InvalidInput: auction duration must be between"));
+
+    // Only the owner of the NFT can auction it
+    let end_time = block_time.plus_seconds(MIN_AUCTION_DURATION);
+    let set_auction = ExecuteMsg::SetAuction {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        reserve_price: coin(RESERVE_PRICE, NATIVE_DENOM),
+        end_time,
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_err());
+
+    // The NFT is escrowed by the marketplace
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let auction_id = find_attrs(response.unwrap(), "wasm-set-auction", "id")
+        .pop()
+        .unwrap();
+    assert_eq!(
+        query_owner(&app, &collection, token_id),
+        marketplace.to_string()
+    );
+
+    let auction = query_auction(&app, &marketplace, &auction_id).unwrap();
+    assert_eq!(auction.creator, accounts.owner);
+    assert_eq!(auction.end_time, end_time);
+    assert!(auction.high_bid.is_none());
+
+    let auctions: Vec<Auction> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::AuctionsByCollection {
+                collection: collection.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(auctions, vec![auction]);
+
+    // Auctions without bids can be removed by their creator
+    let remove_auction = ExecuteMsg::RemoveAuction {
+        id: auction_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &remove_auction,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of auction can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &remove_auction,
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(
+        query_owner(&app, &collection, token_id),
+        accounts.owner.to_string()
+    );
+    assert!(query_auction(&app, &marketplace, &auction_id).is_none());
+}
+
+#[test]
+fn try_bid_auction() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let bidder2 = setup_additional_account(&mut app, "bidder2").unwrap();
+
+    let token_id = "1";
+    let end_time = app.block_info().time.plus_seconds(MIN_AUCTION_DURATION * 2);
+    let auction_id = set_auction(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        end_time,
+    );
+
+    let bid_auction = ExecuteMsg::BidAuction {
+        id: auction_id.clone(),
+        finder: None,
+    };
+
+    // The creator cannot bid on their own auction
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(RESERVE_PRICE, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("auction creator cannot bid".to_string()).to_string(),
+    );
+
+    // The first bid must meet the reserve price
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(RESERVE_PRICE - 1, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!(
+            "bid must be at least {}",
+            coin(RESERVE_PRICE, NATIVE_DENOM)
+        ))
+        .to_string(),
+    );
+
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(RESERVE_PRICE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // Following bids must raise the highest bid by the minimum increment
+    let min_bid_price = coin(1_050_000, NATIVE_DENOM);
+    let response = app.execute_contract(
+        bidder2.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(min_bid_price.amount.u128() - 1, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!("bid must be at least {}", min_bid_price)).to_string(),
+    );
+
+    // Outbid bids are refunded
+    let response = app.execute_contract(
+        bidder2.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[min_bid_price.clone()],
+    );
+    assert!(response.is_ok());
+    let balance = app
+        .wrap()
+        .query_balance(&accounts.bidder, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(balance.amount.u128(), INITIAL_BALANCE);
+
+    let auction = query_auction(&app, &marketplace, &auction_id).unwrap();
+    let high_bid = auction.high_bid.unwrap();
+    assert_eq!(high_bid.bidder, bidder2);
+    assert_eq!(high_bid.price, min_bid_price);
+    assert_eq!(auction.end_time, end_time);
+
+    // Bids close to the end extend the auction
+    app.update_block(|block| block.time = end_time.minus_seconds(10));
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(2_000_000, NATIVE_DENOM)],
+    );
+    let new_end_time = find_attrs(response.unwrap(), "wasm-bid-auction", "end_time")
+        .pop()
+        .unwrap();
+    let config: Config<Addr> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Config {})
+        .unwrap();
+    let expected_end_time = app
+        .block_info()
+        .time
+        .plus_seconds(config.auction_extension_seconds);
+    assert_eq!(new_end_time, expected_end_time.to_string());
+
+    // Bids are not accepted once the auction ended
+    app.update_block(|block| block.time = expected_end_time);
+    let response = app.execute_contract(
+        bidder2.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[coin(3_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("auction has ended".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn try_settle_auction() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let end_time = app.block_info().time.plus_seconds(MIN_AUCTION_DURATION);
+    let sold_auction_id = set_auction(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "1",
+        end_time,
+    );
+    let unsold_auction_id = set_auction(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "2",
+        end_time,
+    );
+
+    let bid_price = coin(2_000_000, NATIVE_DENOM);
+    let bid_auction = ExecuteMsg::BidAuction {
+        id: sold_auction_id.clone(),
+        finder: None,
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &bid_auction,
+        &[bid_price.clone()],
+    );
+    assert!(response.is_ok());
+
+    // Auctions with bids cannot be removed
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveAuction {
+            id: sold_auction_id.clone(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("auction has bids".to_string()).to_string(),
+    );
+
+    // Auctions cannot be settled before the end time
+    let settle_auction = ExecuteMsg::SettleAuction {
+        id: sold_auction_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &settle_auction,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("auction has not ended".to_string()).to_string(),
+    );
+
+    // Settlement sells the NFT through the regular sale pipeline
+    app.update_block(|block| block.time = end_time);
+    let response = app
+        .execute_contract(
+            accounts.creator.clone(),
+            marketplace.clone(),
+            &settle_auction,
+            &[],
+        )
+        .unwrap();
+    let price = find_attrs(response.clone(), "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, bid_price.amount.to_string());
+    let action = find_attrs(response.clone(), "wasm-finalize-sale", "marketplace_action")
+        .pop()
+        .unwrap();
+    assert_eq!(action, "settle-auction");
+    assert!(!find_attrs(response, "wasm-finalize-sale", "protocol").is_empty());
+    assert_eq!(
+        query_owner(&app, &collection, "1"),
+        accounts.bidder.to_string()
+    );
+    assert!(query_auction(&app, &marketplace, &sold_auction_id).is_none());
+
+    // Auctions without bids return the NFT to the seller
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SettleAuction {
+            id: unsold_auction_id,
+        },
+        &[],
+    );
+    assert!(find_attrs(response.unwrap(), "wasm-finalize-sale", "price").is_empty());
+    assert_eq!(
+        query_owner(&app, &collection, "2"),
+        accounts.owner.to_string()
+    );
+}
+
+#[test]
+fn try_settle_auction_while_sales_paused() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let end_time = app.block_info().time.plus_seconds(MIN_AUCTION_DURATION);
+    let auction_id = set_auction(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "1",
+        end_time,
+    );
+
+    let bid_price = coin(2_000_000, NATIVE_DENOM);
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::BidAuction {
+            id: auction_id.clone(),
+            finder: None,
+        },
+        &[bid_price.clone()],
+    );
+    assert!(response.is_ok());
+
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: None,
+            pause_state: PauseState {
+                orders: false,
+                sales: true,
+                withdrawals: false,
+            },
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Only the seller or the highest bidder can settle while sales are paused
+    app.update_block(|block| block.time = end_time);
+    let settle_auction = ExecuteMsg::SettleAuction {
+        id: auction_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &settle_auction,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("sales".to_string()).to_string(),
+    );
+
+    // The highest bidder is refunded and the NFT returned to the seller
+    let bidder_balance = app
+        .wrap()
+        .query_balance(&accounts.bidder, NATIVE_DENOM)
+        .unwrap();
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &settle_auction,
+        &[],
+    );
+    assert!(find_attrs(response.unwrap(), "wasm-finalize-sale", "price").is_empty());
+    assert_eq!(
+        query_owner(&app, &collection, "1"),
+        accounts.owner.to_string()
+    );
+    let new_bidder_balance = app
+        .wrap()
+        .query_balance(&accounts.bidder, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(
+        new_bidder_balance.amount,
+        bidder_balance.amount + bid_price.amount
+    );
+    assert!(query_auction(&app, &marketplace, &auction_id).is_none());
+}
@@ -0,0 +1,207 @@
+use crate::{
+    msg::{BatchMsg, ExecuteMsg, QueryMsg},
+    orders::{Bid, OrderDetails},
+    tests::{
+        helpers::utils::{assert_error, find_attrs},
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
+use cw_utils::NativeBalance;
+use std::ops::Sub;
+
+fn set_bid_msg(collection: &Addr, token_id: &str, price: Coin) -> BatchMsg {
+    BatchMsg {
+        msg: ExecuteMsg::SetBid {
+            collection: collection.to_string(),
+            token_id: token_id.to_string(),
+            details: OrderDetails {
+                price: price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        },
+        funds: vec![price],
+    }
+}
+
+#[test]
+fn try_batch() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts { bidder, .. },
+    } = test_context();
+
+    let price = coin(1_000_000, NATIVE_DENOM);
+
+    // Batch with insufficient funds for its messages fails
+    let batch = ExecuteMsg::Batch {
+        msgs: vec![
+            set_bid_msg(&collection, "1", price.clone()),
+            set_bid_msg(&collection, "2", price.clone()),
+        ],
+        best_effort: None,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &batch,
+        &[price.clone()],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    // Nested batch fails
+    let nested_batch = ExecuteMsg::Batch {
+        msgs: vec![BatchMsg {
+            msg: batch.clone(),
+            funds: vec![],
+        }],
+        best_effort: None,
+    };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &nested_batch, &[]);
+    assert_error(
+        response,
+        ContractError::InvalidInput("batch messages cannot be nested".to_string()).to_string(),
+    );
+
+    // Batch with more funds than its messages fails
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &batch,
+        &[coin(3_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(
+            "batch funds must equal the sum of the funds of its messages".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Batch succeeds with the funds of its messages
+    let bidder_native_balances_before =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &batch,
+        &[coin(2_000_000, NATIVE_DENOM)],
+    );
+    let bid_ids = find_attrs(response.unwrap(), "wasm-set-bid", "id");
+    assert_eq!(bid_ids.len(), 2);
+
+    for bid_id in bid_ids {
+        let bid = app
+            .wrap()
+            .query_wasm_smart::<Option<Bid>>(&marketplace, &QueryMsg::Bid(bid_id))
+            .unwrap();
+        assert_eq!(bid.unwrap().creator, bidder);
+    }
+
+    let bidder_native_balances_after =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    assert_eq!(
+        bidder_native_balances_before
+            .sub(coin(2_000_000, NATIVE_DENOM))
+            .unwrap(),
+        bidder_native_balances_after
+    );
+}
+
+#[test]
+fn try_batch_failures() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts { bidder, .. },
+    } = test_context();
+
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let msgs = vec![
+        set_bid_msg(&collection, "1", price.clone()),
+        BatchMsg {
+            msg: ExecuteMsg::SetBid {
+                collection: collection.to_string(),
+                token_id: "2".to_string(),
+                details: OrderDetails {
+                    price: coin(0, NATIVE_DENOM),
+                    recipient: None,
+                    finder: None,
+                    expires: None,
+                    royalty_tip: None,
+                },
+            },
+            funds: vec![price.clone()],
+        },
+    ];
+
+    // Atomic batch with a failing message reverts entirely
+    let batch = ExecuteMsg::Batch {
+        msgs: msgs.clone(),
+        best_effort: None,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &batch,
+        &[coin(2_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("order price must be greater than 0".to_string()).to_string(),
+    );
+
+    // Best effort batch skips the failing message and refunds its funds
+    let batch = ExecuteMsg::Batch {
+        msgs,
+        best_effort: Some(true),
+    };
+    let bidder_native_balances_before =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &batch,
+        &[coin(2_000_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    let response = response.unwrap();
+
+    assert_eq!(find_attrs(response.clone(), "wasm-set-bid", "id").len(), 1);
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-batch-msg-failed", "index"),
+        vec!["1".to_string()]
+    );
+    assert_eq!(
+        find_attrs(response, "wasm-batch", "num_failed"),
+        vec!["1".to_string()]
+    );
+
+    let bidder_native_balances_after =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    assert_eq!(
+        bidder_native_balances_before.sub(price).unwrap(),
+        bidder_native_balances_after
+    );
+}
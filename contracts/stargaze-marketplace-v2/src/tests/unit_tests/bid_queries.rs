@@ -41,6 +41,8 @@ fn try_query_bids() {
                 price: bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         };
         let response =
@@ -89,6 +91,8 @@ fn try_query_bids_by_token_price() {
                 price: bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         };
         let response =
@@ -224,6 +228,8 @@ fn try_query_bids_by_creator() {
                 price: bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         };
         let response =
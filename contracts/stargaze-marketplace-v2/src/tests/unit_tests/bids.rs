@@ -43,6 +43,8 @@ fn try_set_bid() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -62,6 +64,8 @@ fn try_set_bid() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -84,6 +88,8 @@ fn try_set_bid() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -108,6 +114,8 @@ fn try_set_bid() {
             price: bid_price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let bidder_native_balances_before =
@@ -176,6 +184,8 @@ pub fn try_update_bid() {
                 price: bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         };
         let response =
@@ -195,6 +205,8 @@ pub fn try_update_bid() {
             price: coin(1000000u128, NATIVE_DENOM),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &update_bid, &[]);
@@ -214,6 +226,8 @@ pub fn try_update_bid() {
             price: new_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
 
@@ -262,6 +276,8 @@ pub fn try_remove_bid() {
                 price: price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
         },
         &[price],
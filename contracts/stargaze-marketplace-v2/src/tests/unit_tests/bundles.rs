@@ -0,0 +1,383 @@
+use crate::{
+    msg::{CollectionStatsResponse, ExecuteMsg, QueryMsg},
+    orders::{Bundle, BundleItem, OrderDetails},
+    state::Sale,
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::{setup_cw721, LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, AppResponse, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+const BUNDLE_PRICE: u128 = 10_000_000;
+
+fn query_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+fn bundle_item(collection: &Addr, token_id: &str, royalty_weight: u64) -> BundleItem<String> {
+    BundleItem {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        royalty_weight,
+    }
+}
+
+fn set_bundle(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    items: Vec<BundleItem<String>>,
+) -> anyhow::Result<AppResponse> {
+    let set_bundle = ExecuteMsg::SetBundle {
+        items,
+        details: OrderDetails {
+            price: coin(BUNDLE_PRICE, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_bundle,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    )
+}
+
+#[test]
+fn try_set_bundle() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    for token_id in ["1", "2"] {
+        mint(
+            &mut app,
+            &accounts.creator,
+            &accounts.owner,
+            &collection,
+            token_id,
+        );
+        approve(
+            &mut app,
+            &accounts.owner,
+            &collection,
+            &marketplace,
+            token_id,
+        );
+    }
+
+    // Bundles hold at least two NFTs
+    let response = set_bundle(
+        &mut app,
+        &accounts,
+        &marketplace,
+        vec![bundle_item(&collection, "1", 1)],
+    );
+    assert!(response
+        .unwrap_err()
+        .source()
+        .unwrap()
+        .to_string()
+        .starts_with("InvalidInput: number of bundle items must be between"));
+
+    // Items cannot be repeated
+    let response = set_bundle(
+        &mut app,
+        &accounts,
+        &marketplace,
+        vec![
+            bundle_item(&collection, "1", 1),
+            bundle_item(&collection, "1", 1),
+        ],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!("duplicate bundle item [{}/1]", collection))
+            .to_string(),
+    );
+
+    // Every item needs a royalty weight
+    let response = set_bundle(
+        &mut app,
+        &accounts,
+        &marketplace,
+        vec![
+            bundle_item(&collection, "1", 1),
+            bundle_item(&collection, "2", 0),
+        ],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("royalty weight must be greater than 0".to_string())
+            .to_string(),
+    );
+
+    // All NFTs of the bundle are escrowed
+    let response = set_bundle(
+        &mut app,
+        &accounts,
+        &marketplace,
+        vec![
+            bundle_item(&collection, "1", 1),
+            bundle_item(&collection, "2", 1),
+        ],
+    );
+    let bundle_id = find_attrs(response.unwrap(), "wasm-set-bundle", "id")
+        .pop()
+        .unwrap();
+    for token_id in ["1", "2"] {
+        assert_eq!(
+            query_owner(&app, &collection, token_id),
+            marketplace.to_string()
+        );
+    }
+
+    let bundles: Vec<Bundle> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::BundlesByCreator {
+                creator: accounts.owner.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].id, bundle_id);
+    assert_eq!(bundles[0].items.len(), 2);
+
+    // Only the creator can remove the bundle, the NFTs are returned
+    let remove_bundle = ExecuteMsg::RemoveBundle {
+        id: bundle_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &remove_bundle,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of bundle can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &remove_bundle,
+        &[],
+    );
+    assert!(response.is_ok());
+    for token_id in ["1", "2"] {
+        assert_eq!(
+            query_owner(&app, &collection, token_id),
+            accounts.owner.to_string()
+        );
+    }
+
+    let bundle: Option<Bundle> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Bundle(bundle_id))
+        .unwrap();
+    assert!(bundle.is_none());
+}
+
+#[test]
+fn try_accept_bundle() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let other_collection = setup_cw721(&mut app, &accounts.creator).unwrap();
+    for (collection, token_id) in [(&collection, "1"), (&other_collection, "1")] {
+        mint(
+            &mut app,
+            &accounts.creator,
+            &accounts.owner,
+            collection,
+            token_id,
+        );
+        approve(
+            &mut app,
+            &accounts.owner,
+            collection,
+            &marketplace,
+            token_id,
+        );
+    }
+
+    let response = set_bundle(
+        &mut app,
+        &accounts,
+        &marketplace,
+        vec![
+            bundle_item(&collection, "1", 3),
+            bundle_item(&other_collection, "1", 1),
+        ],
+    );
+    let bundle_id = find_attrs(response.unwrap(), "wasm-set-bundle", "id")
+        .pop()
+        .unwrap();
+
+    // The maximum price of the buyer must cover the bundle price
+    let low_price = coin(BUNDLE_PRICE - 1, NATIVE_DENOM);
+    let accept_bundle = ExecuteMsg::AcceptBundle {
+        id: bundle_id.clone(),
+        details: OrderDetails {
+            price: low_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_bundle,
+        &[low_price],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("bundle price is greater than max input".to_string())
+            .to_string(),
+    );
+
+    // All NFTs are sold atomically for the bundle price
+    let price = coin(BUNDLE_PRICE, NATIVE_DENOM);
+    let accept_bundle = ExecuteMsg::AcceptBundle {
+        id: bundle_id.clone(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app
+        .execute_contract(
+            accounts.bidder.clone(),
+            marketplace.clone(),
+            &accept_bundle,
+            &[price],
+        )
+        .unwrap();
+    let sale_price = find_attrs(response.clone(), "wasm-finalize-bundle-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(sale_price, BUNDLE_PRICE.to_string());
+    assert!(!find_attrs(response.clone(), "wasm-finalize-bundle-sale", "protocol").is_empty());
+    assert!(!find_attrs(response, "wasm-finalize-bundle-sale", "seller").is_empty());
+
+    assert_eq!(
+        query_owner(&app, &collection, "1"),
+        accounts.bidder.to_string()
+    );
+    assert_eq!(
+        query_owner(&app, &other_collection, "1"),
+        accounts.bidder.to_string()
+    );
+
+    let bundle: Option<Bundle> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Bundle(bundle_id.clone()))
+        .unwrap();
+    assert!(bundle.is_none());
+
+    // Each item is recorded as a sale at its royalty weighted share of the bundle price
+    let block_time = app.block_info().time;
+    for (collection, id, price) in [
+        (&collection, 0, BUNDLE_PRICE * 3 / 4),
+        (&other_collection, 0, BUNDLE_PRICE / 4),
+    ] {
+        let last_sale: Option<Sale> = app
+            .wrap()
+            .query_wasm_smart(
+                &marketplace,
+                &QueryMsg::LastSale {
+                    collection: collection.to_string(),
+                    token_id: Some("1".to_string()),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            last_sale,
+            Some(Sale {
+                id,
+                collection: collection.clone(),
+                token_id: "1".to_string(),
+                price: coin(price, NATIVE_DENOM),
+                buyer: accounts.bidder.clone(),
+                seller: accounts.owner.clone(),
+                time: block_time,
+            })
+        );
+
+        let stats: CollectionStatsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &marketplace,
+                &QueryMsg::CollectionStats {
+                    collection: collection.to_string(),
+                    denom: NATIVE_DENOM.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(stats.stats.sale_count, 1);
+        assert_eq!(stats.stats.volume.u128(), price);
+    }
+
+    // Sold bundles cannot be bought again
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_bundle,
+        &[coin(BUNDLE_PRICE, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!("bundle not found [{}]", bundle_id)).to_string(),
+    );
+}
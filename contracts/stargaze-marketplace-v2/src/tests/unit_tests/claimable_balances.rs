@@ -0,0 +1,213 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::{Config, PauseState, PayoutMode, PayoutModes},
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Coin};
+use cw_multi_test::Executor;
+
+fn update_pause_state(withdrawals: bool) -> ExecuteMsg {
+    ExecuteMsg::UpdatePauseState {
+        collection: None,
+        pause_state: PauseState {
+            orders: false,
+            sales: false,
+            withdrawals,
+        },
+    }
+}
+
+#[test]
+fn try_claim_protocol_fees() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    // Protocol fees accrue to the fee manager instead of being transferred
+    let config: Config<Addr> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Config {})
+        .unwrap();
+    let update_config = ExecuteMsg::UpdateConfig {
+        config: Config {
+            fee_manager: config.fee_manager.to_string(),
+            royalty_registry: config.royalty_registry.to_string(),
+            protocol_fee_bps: config.protocol_fee_bps,
+            max_royalty_fee_bps: config.max_royalty_fee_bps,
+            maker_reward_bps: config.maker_reward_bps,
+            taker_reward_bps: config.taker_reward_bps,
+            default_denom: config.default_denom,
+            removal_reward_bps: config.removal_reward_bps,
+            registered_finders_only: config.registered_finders_only,
+            payout_modes: PayoutModes {
+                protocol: PayoutMode::Claimable,
+                ..PayoutModes::default()
+            },
+            auction_min_bid_increment_bps: config.auction_min_bid_increment_bps,
+            auction_extension_seconds: config.auction_extension_seconds,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &update_config,
+        &[],
+    );
+    let payout_mode = find_attrs(response.unwrap(), "wasm-set-config", "protocol_payout_mode")
+        .pop()
+        .unwrap();
+    assert_eq!(payout_mode, "claimable");
+
+    let fee_manager_balance = app
+        .wrap()
+        .query_balance(&accounts.fee_manager, NATIVE_DENOM)
+        .unwrap();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let details = OrderDetails {
+        price: price.clone(),
+        recipient: None,
+        finder: None,
+        expires: None,
+        royalty_tip: None,
+    };
+    mint_and_set_ask(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &marketplace,
+        &collection,
+        token_id,
+        details.clone(),
+    );
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details,
+    };
+    let response = app
+        .execute_contract(
+            accounts.bidder.clone(),
+            marketplace.clone(),
+            &accept_ask,
+            &[price],
+        )
+        .unwrap();
+    let protocol_fee: u128 = find_attrs(response, "wasm-finalize-sale", "protocol")
+        .pop()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(protocol_fee, 20_000);
+
+    let balance = app
+        .wrap()
+        .query_balance(&accounts.fee_manager, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(balance, fee_manager_balance);
+
+    let claimable_balance: Coin = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::ClaimableBalance {
+                address: accounts.fee_manager.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(claimable_balance, coin(protocol_fee, NATIVE_DENOM));
+    let claimable_balances: Vec<Coin> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::ClaimableBalances {
+                address: accounts.fee_manager.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(claimable_balances, vec![coin(protocol_fee, NATIVE_DENOM)]);
+
+    // Claims are paused with withdrawals
+    let claim = ExecuteMsg::Claim {
+        denoms: vec![NATIVE_DENOM.to_string()],
+    };
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &update_pause_state(true),
+        &[],
+    );
+    assert!(response.is_ok());
+    let response = app.execute_contract(
+        accounts.fee_manager.clone(),
+        marketplace.clone(),
+        &claim,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("withdrawals".to_string()).to_string(),
+    );
+
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &update_pause_state(false),
+        &[],
+    );
+    assert!(response.is_ok());
+    let response = app.execute_contract(
+        accounts.fee_manager.clone(),
+        marketplace.clone(),
+        &claim,
+        &[],
+    );
+    let funds = find_attrs(response.unwrap(), "wasm-claim", "funds")
+        .pop()
+        .unwrap();
+    assert_eq!(funds, coin(protocol_fee, NATIVE_DENOM).to_string());
+
+    let balance = app
+        .wrap()
+        .query_balance(&accounts.fee_manager, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(
+        balance.amount.u128(),
+        fee_manager_balance.amount.u128() + protocol_fee
+    );
+
+    // Claimed balances cannot be claimed again
+    let response = app.execute_contract(
+        accounts.fee_manager.clone(),
+        marketplace.clone(),
+        &claim,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("no claimable balance in the given denoms".to_string())
+            .to_string(),
+    );
+}
@@ -42,7 +42,10 @@ fn try_query_collection_bids_by_collection() {
                 price: collection_bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
+            quantity: None,
         };
         let response = app.execute_contract(
             collection_bidder.clone(),
@@ -95,7 +98,10 @@ fn try_query_collection_bids_by_token_price() {
                 price: collection_bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
+            quantity: None,
         };
         let response = app.execute_contract(
             collection_bidder.clone(),
@@ -207,7 +213,10 @@ fn try_query_collection_bids_by_creator() {
                 price: collection_bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
+            quantity: None,
         };
         let response = app.execute_contract(
             bidder.clone(),
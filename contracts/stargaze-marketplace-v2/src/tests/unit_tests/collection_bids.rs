@@ -2,10 +2,13 @@ use crate::{
     msg::{ExecuteMsg, QueryMsg},
     orders::{CollectionBid, OrderDetails},
     tests::{
-        helpers::utils::{assert_error, find_attrs},
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
         setup::{
             setup_accounts::{setup_additional_account, TestAccounts},
-            setup_contracts::{JUNO_DENOM, NATIVE_DENOM},
+            setup_contracts::{JUNO_DENOM, LISTING_FEE, NATIVE_DENOM},
             templates::{test_context, TestContext, TestContracts},
         },
     },
@@ -40,7 +43,10 @@ fn try_set_collection_bid() {
             price: collection_bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response = app.execute_contract(
         bidder.clone(),
@@ -61,7 +67,10 @@ fn try_set_collection_bid() {
             price: collection_bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response = app.execute_contract(
         bidder.clone(),
@@ -82,7 +91,10 @@ fn try_set_collection_bid() {
             price: collection_bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response = app.execute_contract(
         bidder.clone(),
@@ -105,7 +117,10 @@ fn try_set_collection_bid() {
             price: collection_bid_price.clone(),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let bidder_native_balances_before =
         NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
@@ -176,7 +191,10 @@ pub fn try_update_collection_bid() {
                 price: collection_bid_price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
+            quantity: None,
         };
         let response = app.execute_contract(
             bidder.clone(),
@@ -199,7 +217,10 @@ pub fn try_update_collection_bid() {
             price: coin(1000000u128, NATIVE_DENOM),
             recipient: Some(recipient.to_string()),
             finder: Some(finder.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -223,7 +244,10 @@ pub fn try_update_collection_bid() {
             price: new_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
 
     let bidder_native_balances_before =
@@ -267,7 +291,10 @@ pub fn try_remove_bid() {
                 price: price.clone(),
                 recipient: None,
                 finder: None,
+                expires: None,
+                royalty_tip: None,
             },
+            quantity: None,
         },
         &[price],
     );
@@ -312,3 +339,177 @@ pub fn try_remove_bid() {
         .unwrap();
     assert!(collection_bid.is_none());
 }
+
+#[test]
+pub fn try_collection_bid_partial_fills() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let price = coin(1_000_000u128, NATIVE_DENOM);
+
+    // Create collection bid with zero quantity fails
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: Some(0),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("quantity must be greater than 0".to_string()).to_string(),
+    );
+
+    // Create collection bid for multiple NFTs escrows price * quantity
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: Some(3),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[coin(2_000_000u128, NATIVE_DENOM)],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    let bidder_native_balances_before =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[coin(3_000_000u128, NATIVE_DENOM)],
+    );
+    let collection_bid_id = find_attrs(response.unwrap(), "wasm-set-collection-bid", "id")
+        .pop()
+        .unwrap();
+
+    // Accepting the collection bid decrements the remaining quantity
+    mint(&mut app, &creator, &owner, &collection, "1");
+    approve(&mut app, &owner, &collection, &marketplace, "1");
+    let accept_collection_bid = ExecuteMsg::AcceptCollectionBid {
+        id: collection_bid_id.clone(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &accept_collection_bid,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let collection_bid = app
+        .wrap()
+        .query_wasm_smart::<Option<CollectionBid>>(
+            &marketplace,
+            &QueryMsg::CollectionBid(collection_bid_id.clone()),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(collection_bid.quantity, 2);
+
+    // Matching an ask with the collection bid decrements the remaining quantity
+    mint(&mut app, &creator, &owner, &collection, "2");
+    approve(&mut app, &owner, &collection, &marketplace, "2");
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: "2".to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let collection_bid = app
+        .wrap()
+        .query_wasm_smart::<Option<CollectionBid>>(
+            &marketplace,
+            &QueryMsg::CollectionBid(collection_bid_id.clone()),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(collection_bid.quantity, 1);
+
+    // Removing the collection bid refunds the unfilled portion
+    let remove_collection_bid = ExecuteMsg::RemoveCollectionBid {
+        id: collection_bid_id.clone(),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &remove_collection_bid,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let bidder_native_balances_after =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    assert_eq!(
+        bidder_native_balances_before
+            .sub(coin(2_000_000u128, NATIVE_DENOM))
+            .unwrap(),
+        bidder_native_balances_after
+    );
+
+    let collection_bid = app
+        .wrap()
+        .query_wasm_smart::<Option<CollectionBid>>(
+            &marketplace,
+            &QueryMsg::CollectionBid(collection_bid_id),
+        )
+        .unwrap();
+    assert!(collection_bid.is_none());
+}
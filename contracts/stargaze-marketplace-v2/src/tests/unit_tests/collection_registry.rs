@@ -0,0 +1,457 @@
+use crate::{
+    constants::MIN_AUCTION_DURATION,
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{
+        Ask, Auction, Bundle, BundleItem, CollectionBid, OrderDetails, Swap, SwapNft, SwapRequest,
+    },
+    state::CollectionStatus,
+    tests::{
+        helpers::{
+            marketplace::{approve, mint, mint_and_set_ask},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::coin;
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::Executor;
+use sg_marketplace_common::MarketplaceStdError;
+
+#[test]
+fn try_collection_registry() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: coin(500_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: Some(2),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[coin(1_000_000, NATIVE_DENOM)],
+    );
+    let collection_bid_id = find_attrs(response.unwrap(), "wasm-set-collection-bid", "id")
+        .pop()
+        .unwrap();
+
+    let block_collection = ExecuteMsg::UpdateCollectionStatus {
+        collection: collection.to_string(),
+        status: CollectionStatus::Blocked,
+    };
+
+    // Non admin cannot update the collection status
+    let response =
+        app.execute_contract(bidder.clone(), marketplace.clone(), &block_collection, &[]);
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    let response =
+        app.execute_contract(creator.clone(), marketplace.clone(), &block_collection, &[]);
+    assert!(response.is_ok());
+
+    let status: Option<CollectionStatus> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionStatus {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(status, Some(CollectionStatus::Blocked));
+
+    // Orders and sales are rejected for blocked collections
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[coin(1_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("collection is blocked".to_string()).to_string(),
+    );
+
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: ask_id.clone(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("collection is blocked".to_string()).to_string(),
+    );
+
+    // Open bids cannot be accepted either
+    mint(&mut app, &creator, &owner, &collection, "2");
+    approve(&mut app, &owner, &collection, &marketplace, "2");
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::AcceptCollectionBid {
+            id: collection_bid_id.clone(),
+            token_id: "2".to_string(),
+            details: OrderDetails {
+                price: coin(500_000, NATIVE_DENOM),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("collection is blocked".to_string()).to_string(),
+    );
+
+    // Only delisted collections can have their orders cancelled by the admin
+    let cancel_delisted_orders = ExecuteMsg::CancelDelistedOrders {
+        collection: collection.to_string(),
+        asks: vec![ask_id.clone()],
+        bids: vec![],
+        collection_bids: vec![collection_bid_id.clone()],
+        trait_bids: vec![],
+        auctions: vec![],
+        bundles: vec![],
+        swaps: vec![],
+        counter_offers: vec![],
+    };
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &cancel_delisted_orders,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("collection is not delisted".to_string()).to_string(),
+    );
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdateCollectionStatus {
+            collection: collection.to_string(),
+            status: CollectionStatus::Delisted,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let bidder_balance_before = app
+        .wrap()
+        .query_balance(&bidder, NATIVE_DENOM)
+        .unwrap()
+        .amount;
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &cancel_delisted_orders,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The escrowed NFT is returned and the collection bid is refunded in full
+    let owner_of: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of.owner, owner.to_string());
+
+    let bidder_balance_after = app
+        .wrap()
+        .query_balance(&bidder, NATIVE_DENOM)
+        .unwrap()
+        .amount;
+    assert_eq!(
+        bidder_balance_after - bidder_balance_before,
+        coin(1_000_000, NATIVE_DENOM).amount
+    );
+
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id))
+        .unwrap();
+    assert!(ask.is_none());
+    let collection_bid = app
+        .wrap()
+        .query_wasm_smart::<Option<CollectionBid>>(
+            &marketplace,
+            &QueryMsg::CollectionBid(collection_bid_id),
+        )
+        .unwrap();
+    assert!(collection_bid.is_none());
+
+    // Allowing the collection again accepts new orders
+    let response = app.execute_contract(
+        creator,
+        marketplace.clone(),
+        &ExecuteMsg::UpdateCollectionStatus {
+            collection: collection.to_string(),
+            status: CollectionStatus::Allowed,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = app.execute_contract(
+        bidder,
+        marketplace,
+        &set_collection_bid,
+        &[coin(1_000_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_cancel_delisted_escrowed_orders() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    for token_id in ["1", "2", "3", "4"] {
+        mint(&mut app, &creator, &owner, &collection, token_id);
+        approve(&mut app, &owner, &collection, &marketplace, token_id);
+    }
+
+    // An auction with a bid, a bundle and a swap escrow NFTs and funds of the collection
+    let set_auction = ExecuteMsg::SetAuction {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        reserve_price: coin(1_000_000, NATIVE_DENOM),
+        end_time: app.block_info().time.plus_seconds(MIN_AUCTION_DURATION),
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let auction_id = find_attrs(response.unwrap(), "wasm-set-auction", "id")
+        .pop()
+        .unwrap();
+    let bid_price = coin(2_000_000, NATIVE_DENOM);
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::BidAuction {
+            id: auction_id.clone(),
+            finder: None,
+        },
+        &[bid_price.clone()],
+    );
+    assert!(response.is_ok());
+
+    let set_bundle = ExecuteMsg::SetBundle {
+        items: ["2", "3"]
+            .iter()
+            .map(|token_id| BundleItem {
+                collection: collection.to_string(),
+                token_id: token_id.to_string(),
+                royalty_weight: 1,
+            })
+            .collect(),
+        details: OrderDetails {
+            price: coin(5_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_bundle,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let bundle_id = find_attrs(response.unwrap(), "wasm-set-bundle", "id")
+        .pop()
+        .unwrap();
+
+    let offered_funds = coin(500_000, NATIVE_DENOM);
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![SwapNft {
+            collection: collection.to_string(),
+            token_id: "4".to_string(),
+        }],
+        requested_nfts: vec![SwapRequest::Collection {
+            collection: collection.to_string(),
+        }],
+        requested_funds: None,
+        recipient: None,
+        expires: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_swap,
+        &[offered_funds.clone()],
+    );
+    let swap_id = find_attrs(response.unwrap(), "wasm-set-swap", "id")
+        .pop()
+        .unwrap();
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdateCollectionStatus {
+            collection: collection.to_string(),
+            status: CollectionStatus::Delisted,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let owner_balance_before = app.wrap().query_balance(&owner, NATIVE_DENOM).unwrap();
+    let bidder_balance_before = app.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap();
+
+    let response = app.execute_contract(
+        creator,
+        marketplace.clone(),
+        &ExecuteMsg::CancelDelistedOrders {
+            collection: collection.to_string(),
+            asks: vec![],
+            bids: vec![],
+            collection_bids: vec![],
+            trait_bids: vec![],
+            auctions: vec![auction_id.clone()],
+            bundles: vec![bundle_id.clone()],
+            swaps: vec![swap_id.clone()],
+            counter_offers: vec![],
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Every escrowed NFT is returned and the escrowed funds are refunded
+    for token_id in ["1", "2", "3", "4"] {
+        let owner_of: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(owner_of.owner, owner.to_string());
+    }
+
+    let owner_balance_after = app.wrap().query_balance(&owner, NATIVE_DENOM).unwrap();
+    assert_eq!(
+        owner_balance_after.amount - owner_balance_before.amount,
+        offered_funds.amount
+    );
+    let bidder_balance_after = app.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap();
+    assert_eq!(
+        bidder_balance_after.amount - bidder_balance_before.amount,
+        bid_price.amount
+    );
+
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<Option<Auction>>(&marketplace, &QueryMsg::Auction(auction_id))
+        .unwrap()
+        .is_none());
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<Option<Bundle>>(&marketplace, &QueryMsg::Bundle(bundle_id))
+        .unwrap()
+        .is_none());
+    assert!(app
+        .wrap()
+        .query_wasm_smart::<Option<Swap>>(&marketplace, &QueryMsg::Swap(swap_id))
+        .unwrap()
+        .is_none());
+}
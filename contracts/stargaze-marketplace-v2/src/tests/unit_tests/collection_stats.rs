@@ -0,0 +1,171 @@
+use crate::{
+    helpers::generate_id,
+    msg::{CollectionStatsResponse, ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::CollectionStats,
+    tests::{
+        helpers::marketplace::mint_and_set_ask,
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+};
+
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::{App, Executor};
+
+fn query_collection_stats(
+    app: &App,
+    marketplace: &Addr,
+    collection: &Addr,
+) -> CollectionStatsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            marketplace,
+            &QueryMsg::CollectionStats {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn try_collection_stats() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    // Collections without orders have empty stats
+    let response = query_collection_stats(&app, &marketplace, &collection);
+    assert_eq!(response.stats, CollectionStats::default());
+    assert!(response.floor_ask.is_none());
+    assert!(response.top_collection_bid.is_none());
+
+    for (token_id, amount) in [("1", 2_000_000u128), ("2", 3_000_000u128)] {
+        mint_and_set_ask(
+            &mut app,
+            &creator,
+            &owner,
+            &marketplace,
+            &collection,
+            token_id,
+            OrderDetails {
+                price: coin(amount, NATIVE_DENOM),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        );
+    }
+
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: "3".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response =
+        app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[bid_price]);
+    assert!(response.is_ok());
+
+    for amount in [500_000u128, 1_000_000u128] {
+        let collection_bid_price = coin(amount, NATIVE_DENOM);
+        let set_collection_bid = ExecuteMsg::SetCollectionBid {
+            collection: collection.to_string(),
+            details: OrderDetails {
+                price: collection_bid_price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+            quantity: None,
+        };
+        let response = app.execute_contract(
+            bidder.clone(),
+            marketplace.clone(),
+            &set_collection_bid,
+            &[collection_bid_price],
+        );
+        assert!(response.is_ok());
+    }
+
+    // Counters track the stored orders
+    let response = query_collection_stats(&app, &marketplace, &collection);
+    assert_eq!(
+        response.stats,
+        CollectionStats {
+            listing_count: 2,
+            bid_count: 1,
+            collection_bid_count: 2,
+            trait_bid_count: 0,
+            sale_count: 0,
+            volume: Uint128::zero(),
+        }
+    );
+    assert_eq!(response.floor_ask.unwrap().token_id, "1".to_string());
+    assert_eq!(
+        response.top_collection_bid.unwrap().details.price,
+        coin(1_000_000, NATIVE_DENOM)
+    );
+
+    // Sales update volume and remove the sold listing
+    let sale_price = coin(2_000_000, NATIVE_DENOM);
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), "1".as_bytes()]),
+        details: OrderDetails {
+            price: sale_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[sale_price],
+    );
+    assert!(response.is_ok());
+
+    let response = query_collection_stats(&app, &marketplace, &collection);
+    assert_eq!(response.stats.listing_count, 1);
+    assert_eq!(response.stats.sale_count, 1);
+    assert_eq!(response.stats.volume, Uint128::new(2_000_000));
+    assert_eq!(response.floor_ask.unwrap().token_id, "2".to_string());
+
+    // Removing orders decrements the counters
+    let remove_ask = ExecuteMsg::RemoveAsk {
+        id: generate_id(vec![collection.as_bytes(), "2".as_bytes()]),
+    };
+    let response = app.execute_contract(owner.clone(), marketplace.clone(), &remove_ask, &[]);
+    assert!(response.is_ok());
+
+    let response = query_collection_stats(&app, &marketplace, &collection);
+    assert_eq!(response.stats.listing_count, 0);
+    assert!(response.floor_ask.is_none());
+}
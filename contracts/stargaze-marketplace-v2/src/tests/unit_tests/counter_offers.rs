@@ -0,0 +1,355 @@
+use crate::{
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{CounterOffer, OrderDetails},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Coin};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+
+const BID_PRICE: u128 = 1_000_000;
+
+fn set_bid(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+) -> String {
+    let price = coin(BID_PRICE, NATIVE_DENOM);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price],
+    );
+    find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap()
+}
+
+fn set_counter_offer(bid_id: &str, price: Coin) -> ExecuteMsg {
+    ExecuteMsg::SetCounterOffer {
+        bid_id: bid_id.to_string(),
+        price,
+        expires: None,
+    }
+}
+
+fn query_counter_offer(app: &App, marketplace: &Addr, bid_id: &str) -> Option<CounterOffer> {
+    app.wrap()
+        .query_wasm_smart(
+            marketplace,
+            &QueryMsg::CounterOffer {
+                bid_id: bid_id.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn try_accept_counter_offer() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    let bid_id = set_bid(&mut app, &accounts, &marketplace, &collection, token_id);
+
+    // Only the owner of the NFT can counter the bid
+    let counter_price = coin(1_500_000, NATIVE_DENOM);
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_counter_offer(&bid_id, counter_price.clone()),
+        &[],
+    );
+    assert!(response.is_err());
+
+    // The counter price must exceed the bid price
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_counter_offer(&bid_id, coin(BID_PRICE, NATIVE_DENOM)),
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("counter price must be greater than the bid price".to_string())
+            .to_string(),
+    );
+
+    // The counter-offer is linked to the bid it counters
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_counter_offer(&bid_id, counter_price.clone()),
+        &[],
+    );
+    let response_bid_id = find_attrs(response.unwrap(), "wasm-set-counter-offer", "bid")
+        .pop()
+        .unwrap();
+    assert_eq!(response_bid_id, bid_id);
+
+    let counter_offer = query_counter_offer(&app, &marketplace, &bid_id).unwrap();
+    assert_eq!(counter_offer.creator, accounts.owner);
+    assert_eq!(counter_offer.price, counter_price);
+    assert!(counter_offer.ask.is_none());
+
+    // The bidder tops up the escrowed bid to the counter price
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+    let accept_counter_offer = ExecuteMsg::AcceptCounterOffer {
+        bid_id: bid_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_counter_offer,
+        &[coin(400_000, NATIVE_DENOM)],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    let response = app
+        .execute_contract(
+            accounts.bidder.clone(),
+            marketplace.clone(),
+            &accept_counter_offer,
+            &[coin(500_000, NATIVE_DENOM)],
+        )
+        .unwrap();
+    let price = find_attrs(response.clone(), "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, counter_price.amount.to_string());
+    let sale_bid_id = find_attrs(response, "wasm-finalize-sale", "bid")
+        .pop()
+        .unwrap();
+    assert_eq!(sale_bid_id, bid_id);
+
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, accounts.bidder.to_string());
+    assert!(query_counter_offer(&app, &marketplace, &bid_id).is_none());
+}
+
+#[test]
+fn try_stale_counter_offer() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+    let bid_id = set_bid(&mut app, &accounts, &marketplace, &collection, token_id);
+
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_counter_offer(&bid_id, coin(1_500_000, NATIVE_DENOM)),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Changing the bid invalidates the counter-offer
+    let update_bid = ExecuteMsg::UpdateBid {
+        id: bid_id.clone(),
+        details: OrderDetails {
+            price: coin(1_100_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &update_bid,
+        &[coin(100_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert!(query_counter_offer(&app, &marketplace, &bid_id).is_none());
+
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::AcceptCounterOffer {
+            bid_id: bid_id.clone(),
+        },
+        &[coin(400_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("bid or ask changed since the counter offer".to_string())
+            .to_string(),
+    );
+
+    // Removing the bid removes the counter-offer
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveBid { id: bid_id.clone() },
+        &[],
+    );
+    assert!(response.is_ok());
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveCounterOffer {
+            bid_id: bid_id.clone(),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!("counter offer not found [{}]", bid_id)).to_string(),
+    );
+}
+
+#[test]
+fn try_accept_counter_offer_of_expired_bid() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+
+    let price = coin(BID_PRICE, NATIVE_DENOM);
+    let expires = app.block_info().time.plus_seconds(100);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: Some(expires),
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price],
+    );
+    let bid_id = find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap();
+
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_counter_offer(&bid_id, coin(1_500_000, NATIVE_DENOM)),
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The counter-offer cannot be accepted once the bid it counters has expired
+    app.update_block(|block| block.time = expires);
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::AcceptCounterOffer {
+            bid_id: bid_id.clone(),
+        },
+        &[coin(500_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("bid is expired".to_string()).to_string(),
+    );
+    assert!(query_counter_offer(&app, &marketplace, &bid_id).is_some());
+}
@@ -0,0 +1,444 @@
+use crate::{
+    constants::MIN_AUCTION_DURATION,
+    msg::{ExecuteMsg, QueryMsg, ReceiveMsg},
+    orders::{Ask, Auction, Bid, OrderDetails},
+    state::Config,
+    tests::{
+        helpers::{
+            marketplace::{approve, mint, mint_and_set_ask},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::{setup_additional_account, TestAccounts},
+            setup_contracts::{setup_cw20, LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, to_json_binary, Addr, Decimal, Uint128};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+use sg_marketplace_common::asset::Asset;
+
+fn query_cw20_balance(app: &App, token: &Addr, address: &Addr) -> Uint128 {
+    app.wrap()
+        .query_wasm_smart::<BalanceResponse>(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap()
+        .balance
+}
+
+#[test]
+fn try_cw20_sales() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                fee_manager,
+            },
+    } = test_context();
+
+    let initial_balance = 100_000_000u128;
+    let token = setup_cw20(&mut app, &creator, vec![(&bidder, initial_balance)]).unwrap();
+    let cw20_denom = Asset::Cw20(token.clone()).denom();
+
+    let config: Config<Addr> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Config {})
+        .unwrap();
+
+    let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
+        collection: collection.to_string(),
+        denom: Asset::Cw20(token.clone()),
+    };
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &update_collection_denom,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let collection_denom: Option<Asset> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionDenom {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(collection_denom, Some(Asset::Cw20(token.clone())));
+
+    let token_id = "1";
+    let price = coin(10_000_000, &cw20_denom);
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    // Bids in the native denom are rejected for a cw20 collection
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: coin(10_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[coin(10_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("invalid denom".to_string()).to_string(),
+    );
+
+    // Accepting the ask with cw20 tokens pays out in cw20 tokens and refunds the excess
+    let ask_id = app
+        .wrap()
+        .query_wasm_smart::<Vec<Ask>>(
+            &marketplace,
+            &QueryMsg::AsksByCollectionDenom {
+                collection: collection.to_string(),
+                denom: cw20_denom.clone(),
+                query_options: None,
+            },
+        )
+        .unwrap()
+        .pop()
+        .unwrap()
+        .id;
+
+    let send = Cw20ExecuteMsg::Send {
+        contract: marketplace.to_string(),
+        amount: Uint128::from(12_000_000u128),
+        msg: to_json_binary(&ReceiveMsg::AcceptAsk {
+            id: ask_id,
+            details: OrderDetails {
+                price: price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        })
+        .unwrap(),
+    };
+    let response = app.execute_contract(bidder.clone(), token.clone(), &send, &[]);
+    assert!(response.is_ok());
+
+    let fee_amount = price.amount.mul_ceil(Decimal::bps(config.protocol_fee_bps));
+    assert_eq!(
+        query_cw20_balance(&app, &token, &bidder),
+        Uint128::from(initial_balance) - price.amount
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &token, &owner),
+        price.amount - fee_amount
+    );
+    assert_eq!(query_cw20_balance(&app, &token, &fee_manager), fee_amount);
+    assert_eq!(
+        query_cw20_balance(&app, &token, &marketplace),
+        Uint128::zero()
+    );
+
+    let owner_of: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of.owner, bidder.to_string());
+}
+
+#[test]
+fn try_cw20_bid() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts {
+            creator, bidder, ..
+        },
+    } = test_context();
+
+    let initial_balance = 100_000_000u128;
+    let token = setup_cw20(&mut app, &creator, vec![(&bidder, initial_balance)]).unwrap();
+    let cw20_denom = Asset::Cw20(token.clone()).denom();
+
+    let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
+        collection: collection.to_string(),
+        denom: Asset::Cw20(token.clone()),
+    };
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &update_collection_denom,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Create bid with cw20 tokens escrows the bid price
+    let price = coin(5_000_000, &cw20_denom);
+    let send = Cw20ExecuteMsg::Send {
+        contract: marketplace.to_string(),
+        amount: price.amount,
+        msg: to_json_binary(&ReceiveMsg::SetBid {
+            collection: collection.to_string(),
+            token_id: "1".to_string(),
+            details: OrderDetails {
+                price: price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        })
+        .unwrap(),
+    };
+    let response = app.execute_contract(bidder.clone(), token.clone(), &send, &[]);
+    let bid_id = find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap();
+
+    let bid = app
+        .wrap()
+        .query_wasm_smart::<Option<Bid>>(&marketplace, &QueryMsg::Bid(bid_id.clone()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(bid.creator, bidder);
+    assert_eq!(bid.details.price, price);
+    assert_eq!(query_cw20_balance(&app, &token, &marketplace), price.amount);
+
+    // Removing the bid refunds the cw20 tokens
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveBid { id: bid_id },
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(
+        query_cw20_balance(&app, &token, &bidder),
+        Uint128::from(initial_balance)
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &token, &marketplace),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn try_cw20_denom_restrictions() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts {
+            creator, bidder, ..
+        },
+    } = test_context();
+
+    let token = setup_cw20(&mut app, &creator, vec![(&bidder, 100_000_000)]).unwrap();
+    let cw20_denom = Asset::Cw20(token.clone()).denom();
+
+    // Native denoms cannot pose as a cw20 token
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdateCollectionDenom {
+            collection: collection.to_string(),
+            denom: Asset::Native(cw20_denom.clone()),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("native denoms cannot use the cw20 prefix".to_string())
+            .to_string(),
+    );
+
+    // Listing fees are only paid in native denoms
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetListingFee {
+            fee: coin(100, &cw20_denom),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("listing fees must be in a native denom".to_string())
+            .to_string(),
+    );
+
+    // Collections priced in cw20 tokens cannot be swept
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdateCollectionDenom {
+            collection: collection.to_string(),
+            denom: Asset::Cw20(token.clone()),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SweepCollection {
+            collection: collection.to_string(),
+            max_items: 1,
+            max_price_per_item: coin(1_000, &cw20_denom),
+            max_total: coin(1_000, &cw20_denom),
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("sweeps are only supported in native denoms".to_string())
+            .to_string(),
+    );
+}
+
+#[test]
+fn try_cw20_auction_bid() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let bidder2 = setup_additional_account(&mut app, "bidder2").unwrap();
+    let initial_balance = 100_000_000u128;
+    let token = setup_cw20(
+        &mut app,
+        &creator,
+        vec![(&bidder, initial_balance), (&bidder2, initial_balance)],
+    )
+    .unwrap();
+    let cw20_denom = Asset::Cw20(token.clone()).denom();
+
+    let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
+        collection: collection.to_string(),
+        denom: Asset::Cw20(token.clone()),
+    };
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &update_collection_denom,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let token_id = "1";
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+
+    let set_auction = ExecuteMsg::SetAuction {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        reserve_price: coin(1_000_000, &cw20_denom),
+        end_time: app.block_info().time.plus_seconds(MIN_AUCTION_DURATION),
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_auction,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let auction_id = find_attrs(response.unwrap(), "wasm-set-auction", "id")
+        .pop()
+        .unwrap();
+
+    // Auction bids can be paid with cw20 tokens, outbid bids are refunded in cw20 tokens
+    for (sender, amount) in [(&bidder, 1_000_000u128), (&bidder2, 1_050_000u128)] {
+        let send = Cw20ExecuteMsg::Send {
+            contract: marketplace.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_json_binary(&ReceiveMsg::BidAuction {
+                id: auction_id.clone(),
+                finder: None,
+            })
+            .unwrap(),
+        };
+        let response = app.execute_contract(sender.clone(), token.clone(), &send, &[]);
+        assert!(response.is_ok());
+    }
+
+    let auction = app
+        .wrap()
+        .query_wasm_smart::<Option<Auction>>(&marketplace, &QueryMsg::Auction(auction_id))
+        .unwrap()
+        .unwrap();
+    let high_bid = auction.high_bid.unwrap();
+    assert_eq!(high_bid.bidder, bidder2);
+    assert_eq!(high_bid.price, coin(1_050_000, &cw20_denom));
+    assert_eq!(
+        query_cw20_balance(&app, &token, &bidder),
+        Uint128::from(initial_balance)
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &token, &marketplace),
+        Uint128::from(1_050_000u128)
+    );
+}
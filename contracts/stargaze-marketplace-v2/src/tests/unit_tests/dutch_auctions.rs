@@ -0,0 +1,617 @@
+use crate::{
+    helpers::generate_id,
+    msg::{CollectionStatsResponse, ExecuteMsg, PriceOffset, QueryMsg},
+    orders::{Ask, DutchAuction, OrderDetails},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint, mint_and_set_ask},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw_multi_test::{App, AppResponse, Executor};
+use sg_index_query::{QueryBound, QueryOptions};
+
+const START_PRICE: u128 = 10_000_000;
+const END_PRICE: u128 = 2_000_000;
+const DURATION: u64 = 100;
+
+fn set_dutch_ask(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    dutch_auction: DutchAuction,
+) -> anyhow::Result<AppResponse> {
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: coin(START_PRICE, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: Some(dutch_auction),
+    };
+    app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    )
+}
+
+fn default_dutch_auction(app: &App) -> DutchAuction {
+    let block_time = app.block_info().time;
+    DutchAuction {
+        end_price: Uint128::new(END_PRICE),
+        start_time: block_time,
+        end_time: block_time.plus_seconds(DURATION),
+    }
+}
+
+#[test]
+fn try_set_dutch_auction_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+
+    // End price must be below the start price
+    let mut dutch_auction = default_dutch_auction(&app);
+    dutch_auction.end_price = Uint128::new(START_PRICE);
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction,
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("end price must be less than the ask price".to_string())
+            .to_string(),
+    );
+
+    // The auction period cannot be empty
+    let mut dutch_auction = default_dutch_auction(&app);
+    dutch_auction.end_time = dutch_auction.start_time;
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction,
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("start_time must be before end_time".to_string()).to_string(),
+    );
+
+    let dutch_auction = default_dutch_auction(&app);
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction.clone(),
+    );
+    let end_price = find_attrs(response.unwrap(), "wasm-set-ask", "end_price")
+        .pop()
+        .unwrap();
+    assert_eq!(end_price, END_PRICE.to_string());
+
+    // Dutch auction asks are indexed by their stored price
+    let asks: Vec<Ask> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::AsksByCollectionDenom {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: Some(QueryOptions {
+                    descending: None,
+                    limit: None,
+                    min: Some(QueryBound::Inclusive(PriceOffset {
+                        id: "".to_string(),
+                        amount: START_PRICE,
+                    })),
+                    max: None,
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(asks.len(), 1);
+    assert_eq!(asks[0].dutch_auction, Some(dutch_auction));
+
+    // Dutch auction asks cannot be updated
+    let update_ask = ExecuteMsg::UpdateAsk {
+        id: asks[0].id.clone(),
+        details: OrderDetails {
+            price: coin(START_PRICE, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &update_ask,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("dutch auction asks cannot be updated".to_string()).to_string(),
+    );
+}
+
+#[test]
+fn try_accept_dutch_auction_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+    let dutch_auction = default_dutch_auction(&app);
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction,
+    );
+    assert!(response.is_ok());
+
+    // Halfway through the auction the price is halfway between start and end price
+    app.update_block(|block| block.time = block.time.plus_seconds(DURATION / 2));
+    let current_price = 6_000_000u128;
+
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: ask_id.clone(),
+        details: OrderDetails {
+            price: coin(current_price - 1, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[coin(current_price - 1, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("ask price is greater than max input".to_string()).to_string(),
+    );
+
+    let max_price = coin(7_000_000, NATIVE_DENOM);
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: ask_id,
+        details: OrderDetails {
+            price: max_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[max_price],
+    );
+    let price = find_attrs(response.unwrap(), "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, current_price.to_string());
+}
+
+#[test]
+fn try_match_dutch_auction_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    for token_id in ["1", "2"] {
+        mint(
+            &mut app,
+            &accounts.creator,
+            &accounts.owner,
+            &collection,
+            token_id,
+        );
+        approve(
+            &mut app,
+            &accounts.owner,
+            &collection,
+            &marketplace,
+            token_id,
+        );
+        let dutch_auction = default_dutch_auction(&app);
+        let response = set_dutch_ask(
+            &mut app,
+            &accounts,
+            &marketplace,
+            &collection,
+            token_id,
+            dutch_auction,
+        );
+        assert!(response.is_ok());
+    }
+
+    // Buying before the price declined enough finds no match
+    let buy_price = coin(4_000_000, NATIVE_DENOM);
+    let buy_specific_nft = ExecuteMsg::BuySpecificNft {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: buy_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &buy_specific_nft,
+        &[buy_price.clone()],
+    );
+    assert_error(response, ContractError::NoMatchFound.to_string());
+
+    // The sale happens at the current price of the ask
+    app.update_block(|block| block.time = block.time.plus_seconds(80));
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &buy_specific_nft,
+        &[buy_price],
+    );
+    let price = find_attrs(response.unwrap(), "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, "3600000");
+
+    // After the end time the price stays at the end price
+    app.update_block(|block| block.time = block.time.plus_seconds(DURATION));
+    let collection_bid_price = coin(2_500_000, NATIVE_DENOM);
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: collection_bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: None,
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[collection_bid_price],
+    );
+    let response = response.unwrap();
+    let token_id = find_attrs(response.clone(), "wasm-finalize-sale", "token_id")
+        .pop()
+        .unwrap();
+    assert_eq!(token_id, "2");
+    let price = find_attrs(response, "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, END_PRICE.to_string());
+}
+
+#[test]
+fn try_match_cheapest_current_price() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    // The dutch auction ask ends below the static asks
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+    let dutch_auction = default_dutch_auction(&app);
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction,
+    );
+    assert!(response.is_ok());
+
+    for (token_id, price) in [("2", 5_000_000u128), ("3", 6_000_000u128)] {
+        mint_and_set_ask(
+            &mut app,
+            &accounts.creator,
+            &accounts.owner,
+            &marketplace,
+            &collection,
+            token_id,
+            OrderDetails {
+                price: coin(price, NATIVE_DENOM),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        );
+    }
+
+    // The floor ask is the cheapest at the current price
+    let response: CollectionStatsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionStats {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    let floor_ask = response.floor_ask.unwrap();
+    assert_eq!(floor_ask.token_id, "2".to_string());
+    assert_eq!(floor_ask.details.price, coin(5_000_000, NATIVE_DENOM));
+
+    // A collection bid matches the static ask, cheaper than the dutch auction ask for now
+    let collection_bid_price = coin(6_000_000, NATIVE_DENOM);
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: collection_bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: None,
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[collection_bid_price],
+    );
+    let response = response.unwrap();
+    let token_id = find_attrs(response.clone(), "wasm-finalize-sale", "token_id")
+        .pop()
+        .unwrap();
+    assert_eq!(token_id, "2");
+    let price = find_attrs(response, "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(price, "5000000");
+
+    // A sweep buys the static ask at 6_000_000 over the dutch auction ask at 6_800_000
+    app.update_block(|block| block.time = block.time.plus_seconds(40));
+    let sweep = ExecuteMsg::SweepCollection {
+        collection: collection.to_string(),
+        max_items: 1,
+        max_price_per_item: coin(7_000_000, NATIVE_DENOM),
+        max_total: coin(7_000_000, NATIVE_DENOM),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &sweep,
+        &[coin(7_000_000, NATIVE_DENOM)],
+    );
+    let response = response.unwrap();
+    let token_ids = find_attrs(response, "wasm-finalize-sale", "token_id");
+    assert_eq!(token_ids, vec!["3".to_string()]);
+}
+
+#[test]
+fn try_match_cheapest_dutch_auction_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    // A dutch auction ask that declined to 5_500_000
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+    let mut dutch_auction = default_dutch_auction(&app);
+    dutch_auction.end_price = Uint128::new(5_000_000);
+    let response = set_dutch_ask(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        token_id,
+        dutch_auction,
+    );
+    assert!(response.is_ok());
+    app.update_block(|block| block.time = block.time.plus_seconds(90));
+
+    // Dutch auction asks that only just started, ending below the declined ask
+    for index in 0..60 {
+        let token_id = (index + 2).to_string();
+        mint(
+            &mut app,
+            &accounts.creator,
+            &accounts.owner,
+            &collection,
+            &token_id,
+        );
+        approve(
+            &mut app,
+            &accounts.owner,
+            &collection,
+            &marketplace,
+            &token_id,
+        );
+        let response = set_dutch_ask(
+            &mut app,
+            &accounts,
+            &marketplace,
+            &collection,
+            &token_id,
+            default_dutch_auction(&app),
+        );
+        assert!(response.is_ok());
+    }
+    mint_and_set_ask(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &marketplace,
+        &collection,
+        "100",
+        OrderDetails {
+            price: coin(8_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    // The declined dutch auction ask is the floor ask
+    let response: CollectionStatsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionStats {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap();
+    let floor_ask = response.floor_ask.unwrap();
+    assert_eq!(floor_ask.token_id, "1".to_string());
+    assert_eq!(floor_ask.details.price, coin(5_500_000, NATIVE_DENOM));
+
+    // A sweep buys the declined dutch auction ask first
+    let sweep = ExecuteMsg::SweepCollection {
+        collection: collection.to_string(),
+        max_items: 1,
+        max_price_per_item: coin(8_000_000, NATIVE_DENOM),
+        max_total: coin(8_000_000, NATIVE_DENOM),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &sweep,
+        &[coin(8_000_000, NATIVE_DENOM)],
+    );
+    let token_ids = find_attrs(response.unwrap(), "wasm-finalize-sale", "token_id");
+    assert_eq!(token_ids, vec!["1".to_string()]);
+}
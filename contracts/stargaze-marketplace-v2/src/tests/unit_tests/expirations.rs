@@ -0,0 +1,488 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{Ask, Bid, Bundle, BundleItem, OrderDetails, Swap, SwapNft, SwapRequest},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint, mint_and_set_ask},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::{setup_additional_account, TestAccounts},
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Decimal};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::Executor;
+use cw_utils::NativeBalance;
+use std::ops::{Add, Sub};
+
+#[test]
+fn try_set_expired_order() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let block_time = app.block_info().time;
+
+    // Create bid with an expiration in the past fails
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time),
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[bid_price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("order expiration must be in the future".to_string())
+            .to_string(),
+    );
+
+    // Create ask with an expiration in the past fails
+    mint(&mut app, &creator, &owner, &collection, "1");
+    approve(&mut app, &owner, &collection, &marketplace, "1");
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: coin(1_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time),
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("order expiration must be in the future".to_string())
+            .to_string(),
+    );
+}
+
+#[test]
+fn try_expired_bid_is_not_matched() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let token_id = "1";
+    let block_time = app.block_info().time;
+
+    // Create bid that expires in 100 seconds
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time.plus_seconds(100)),
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[bid_price.clone()],
+    );
+    let bid_id = find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+    // Accepting an expired bid fails
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+    let accept_bid = ExecuteMsg::AcceptBid {
+        id: bid_id.clone(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(owner.clone(), marketplace.clone(), &accept_bid, &[]);
+    assert_error(
+        response,
+        ContractError::InvalidInput("bid is expired".to_string()).to_string(),
+    );
+
+    // Ask at the expired bid price is created without a match
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id))
+        .unwrap();
+    assert!(ask.is_some());
+
+    let bid = app
+        .wrap()
+        .query_wasm_smart::<Option<Bid>>(&marketplace, &QueryMsg::Bid(bid_id))
+        .unwrap();
+    assert!(bid.is_some());
+}
+
+#[test]
+fn try_remove_expired_orders() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let operator = setup_additional_account(&mut app, "operator").unwrap();
+    let block_time = app.block_info().time;
+
+    let token_id = "1";
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        OrderDetails {
+            price: coin(10_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time.plus_seconds(100)),
+            royalty_tip: None,
+        },
+    );
+
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time.plus_seconds(100)),
+            royalty_tip: None,
+        },
+        quantity: None,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[bid_price.clone()],
+    );
+    assert!(response.is_ok());
+
+    // Removing orders before expiration fails
+    let remove_expired_orders = ExecuteMsg::RemoveExpiredOrders { limit: None };
+    let response = app.execute_contract(
+        operator.clone(),
+        marketplace.clone(),
+        &remove_expired_orders,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("no expired orders found".to_string()).to_string(),
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+    let bidder_balances_before =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    let operator_balances_before =
+        NativeBalance(app.wrap().query_all_balances(operator.clone()).unwrap());
+
+    // Removing expired orders refunds the ask and bid, and rewards the operator
+    let response = app.execute_contract(
+        operator.clone(),
+        marketplace.clone(),
+        &remove_expired_orders,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let owner_of: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of.owner, owner.to_string());
+
+    let reward = coin(
+        bid_price.amount.mul_floor(Decimal::bps(100)).u128(),
+        NATIVE_DENOM,
+    );
+    let bidder_balances_after =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    assert_eq!(
+        bidder_balances_before
+            .add(bid_price.clone())
+            .sub(reward.clone())
+            .unwrap(),
+        bidder_balances_after
+    );
+    let operator_balances_after =
+        NativeBalance(app.wrap().query_all_balances(operator.clone()).unwrap());
+    assert_eq!(
+        operator_balances_before.add(reward),
+        operator_balances_after
+    );
+}
+
+#[test]
+fn try_remove_expired_bundle() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts { creator, owner, .. },
+    } = test_context();
+
+    let operator = setup_additional_account(&mut app, "operator").unwrap();
+    let block_time = app.block_info().time;
+
+    for token_id in ["1", "2"] {
+        mint(&mut app, &creator, &owner, &collection, token_id);
+        approve(&mut app, &owner, &collection, &marketplace, token_id);
+    }
+
+    let set_bundle = ExecuteMsg::SetBundle {
+        items: ["1", "2"]
+            .into_iter()
+            .map(|token_id| BundleItem {
+                collection: collection.to_string(),
+                token_id: token_id.to_string(),
+                royalty_weight: 1,
+            })
+            .collect(),
+        details: OrderDetails {
+            price: coin(10_000_000, NATIVE_DENOM),
+            recipient: None,
+            finder: None,
+            expires: Some(block_time.plus_seconds(100)),
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_bundle,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let bundle_id = find_attrs(response.unwrap(), "wasm-set-bundle", "id")
+        .pop()
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+    // Removing an expired bundle returns the escrowed NFTs to the creator
+    let response = app.execute_contract(
+        operator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveExpiredOrders { limit: None },
+        &[],
+    );
+    let removed = find_attrs(response.unwrap(), "wasm-remove-expired-bundle", "id");
+    assert_eq!(removed, vec![bundle_id.clone()]);
+
+    for token_id in ["1", "2"] {
+        let owner_of: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(owner_of.owner, owner.to_string());
+    }
+
+    let bundle: Option<Bundle> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Bundle(bundle_id))
+        .unwrap();
+    assert!(bundle.is_none());
+}
+
+#[test]
+fn try_remove_expired_swap() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts { creator, owner, .. },
+    } = test_context();
+
+    let operator = setup_additional_account(&mut app, "operator").unwrap();
+    let block_time = app.block_info().time;
+
+    let token_id = "1";
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+
+    // The funds sent with the swap are escrowed as its offered funds
+    let offered_funds = coin(1_000_000, NATIVE_DENOM);
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![SwapNft {
+            collection: collection.to_string(),
+            token_id: token_id.to_string(),
+        }],
+        requested_nfts: vec![SwapRequest::Collection {
+            collection: collection.to_string(),
+        }],
+        requested_funds: None,
+        recipient: None,
+        expires: Some(block_time.plus_seconds(100)),
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_swap,
+        &[offered_funds.clone()],
+    );
+    let swap_id = find_attrs(response.unwrap(), "wasm-set-swap", "id")
+        .pop()
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+
+    let owner_balances_before =
+        NativeBalance(app.wrap().query_all_balances(owner.clone()).unwrap());
+
+    // Removing an expired swap returns the escrowed NFT and funds to the creator
+    let response = app.execute_contract(
+        operator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveExpiredOrders { limit: None },
+        &[],
+    );
+    let removed = find_attrs(response.unwrap(), "wasm-remove-expired-swap", "id");
+    assert_eq!(removed, vec![swap_id.clone()]);
+
+    let owner_of: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of.owner, owner.to_string());
+
+    let owner_balances_after = NativeBalance(app.wrap().query_all_balances(owner.clone()).unwrap());
+    assert_eq!(
+        owner_balances_before.add(offered_funds),
+        owner_balances_after
+    );
+
+    let swap: Option<Swap> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Swap(swap_id))
+        .unwrap();
+    assert!(swap.is_none());
+}
@@ -0,0 +1,203 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::{Config, FeeOverride},
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::{App, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+#[allow(clippy::too_many_arguments)]
+fn buy_nft(
+    app: &mut App,
+    creator: &Addr,
+    owner: &Addr,
+    bidder: &Addr,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    price: u128,
+) -> Uint128 {
+    let price = coin(price, NATIVE_DENOM);
+    mint_and_set_ask(
+        app,
+        creator,
+        owner,
+        marketplace,
+        collection,
+        token_id,
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app
+        .execute_contract(bidder.clone(), marketplace.clone(), &accept_ask, &[price])
+        .unwrap();
+    find_attrs(response, "wasm-finalize-sale", "protocol")
+        .pop()
+        .map_or(Uint128::zero(), |fee| fee.parse().unwrap())
+}
+
+#[test]
+fn try_collection_fee_override() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let config: Config<Addr> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Config {})
+        .unwrap();
+
+    let block_time = app.block_info().time;
+    let fee_override = FeeOverride {
+        protocol_fee_bps: 0,
+        maker_reward_bps: 0,
+        taker_reward_bps: 0,
+        start_time: Some(block_time),
+        end_time: Some(block_time.plus_seconds(100)),
+    };
+    let set_fee_override = ExecuteMsg::SetCollectionFeeOverride {
+        collection: collection.to_string(),
+        fee_override: fee_override.clone(),
+    };
+
+    // Non admin cannot set a fee override
+    let response =
+        app.execute_contract(bidder.clone(), marketplace.clone(), &set_fee_override, &[]);
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    // The override period must be valid
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetCollectionFeeOverride {
+            collection: collection.to_string(),
+            fee_override: FeeOverride {
+                start_time: fee_override.end_time,
+                end_time: fee_override.start_time,
+                ..fee_override.clone()
+            },
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("start_time must be before end_time".to_string()).to_string(),
+    );
+
+    let response =
+        app.execute_contract(creator.clone(), marketplace.clone(), &set_fee_override, &[]);
+    assert!(response.is_ok());
+
+    let stored_fee_override: Option<FeeOverride> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionFeeOverride {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(stored_fee_override, Some(fee_override));
+
+    // No protocol fee is charged while the override is active
+    let price = 1_000_000u128;
+    let protocol_fee = buy_nft(
+        &mut app,
+        &creator,
+        &owner,
+        &bidder,
+        &marketplace,
+        &collection,
+        "1",
+        price,
+    );
+    assert_eq!(protocol_fee, Uint128::zero());
+
+    // The config fees apply again once the override ended
+    app.update_block(|block| block.time = block.time.plus_seconds(200));
+    let protocol_fee = buy_nft(
+        &mut app,
+        &creator,
+        &owner,
+        &bidder,
+        &marketplace,
+        &collection,
+        "2",
+        price,
+    );
+    assert_eq!(
+        protocol_fee,
+        Uint128::from(price).mul_ceil(Decimal::bps(config.protocol_fee_bps))
+    );
+
+    let response = app.execute_contract(
+        creator,
+        marketplace.clone(),
+        &ExecuteMsg::RemoveCollectionFeeOverride {
+            collection: collection.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let stored_fee_override: Option<FeeOverride> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionFeeOverride {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(stored_fee_override, None);
+}
@@ -0,0 +1,303 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, FinderResponse, QueryMsg},
+    orders::OrderDetails,
+    state::Config,
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr};
+use cw_multi_test::{App, AppResponse, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+const PRICE: u128 = 1_000_000;
+
+#[allow(clippy::too_many_arguments)]
+fn sell_nft(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    maker: &Addr,
+    taker: &Addr,
+) -> AppResponse {
+    let price = coin(PRICE, NATIVE_DENOM);
+    mint_and_set_ask(
+        app,
+        &accounts.creator,
+        &accounts.owner,
+        marketplace,
+        collection,
+        token_id,
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: Some(maker.to_string()),
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: Some(taker.to_string()),
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price],
+    )
+    .unwrap()
+}
+
+#[test]
+fn try_registered_finders() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let frontend = Addr::unchecked("frontend");
+    let other_frontend = Addr::unchecked("other_frontend");
+
+    // Non admin cannot register finders
+    let set_finder = ExecuteMsg::SetFinder {
+        finder: frontend.to_string(),
+        reward_bps: 4500,
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &set_finder,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetFinder {
+            finder: frontend.to_string(),
+            reward_bps: 5000,
+        },
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(
+            "reward_bps must be less than 0.5 and less than 1 combined with the default rewards"
+                .to_string(),
+        )
+        .to_string(),
+    );
+
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &set_finder,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Registered finders earn their own reward, unregistered finders the default reward
+    let response = sell_nft(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "1",
+        &frontend,
+        &other_frontend,
+    );
+    let maker_reward = find_attrs(response.clone(), "wasm-finalize-sale", "maker")
+        .pop()
+        .unwrap();
+    assert_eq!(maker_reward, "9000");
+    let taker_reward = find_attrs(response, "wasm-finalize-sale", "taker")
+        .pop()
+        .unwrap();
+    assert_eq!(taker_reward, "2000");
+
+    // Only registered finders accrue volume
+    let finder: Option<FinderResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::Finder {
+                finder: frontend.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        finder,
+        Some(FinderResponse {
+            finder: frontend.clone(),
+            reward_bps: 4500,
+            volume: vec![coin(PRICE, NATIVE_DENOM)],
+        })
+    );
+    let finders: Vec<FinderResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::Finders {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(finders.len(), 1);
+
+    // Unregistered finders earn nothing when only registered finders are rewarded
+    let config: Config<Addr> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Config {})
+        .unwrap();
+    let update_config = ExecuteMsg::UpdateConfig {
+        config: Config {
+            fee_manager: config.fee_manager.to_string(),
+            royalty_registry: config.royalty_registry.to_string(),
+            protocol_fee_bps: config.protocol_fee_bps,
+            max_royalty_fee_bps: config.max_royalty_fee_bps,
+            maker_reward_bps: config.maker_reward_bps,
+            taker_reward_bps: config.taker_reward_bps,
+            default_denom: config.default_denom,
+            removal_reward_bps: config.removal_reward_bps,
+            registered_finders_only: true,
+            payout_modes: config.payout_modes,
+            auction_min_bid_increment_bps: config.auction_min_bid_increment_bps,
+            auction_extension_seconds: config.auction_extension_seconds,
+        },
+    };
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &update_config,
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = sell_nft(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "2",
+        &frontend,
+        &other_frontend,
+    );
+    assert!(!find_attrs(response.clone(), "wasm-finalize-sale", "maker").is_empty());
+    assert!(find_attrs(response, "wasm-finalize-sale", "taker").is_empty());
+
+    // Removed finders are no longer listed
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveFinder {
+            finder: frontend.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let finders: Vec<FinderResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::Finders {
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert!(finders.is_empty());
+}
+
+#[test]
+fn try_registered_finder_on_both_sides() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let frontend = Addr::unchecked("frontend");
+    let response = app.execute_contract(
+        accounts.creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetFinder {
+            finder: frontend.to_string(),
+            reward_bps: 4999,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // The finder earns both rewards without exceeding the protocol fee
+    let response = sell_nft(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "1",
+        &frontend,
+        &frontend,
+    );
+    let maker_reward = find_attrs(response.clone(), "wasm-finalize-sale", "maker")
+        .pop()
+        .unwrap();
+    assert_eq!(maker_reward, "9998");
+    let taker_reward = find_attrs(response.clone(), "wasm-finalize-sale", "taker")
+        .pop()
+        .unwrap();
+    assert_eq!(taker_reward, "9998");
+    let protocol_fee = find_attrs(response, "wasm-finalize-sale", "protocol")
+        .pop()
+        .unwrap();
+    assert_eq!(protocol_fee, "4");
+
+    // The volume of the sale is recorded once
+    let finder: Option<FinderResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::Finder {
+                finder: frontend.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(finder.unwrap().volume, vec![coin(PRICE, NATIVE_DENOM)]);
+}
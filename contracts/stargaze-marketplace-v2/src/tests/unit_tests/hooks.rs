@@ -0,0 +1,183 @@
+use crate::{
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    reply::reply,
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{
+    coin,
+    testing::{mock_dependencies, mock_env},
+    Reply, SubMsgResult,
+};
+use cw_multi_test::Executor;
+use sg_controllers::HooksResponse;
+use sg_marketplace_common::MarketplaceStdError;
+
+#[test]
+fn try_add_remove_hooks() {
+    let TestContext {
+        mut app,
+        contracts: TestContracts { marketplace, .. },
+        accounts: TestAccounts { creator, owner, .. },
+    } = test_context();
+
+    let hook = "hook".to_string();
+
+    // Non admin cannot add hooks
+    let add_ask_hook = ExecuteMsg::AddAskHook { hook: hook.clone() };
+    let response = app.execute_contract(owner.clone(), marketplace.clone(), &add_ask_hook, &[]);
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Admin can add hooks
+    let response = app.execute_contract(creator.clone(), marketplace.clone(), &add_ask_hook, &[]);
+    assert!(response.is_ok());
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::AskHooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![hook.clone()]);
+
+    // Admin can remove hooks
+    let remove_ask_hook = ExecuteMsg::RemoveAskHook { hook: hook.clone() };
+    let response =
+        app.execute_contract(creator.clone(), marketplace.clone(), &remove_ask_hook, &[]);
+    assert!(response.is_ok());
+
+    let hooks: HooksResponse = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::AskHooks {})
+        .unwrap();
+    assert!(hooks.hooks.is_empty());
+}
+
+#[test]
+fn try_failing_hooks_do_not_revert_sale() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    // Hooks that are not contracts fail on every call
+    for add_hook in [
+        ExecuteMsg::AddAskHook {
+            hook: "hook".to_string(),
+        },
+        ExecuteMsg::AddSaleHook {
+            hook: "hook".to_string(),
+        },
+    ] {
+        let response = app.execute_contract(creator.clone(), marketplace.clone(), &add_hook, &[]);
+        assert!(response.is_ok());
+    }
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    let buy_specific_nft = ExecuteMsg::BuySpecificNft {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &buy_specific_nft,
+        &[price.clone()],
+    );
+    assert!(response.is_ok());
+
+    let response = response.unwrap();
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-finalize-sale", "price"),
+        vec![price.amount.to_string()]
+    );
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-ask-hook-failed", "error").len(),
+        1
+    );
+    assert_eq!(
+        find_attrs(response, "wasm-sale-hook-failed", "error").len(),
+        1
+    );
+}
+
+#[test]
+fn try_reply_with_invalid_id() {
+    let mut deps = mock_dependencies();
+
+    // Replies with an unknown id return an error instead of panicking
+    let response = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 99,
+            result: SubMsgResult::Err("hook failed".to_string()),
+        },
+    );
+    assert_eq!(
+        response.unwrap_err(),
+        ContractError::InternalError("invalid reply id [99]".to_string())
+    );
+
+    let response = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: SubMsgResult::Err("hook failed".to_string()),
+        },
+    );
+    assert_eq!(response.unwrap().events[0].ty, "ask-hook-failed");
+}
@@ -5,12 +5,58 @@ mod ask_queries;
 #[cfg(test)]
 mod asks;
 #[cfg(test)]
+mod auctions;
+#[cfg(test)]
+mod batch;
+#[cfg(test)]
 mod bid_queries;
 #[cfg(test)]
 mod bids;
 #[cfg(test)]
+mod bundles;
+#[cfg(test)]
+mod claimable_balances;
+#[cfg(test)]
 mod collection_bid_queries;
 #[cfg(test)]
 mod collection_bids;
 #[cfg(test)]
+mod collection_registry;
+#[cfg(test)]
+mod collection_stats;
+#[cfg(test)]
+mod counter_offers;
+#[cfg(test)]
+mod cw20;
+#[cfg(test)]
+mod dutch_auctions;
+#[cfg(test)]
+mod expirations;
+#[cfg(test)]
+mod fee_overrides;
+#[cfg(test)]
+mod finders;
+#[cfg(test)]
+mod hooks;
+#[cfg(test)]
+mod non_custodial_asks;
+#[cfg(test)]
+mod pause;
+#[cfg(test)]
+mod price_oracle;
+#[cfg(test)]
+mod receive_nft;
+#[cfg(test)]
+mod reserved_asks;
+#[cfg(test)]
+mod royalty_policies;
+#[cfg(test)]
+mod sale_history;
+#[cfg(test)]
 mod sales;
+#[cfg(test)]
+mod swaps;
+#[cfg(test)]
+mod sweeps;
+#[cfg(test)]
+mod trait_bids;
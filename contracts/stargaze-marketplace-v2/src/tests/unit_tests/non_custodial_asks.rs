@@ -0,0 +1,330 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{Ask, OrderDetails},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::{setup_additional_account, TestAccounts},
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+
+fn query_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+#[test]
+fn try_non_custodial_ask_sale() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+
+    // Create non-custodial ask without approving the marketplace fails
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: Some(true),
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("marketplace must be approved to transfer the NFT".to_string())
+            .to_string(),
+    );
+
+    // Create non-custodial ask succeeds, the seller keeps the NFT
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_eq!(query_owner(&app, &collection, token_id), owner.to_string());
+
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id.clone()))
+        .unwrap()
+        .unwrap();
+    assert!(ask.non_custodial);
+
+    // Syncing a valid non-custodial ask fails
+    let sync_ask = ExecuteMsg::SyncAsk { id: ask_id };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &sync_ask, &[]);
+    assert_error(
+        response,
+        ContractError::InvalidInput("ask is unchanged".to_string()).to_string(),
+    );
+
+    // Matching bid transfers the NFT from the seller to the buyer
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[price]);
+    assert!(response.is_ok());
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-finalize-sale", "token_id"),
+        vec![token_id.to_string()]
+    );
+    assert_eq!(query_owner(&app, &collection, token_id), bidder.to_string());
+}
+
+#[test]
+fn try_sync_non_custodial_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let recipient = setup_additional_account(&mut app, "recipient").unwrap();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: Some(true),
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // Seller transfers the NFT outside of the marketplace
+    let transfer_nft = Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: token_id.to_string(),
+    };
+    let response = app.execute_contract(owner.clone(), collection.clone(), &transfer_nft, &[]);
+    assert!(response.is_ok());
+
+    // Bid is not matched with the stale ask
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[price]);
+    assert!(response.is_ok());
+    assert!(find_attrs(response.unwrap(), "wasm-finalize-sale", "token_id").is_empty());
+    assert_eq!(
+        query_owner(&app, &collection, token_id),
+        recipient.to_string()
+    );
+
+    // Anyone can remove the stale ask
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let sync_ask = ExecuteMsg::SyncAsk { id: ask_id.clone() };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &sync_ask, &[]);
+    assert!(response.is_ok());
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-remove-stale-ask", "is_owner"),
+        vec!["false".to_string()]
+    );
+
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id))
+        .unwrap();
+    assert!(ask.is_none());
+}
+
+#[test]
+fn try_non_custodial_ask_with_approve_all() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let price = coin(1_000_000, NATIVE_DENOM);
+    for token_id in ["1", "2"] {
+        mint(&mut app, &creator, &owner, &collection, token_id);
+    }
+
+    // Approving the marketplace as an operator of all NFTs allows non-custodial asks
+    let approve_all = Cw721ExecuteMsg::ApproveAll {
+        operator: marketplace.to_string(),
+        expires: None,
+    };
+    let response = app.execute_contract(owner.clone(), collection.clone(), &approve_all, &[]);
+    assert!(response.is_ok());
+
+    for token_id in ["1", "2"] {
+        let set_ask = ExecuteMsg::SetAsk {
+            collection: collection.to_string(),
+            token_id: token_id.to_string(),
+            details: OrderDetails {
+                price: price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+            non_custodial: Some(true),
+            reserved_for: None,
+            dutch_auction: None,
+        };
+        let response = app.execute_contract(
+            owner.clone(),
+            marketplace.clone(),
+            &set_ask,
+            &[coin(LISTING_FEE, NATIVE_DENOM)],
+        );
+        assert!(response.is_ok());
+    }
+
+    // Syncing an ask covered by the operator approval fails
+    let ask_id = generate_id(vec![collection.as_bytes(), "1".as_bytes()]);
+    let sync_ask = ExecuteMsg::SyncAsk { id: ask_id };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &sync_ask, &[]);
+    assert_error(
+        response,
+        ContractError::InvalidInput("ask is unchanged".to_string()).to_string(),
+    );
+
+    // Matching bid transfers the NFT through the operator approval
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price.clone()],
+    );
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-finalize-sale", "token_id"),
+        vec!["1".to_string()]
+    );
+    assert_eq!(query_owner(&app, &collection, "1"), bidder.to_string());
+
+    // Revoking the operator approval makes the remaining ask stale
+    let revoke_all = Cw721ExecuteMsg::RevokeAll {
+        operator: marketplace.to_string(),
+    };
+    let response = app.execute_contract(owner.clone(), collection.clone(), &revoke_all, &[]);
+    assert!(response.is_ok());
+
+    let ask_id = generate_id(vec![collection.as_bytes(), "2".as_bytes()]);
+    let sync_ask = ExecuteMsg::SyncAsk { id: ask_id };
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &sync_ask, &[]);
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-remove-stale-ask", "has_approval"),
+        vec!["false".to_string()]
+    );
+}
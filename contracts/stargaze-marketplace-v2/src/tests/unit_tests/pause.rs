@@ -0,0 +1,300 @@
+use crate::{
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::PauseState,
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::coin;
+use cw_multi_test::Executor;
+use sg_marketplace_common::MarketplaceStdError;
+
+#[test]
+fn try_pause_marketplace() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price.clone()],
+    );
+    let bid_id = find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap();
+
+    let pause_orders = ExecuteMsg::UpdatePauseState {
+        collection: None,
+        pause_state: PauseState {
+            orders: true,
+            sales: false,
+            withdrawals: false,
+        },
+    };
+
+    // Non admin cannot pause the marketplace
+    let response = app.execute_contract(bidder.clone(), marketplace.clone(), &pause_orders, &[]);
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    let response = app.execute_contract(creator.clone(), marketplace.clone(), &pause_orders, &[]);
+    assert!(response.is_ok());
+
+    let pause_state: PauseState = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::PauseState { collection: None })
+        .unwrap();
+    assert!(pause_state.orders);
+
+    // New orders are rejected while orders are paused
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("orders".to_string()).to_string(),
+    );
+
+    // Buying now creates an order as well and is rejected too
+    let buy_collection_nft = ExecuteMsg::BuyCollectionNft {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &buy_collection_nft,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("orders".to_string()).to_string(),
+    );
+
+    // Orders can still be cancelled and refunded
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveBid { id: bid_id },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Pausing sales blocks buying existing asks
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: None,
+            pause_state: PauseState::default(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        "1",
+        OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    );
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: None,
+            pause_state: PauseState {
+                orders: false,
+                sales: true,
+                withdrawals: false,
+            },
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let buy_specific_nft = ExecuteMsg::BuySpecificNft {
+        collection: collection.to_string(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &buy_specific_nft,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("sales".to_string()).to_string(),
+    );
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: None,
+            pause_state: PauseState::default(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = app.execute_contract(bidder, marketplace, &buy_specific_nft, &[price]);
+    assert!(response.is_ok());
+}
+
+#[test]
+fn try_pause_collection() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts: TestAccounts {
+            creator, bidder, ..
+        },
+    } = test_context();
+
+    let pause_state = PauseState {
+        orders: true,
+        sales: true,
+        withdrawals: false,
+    };
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: Some(collection.to_string()),
+            pause_state: pause_state.clone(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let collection_pause_state: PauseState = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::PauseState {
+                collection: Some(collection.to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(collection_pause_state, pause_state);
+
+    let marketplace_pause_state: PauseState = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::PauseState { collection: None })
+        .unwrap();
+    assert_eq!(marketplace_pause_state, PauseState::default());
+
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let set_collection_bid = ExecuteMsg::SetCollectionBid {
+        collection: collection.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        quantity: None,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_bid,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::Paused("orders".to_string()).to_string(),
+    );
+
+    // Unpausing the collection removes its pause state
+    let response = app.execute_contract(
+        creator,
+        marketplace.clone(),
+        &ExecuteMsg::UpdatePauseState {
+            collection: Some(collection.to_string()),
+            pause_state: PauseState::default(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let response = app.execute_contract(bidder, marketplace, &set_collection_bid, &[price]);
+    assert!(response.is_ok());
+}
@@ -0,0 +1,152 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, PriceOracleResponse, QueryMsg},
+    orders::OrderDetails,
+    tests::{
+        helpers::marketplace::mint_and_set_ask,
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+};
+
+use cosmwasm_std::{coin, Addr, StdResult, Uint128};
+use cw_multi_test::{App, Executor};
+
+fn sell_nft(
+    app: &mut App,
+    accounts: &TestAccounts,
+    marketplace: &Addr,
+    collection: &Addr,
+    token_id: &str,
+    price: u128,
+) {
+    let details = OrderDetails {
+        price: coin(price, NATIVE_DENOM),
+        recipient: None,
+        finder: None,
+        expires: None,
+        royalty_tip: None,
+    };
+    mint_and_set_ask(
+        app,
+        &accounts.creator,
+        &accounts.owner,
+        marketplace,
+        collection,
+        token_id,
+        details.clone(),
+    );
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details: details.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[details.price],
+    );
+    assert!(response.is_ok());
+}
+
+fn query_price_oracle(
+    app: &App,
+    marketplace: &Addr,
+    collection: &Addr,
+    window_seconds: u64,
+) -> StdResult<PriceOracleResponse> {
+    app.wrap().query_wasm_smart(
+        marketplace,
+        &QueryMsg::CollectionPriceOracle {
+            collection: collection.to_string(),
+            denom: NATIVE_DENOM.to_string(),
+            window_seconds,
+        },
+    )
+}
+
+#[test]
+fn try_collection_price_oracle() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    // Window must not be empty
+    let response = query_price_oracle(&app, &marketplace, &collection, 0);
+    assert!(response
+        .unwrap_err()
+        .to_string()
+        .contains("window_seconds must be greater than 0"));
+
+    // Collections without sales have no price
+    let response = query_price_oracle(&app, &marketplace, &collection, 100).unwrap();
+    assert_eq!(
+        response,
+        PriceOracleResponse {
+            twap: Uint128::zero(),
+            samples: 0,
+            last_update: None,
+        }
+    );
+
+    sell_nft(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "1",
+        1_000_000,
+    );
+
+    // A single sale is its own average
+    let response = query_price_oracle(&app, &marketplace, &collection, 100).unwrap();
+    assert_eq!(response.twap, Uint128::new(1_000_000));
+    assert_eq!(response.samples, 1);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    sell_nft(
+        &mut app,
+        &accounts,
+        &marketplace,
+        &collection,
+        "2",
+        3_000_000,
+    );
+    let last_update = app.block_info().time;
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    // Each price is weighted by the time it was the last sale price
+    let response = query_price_oracle(&app, &marketplace, &collection, 200).unwrap();
+    assert_eq!(
+        response,
+        PriceOracleResponse {
+            twap: Uint128::new(2_000_000),
+            samples: 2,
+            last_update: Some(last_update),
+        }
+    );
+
+    let response = query_price_oracle(&app, &marketplace, &collection, 100).unwrap();
+    assert_eq!(response.twap, Uint128::new(3_000_000));
+    assert_eq!(response.samples, 1);
+
+    // Windows before the first sale start at the first sale
+    let response = query_price_oracle(&app, &marketplace, &collection, 10_000).unwrap();
+    assert_eq!(response.twap, Uint128::new(2_000_000));
+    assert_eq!(response.samples, 2);
+
+    // Windows after the last sale use the last sale price
+    let response = query_price_oracle(&app, &marketplace, &collection, 50).unwrap();
+    assert_eq!(response.twap, Uint128::new(3_000_000));
+    assert_eq!(response.samples, 0);
+}
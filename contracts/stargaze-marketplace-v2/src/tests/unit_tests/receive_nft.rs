@@ -0,0 +1,212 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg, ReceiveNftMsg},
+    orders::{Ask, OrderDetails},
+    tests::{
+        helpers::{marketplace::mint, utils::find_attrs},
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, to_json_binary, Addr};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+
+fn query_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+fn send_nft(marketplace: &Addr, token_id: &str, msg: &ReceiveNftMsg) -> Cw721ExecuteMsg {
+    Cw721ExecuteMsg::SendNft {
+        contract: marketplace.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_json_binary(msg).unwrap(),
+    }
+}
+
+#[test]
+fn try_receive_nft_set_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+
+    // Sending the NFT escrows and lists it without a prior approval
+    let set_ask = ReceiveNftMsg::SetAsk {
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        collection.clone(),
+        &send_nft(&marketplace, token_id, &set_ask),
+        &[],
+    );
+    let ask_id = find_attrs(response.unwrap(), "wasm-set-ask", "id")
+        .pop()
+        .unwrap();
+    assert_eq!(
+        ask_id,
+        generate_id(vec![collection.as_bytes(), token_id.as_bytes()])
+    );
+    assert_eq!(query_owner(&app, &collection, token_id), marketplace);
+
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id.clone()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(ask.creator, owner);
+    assert_eq!(ask.collection, collection);
+    assert_eq!(ask.details.price, price);
+    assert!(!ask.non_custodial);
+
+    // The ask can be bought like any other escrowed ask
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: ask_id,
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price.clone()],
+    );
+    assert!(response.is_ok());
+    assert_eq!(query_owner(&app, &collection, token_id), bidder);
+
+    // NFTs cannot be received directly from an account
+    let receive_nft = ExecuteMsg::ReceiveNft(cw721::Cw721ReceiveMsg {
+        sender: owner.to_string(),
+        token_id: token_id.to_string(),
+        msg: to_json_binary(&set_ask).unwrap(),
+    });
+    let response = app.execute_contract(owner, marketplace, &receive_nft, &[]);
+    assert!(response.is_err());
+}
+
+#[test]
+fn try_receive_nft_sell_nft() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+
+    let sell_nft = ReceiveNftMsg::SellNft {
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+
+    // Selling without a matching bid fails and the NFT stays with the owner
+    let response = app.execute_contract(
+        owner.clone(),
+        collection.clone(),
+        &send_nft(&marketplace, token_id, &sell_nft),
+        &[],
+    );
+    assert_eq!(
+        response.unwrap_err().root_cause().to_string(),
+        ContractError::NoMatchFound.to_string()
+    );
+    assert_eq!(query_owner(&app, &collection, token_id), owner);
+
+    // Selling into the top bid transfers the NFT to the bidder
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price.clone()],
+    );
+    assert!(response.is_ok());
+
+    let response = app.execute_contract(
+        owner.clone(),
+        collection.clone(),
+        &send_nft(&marketplace, token_id, &sell_nft),
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(query_owner(&app, &collection, token_id), bidder);
+
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id))
+        .unwrap();
+    assert!(ask.is_none());
+}
@@ -0,0 +1,231 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{Ask, Bid, OrderDetails},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::{setup_additional_account, TestAccounts},
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+};
+
+use cosmwasm_std::coin;
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::Executor;
+use sg_marketplace_common::MarketplaceStdError;
+
+#[test]
+fn try_reserved_ask() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let buyer = setup_additional_account(&mut app, "buyer").unwrap();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: Some(vec![buyer.to_string()]),
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    let ask_id = generate_id(vec![collection.as_bytes(), token_id.as_bytes()]);
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id.clone()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(ask.reserved_for, vec![buyer.clone()]);
+
+    // Bids from buyers the ask is not reserved for are not matched
+    let set_bid = ExecuteMsg::SetBid {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_bid,
+        &[price.clone()],
+    );
+    let bid_id = find_attrs(response.unwrap(), "wasm-set-bid", "id")
+        .pop()
+        .unwrap();
+    let bid = app
+        .wrap()
+        .query_wasm_smart::<Option<Bid>>(&marketplace, &QueryMsg::Bid(bid_id))
+        .unwrap();
+    assert!(bid.is_some());
+
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: ask_id.clone(),
+        details: OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+
+    // Accepting the ask fails for buyers the ask is not reserved for
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price.clone()],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized("sender is not allowed to buy the ask".to_string())
+            .to_string(),
+    );
+
+    // Accepting the ask succeeds for the reserved buyer
+    let response = app.execute_contract(
+        buyer.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price.clone()],
+    );
+    assert!(response.is_ok());
+
+    let owner_of: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of.owner, buyer.to_string());
+
+    let ask = app
+        .wrap()
+        .query_wasm_smart::<Option<Ask>>(&marketplace, &QueryMsg::Ask(ask_id))
+        .unwrap();
+    assert!(ask.is_none());
+}
+
+#[test]
+fn try_reserved_ask_matches_allowed_bid() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let buyer = setup_additional_account(&mut app, "buyer").unwrap();
+
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+
+    // The higher bid comes from a buyer the ask is not reserved for
+    for (creator, amount) in [(&bidder, 2_000_000), (&buyer, 1_500_000)] {
+        let bid_price = coin(amount, NATIVE_DENOM);
+        let set_bid = ExecuteMsg::SetCollectionBid {
+            collection: collection.to_string(),
+            details: OrderDetails {
+                price: bid_price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+            quantity: None,
+        };
+        let response =
+            app.execute_contract(creator.clone(), marketplace.clone(), &set_bid, &[bid_price]);
+        assert!(response.is_ok());
+    }
+
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+        details: OrderDetails {
+            price,
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: Some(vec![buyer.to_string()]),
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    let response = response.unwrap();
+    let nft_recipient = find_attrs(response.clone(), "wasm-finalize-sale", "nft_recipient")
+        .pop()
+        .unwrap();
+    assert_eq!(nft_recipient, buyer.to_string());
+    let sale_price = find_attrs(response, "wasm-finalize-sale", "price")
+        .pop()
+        .unwrap();
+    assert_eq!(sale_price, "1500000");
+}
@@ -0,0 +1,475 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::{CollectionRoyalty, RoyaltyPolicy},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint, mint_and_set_ask},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::{LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use sg_marketplace_common::MarketplaceStdError;
+
+#[test]
+fn try_collection_royalty_policy() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    // Collections without a policy require royalties
+    let royalty_policy: RoyaltyPolicy = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionRoyaltyPolicy {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(royalty_policy, RoyaltyPolicy::Required);
+
+    // Non admin cannot set a royalty policy
+    let set_royalty_policy = ExecuteMsg::SetCollectionRoyaltyPolicy {
+        collection: collection.to_string(),
+        royalty_policy: RoyaltyPolicy::Zero,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_royalty_policy,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of contract can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &set_royalty_policy,
+        &[],
+    );
+    let policy = find_attrs(
+        response.unwrap(),
+        "wasm-set-collection-royalty-policy",
+        "royalty_policy",
+    )
+    .pop()
+    .unwrap();
+    assert_eq!(policy, "zero");
+
+    // The applied policy and the royalty paid are part of the sale event
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let details = OrderDetails {
+        price: price.clone(),
+        recipient: None,
+        finder: None,
+        expires: None,
+        royalty_tip: None,
+    };
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        details.clone(),
+    );
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details,
+    };
+    let response = app
+        .execute_contract(bidder.clone(), marketplace.clone(), &accept_ask, &[price])
+        .unwrap();
+    let policy = find_attrs(response.clone(), "wasm-finalize-sale", "royalty_policy")
+        .pop()
+        .unwrap();
+    assert_eq!(policy, "zero");
+    let royalty_paid = find_attrs(response.clone(), "wasm-finalize-sale", "royalty_paid")
+        .pop()
+        .unwrap();
+    assert_eq!(royalty_paid, "0");
+    assert!(find_attrs(response, "wasm-finalize-sale", "royalty").is_empty());
+
+    // Removing the policy requires royalties again
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveCollectionRoyaltyPolicy {
+            collection: collection.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let royalty_policy: RoyaltyPolicy = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionRoyaltyPolicy {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(royalty_policy, RoyaltyPolicy::Required);
+}
+
+#[test]
+fn try_royalty_tip() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetCollectionRoyaltyPolicy {
+            collection: collection.to_string(),
+            royalty_policy: RoyaltyPolicy::Optional,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Asks cannot carry a royalty tip
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    mint(&mut app, &creator, &owner, &collection, token_id);
+    approve(&mut app, &owner, &collection, &marketplace, token_id);
+    let mut details = OrderDetails {
+        price: price.clone(),
+        recipient: None,
+        finder: None,
+        expires: None,
+        royalty_tip: Some(Uint128::from(10_000u128)),
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetAsk {
+            collection: collection.to_string(),
+            token_id: token_id.to_string(),
+            details: details.clone(),
+            non_custodial: None,
+            reserved_for: None,
+            dutch_auction: None,
+        },
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("royalty tips are only supported on bids".to_string())
+            .to_string(),
+    );
+
+    details.royalty_tip = None;
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetAsk {
+            collection: collection.to_string(),
+            token_id: token_id.to_string(),
+            details: details.clone(),
+            non_custodial: None,
+            reserved_for: None,
+            dutch_auction: None,
+        },
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    // The buyer pays the tip on top of the price
+    details.royalty_tip = Some(Uint128::from(10_000u128));
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details,
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &accept_ask,
+        &[price.clone()],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    // Without a royalty to tip the tip is refunded and the seller proceeds are unchanged
+    let bidder_balance_before = app.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap();
+    let response = app
+        .execute_contract(
+            bidder.clone(),
+            marketplace.clone(),
+            &accept_ask,
+            &[coin(1_010_000, NATIVE_DENOM)],
+        )
+        .unwrap();
+    let royalty_tip = find_attrs(response.clone(), "wasm-finalize-sale", "royalty_tip")
+        .pop()
+        .unwrap();
+    assert_eq!(royalty_tip, "0");
+    let seller = find_attrs(response.clone(), "wasm-finalize-sale", "seller")
+        .pop()
+        .unwrap();
+    let protocol_fee = find_attrs(response, "wasm-finalize-sale", "protocol")
+        .pop()
+        .unwrap();
+    assert_eq!(
+        seller.parse::<u128>().unwrap() + protocol_fee.parse::<u128>().unwrap(),
+        price.amount.u128()
+    );
+    let bidder_balance_after = app.wrap().query_balance(&bidder, NATIVE_DENOM).unwrap();
+    assert_eq!(
+        bidder_balance_before.amount - bidder_balance_after.amount,
+        price.amount
+    );
+}
+
+#[test]
+fn try_collection_royalty() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let artist = Addr::unchecked("artist");
+    let curator = Addr::unchecked("curator");
+    let mut set_collection_royalty = ExecuteMsg::SetCollectionRoyalty {
+        collection: collection.to_string(),
+        collection_royalty: CollectionRoyalty {
+            share: Decimal::percent(5),
+            recipients: vec![(artist.to_string(), 3), (curator.to_string(), 2)],
+        },
+    };
+
+    // Only the admin of the collection can set its royalty
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_collection_royalty,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the admin of the collection can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    // The royalty cannot exceed the max royalty fee
+    if let ExecuteMsg::SetCollectionRoyalty {
+        collection_royalty, ..
+    } = &mut set_collection_royalty
+    {
+        collection_royalty.share = Decimal::percent(20);
+    }
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &set_collection_royalty,
+        &[],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(
+            "royalty share must not exceed the max royalty fee".to_string(),
+        )
+        .to_string(),
+    );
+
+    if let ExecuteMsg::SetCollectionRoyalty {
+        collection_royalty, ..
+    } = &mut set_collection_royalty
+    {
+        collection_royalty.share = Decimal::percent(5);
+    }
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &set_collection_royalty,
+        &[],
+    );
+    assert!(response.is_ok());
+    let collection_royalty: Option<CollectionRoyalty<Addr>> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionRoyalty {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        collection_royalty.unwrap().recipients,
+        vec![(artist.clone(), 3), (curator.clone(), 2)]
+    );
+
+    // The royalty of a sale is split between the recipients by weight
+    let token_id = "1";
+    let price = coin(1_000_000, NATIVE_DENOM);
+    let details = OrderDetails {
+        price: price.clone(),
+        recipient: None,
+        finder: None,
+        expires: None,
+        royalty_tip: None,
+    };
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        details.clone(),
+    );
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details: details.clone(),
+    };
+    let response = app
+        .execute_contract(
+            bidder.clone(),
+            marketplace.clone(),
+            &accept_ask,
+            &[price.clone()],
+        )
+        .unwrap();
+    let royalty_paid = find_attrs(response.clone(), "wasm-finalize-sale", "royalty_paid")
+        .pop()
+        .unwrap();
+    assert_eq!(royalty_paid, "50000");
+    let seller = find_attrs(response, "wasm-finalize-sale", "seller")
+        .pop()
+        .unwrap();
+    assert_eq!(seller, "930000");
+    let artist_balance = app.wrap().query_balance(&artist, NATIVE_DENOM).unwrap();
+    assert_eq!(artist_balance.amount.u128(), 30_000);
+    let curator_balance = app.wrap().query_balance(&curator, NATIVE_DENOM).unwrap();
+    assert_eq!(curator_balance.amount.u128(), 20_000);
+
+    // Under an optional royalty policy only the tip of the buyer is paid, on top of the price
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::SetCollectionRoyaltyPolicy {
+            collection: collection.to_string(),
+            royalty_policy: RoyaltyPolicy::Optional,
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    let token_id = "2";
+    mint_and_set_ask(
+        &mut app,
+        &creator,
+        &owner,
+        &marketplace,
+        &collection,
+        token_id,
+        details.clone(),
+    );
+    let accept_ask = ExecuteMsg::AcceptAsk {
+        id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+        details: OrderDetails {
+            royalty_tip: Some(Uint128::from(10_000u128)),
+            ..details
+        },
+    };
+    let response = app
+        .execute_contract(
+            bidder.clone(),
+            marketplace.clone(),
+            &accept_ask,
+            &[coin(1_010_000, NATIVE_DENOM)],
+        )
+        .unwrap();
+    let royalty_tip = find_attrs(response.clone(), "wasm-finalize-sale", "royalty_tip")
+        .pop()
+        .unwrap();
+    assert_eq!(royalty_tip, "10000");
+    let seller = find_attrs(response, "wasm-finalize-sale", "seller")
+        .pop()
+        .unwrap();
+    assert_eq!(seller, "980000");
+    let artist_balance = app.wrap().query_balance(&artist, NATIVE_DENOM).unwrap();
+    assert_eq!(artist_balance.amount.u128(), 36_000);
+    let curator_balance = app.wrap().query_balance(&curator, NATIVE_DENOM).unwrap();
+    assert_eq!(curator_balance.amount.u128(), 24_000);
+
+    // Removing the royalty falls back to the royalty registry
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::RemoveCollectionRoyalty {
+            collection: collection.to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+    let collection_royalty: Option<CollectionRoyalty<Addr>> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::CollectionRoyalty {
+                collection: collection.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(collection_royalty.is_none());
+}
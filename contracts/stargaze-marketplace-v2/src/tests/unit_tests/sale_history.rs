@@ -0,0 +1,150 @@
+use crate::{
+    helpers::generate_id,
+    msg::{ExecuteMsg, QueryMsg},
+    orders::OrderDetails,
+    state::Sale,
+    tests::{
+        helpers::marketplace::mint_and_set_ask,
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+};
+
+use cosmwasm_std::coin;
+use cw_multi_test::Executor;
+use sg_index_query::{QueryBound, QueryOptions};
+
+#[test]
+fn try_sale_history() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    // No sales have been recorded yet
+    let last_sale: Option<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::LastSale {
+                collection: collection.to_string(),
+                token_id: None,
+            },
+        )
+        .unwrap();
+    assert!(last_sale.is_none());
+
+    for token_id in 1..=3u128 {
+        let price = coin(1_000_000 * token_id, NATIVE_DENOM);
+        let token_id = token_id.to_string();
+        let details = OrderDetails {
+            price: price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        };
+        mint_and_set_ask(
+            &mut app,
+            &creator,
+            &owner,
+            &marketplace,
+            &collection,
+            &token_id,
+            details.clone(),
+        );
+        let accept_ask = ExecuteMsg::AcceptAsk {
+            id: generate_id(vec![collection.as_bytes(), token_id.as_bytes()]),
+            details,
+        };
+        let response =
+            app.execute_contract(bidder.clone(), marketplace.clone(), &accept_ask, &[price]);
+        assert!(response.is_ok());
+    }
+
+    // The last sale of a token is recorded with buyer and seller
+    let block_time = app.block_info().time;
+    let last_sale: Option<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::LastSale {
+                collection: collection.to_string(),
+                token_id: Some("2".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        last_sale,
+        Some(Sale {
+            id: 1,
+            collection: collection.clone(),
+            token_id: "2".to_string(),
+            price: coin(2_000_000, NATIVE_DENOM),
+            buyer: bidder.clone(),
+            seller: owner.clone(),
+            time: block_time,
+        })
+    );
+
+    // The last sale of the collection is the most recent one
+    let last_sale: Option<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::LastSale {
+                collection: collection.to_string(),
+                token_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(last_sale.unwrap().token_id, "3".to_string());
+
+    // Collection sales can be paginated by sale id
+    let sales: Vec<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::SalesByCollection {
+                collection: collection.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(sales.len(), 3);
+    assert_eq!(sales[0].token_id, "1".to_string());
+
+    let sales: Vec<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::SalesByCollection {
+                collection: collection.to_string(),
+                query_options: Some(QueryOptions {
+                    descending: Some(true),
+                    limit: Some(1),
+                    min: None,
+                    max: Some(QueryBound::Exclusive(2)),
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(sales.len(), 1);
+    assert_eq!(sales[0].id, 1);
+    assert_eq!(sales[0].price, coin(2_000_000, NATIVE_DENOM));
+}
@@ -19,6 +19,7 @@ use crate::{
 use cosmwasm_std::{coin, Addr, Decimal};
 use cw_multi_test::Executor;
 use cw_utils::NativeBalance;
+use sg_marketplace_common::asset::Asset;
 use std::ops::{Add, Sub};
 
 #[test]
@@ -65,6 +66,8 @@ fn try_set_ask_sale() {
             price: bid_price_1.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(bidder, marketplace.clone(), &set_bid, &[bid_price_1]);
@@ -79,6 +82,8 @@ fn try_set_ask_sale() {
             price: bid_price_2.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -99,7 +104,12 @@ fn try_set_ask_sale() {
             price: coin(5_000_000, NATIVE_DENOM),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -173,7 +183,12 @@ fn try_accept_ask_sale() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -193,6 +208,8 @@ fn try_accept_ask_sale() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -267,7 +284,12 @@ fn try_set_bid_sale() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -286,6 +308,8 @@ fn try_set_bid_sale() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response =
@@ -354,6 +378,8 @@ fn try_accept_bid_sale() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -376,6 +402,8 @@ fn try_accept_bid_sale() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(owner.clone(), marketplace.clone(), &accept_bid, &[]);
@@ -442,7 +470,12 @@ fn try_set_collection_bid_sale() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -460,7 +493,10 @@ fn try_set_collection_bid_sale() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response =
         app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[bid_price]);
@@ -527,7 +563,10 @@ fn try_accept_collection_bid_sale() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        quantity: None,
     };
     let response = app.execute_contract(
         bidder.clone(),
@@ -551,7 +590,12 @@ fn try_accept_collection_bid_sale() {
             price: coin(20_000_000, NATIVE_DENOM),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -568,6 +612,8 @@ fn try_accept_collection_bid_sale() {
             price: bid_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -643,7 +689,12 @@ fn try_sale_fee_breakdown() {
             price: ask_price.clone(),
             recipient: Some(tokens_recipient.to_string()),
             finder: Some(maker.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -665,6 +716,8 @@ fn try_sale_fee_breakdown() {
             price: ask_price.clone(),
             recipient: Some(nft_recipient.to_string()),
             finder: Some(taker.to_string()),
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -789,7 +842,12 @@ fn try_accept_ask_invalid_inputs() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -809,6 +867,8 @@ fn try_accept_ask_invalid_inputs() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
 
@@ -830,6 +890,8 @@ fn try_accept_ask_invalid_inputs() {
             price: buy_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -878,7 +940,12 @@ fn try_set_ask_with_old_denom() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -891,7 +958,7 @@ fn try_set_ask_with_old_denom() {
     // Update collection denom to ATOM_DENOM
     let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
         collection: collection.to_string(),
-        denom: ATOM_DENOM.to_string(),
+        denom: Asset::Native(ATOM_DENOM.to_string()),
     };
     let response = app.execute_contract(
         creator.clone(),
@@ -910,6 +977,8 @@ fn try_set_ask_with_old_denom() {
             price: atom_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -928,6 +997,8 @@ fn try_set_ask_with_old_denom() {
             price: atom_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[atom_bid]);
@@ -942,6 +1013,8 @@ fn try_set_ask_with_old_denom() {
             price: native_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -960,6 +1033,8 @@ fn try_set_ask_with_old_denom() {
             price: buy_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -1003,7 +1078,12 @@ fn try_match_bid_with_old_denom() {
             price: ask_price.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
     };
     let response = app.execute_contract(
         owner.clone(),
@@ -1016,7 +1096,7 @@ fn try_match_bid_with_old_denom() {
     // Update collection denom to ATOM_DENOM
     let update_collection_denom = ExecuteMsg::UpdateCollectionDenom {
         collection: collection.to_string(),
-        denom: ATOM_DENOM.to_string(),
+        denom: Asset::Native(ATOM_DENOM.to_string()),
     };
     let response = app.execute_contract(
         creator.clone(),
@@ -1035,6 +1115,8 @@ fn try_match_bid_with_old_denom() {
             price: atom_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
@@ -1053,6 +1135,8 @@ fn try_match_bid_with_old_denom() {
             price: atom_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(bidder.clone(), marketplace.clone(), &set_bid, &[atom_bid]);
@@ -1067,6 +1151,8 @@ fn try_match_bid_with_old_denom() {
             price: native_bid.clone(),
             recipient: None,
             finder: None,
+            expires: None,
+            royalty_tip: None,
         },
     };
     let response = app.execute_contract(
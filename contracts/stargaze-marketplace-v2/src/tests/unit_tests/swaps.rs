@@ -0,0 +1,344 @@
+use crate::{
+    msg::{ExecuteMsg, QueryMsg},
+    orders::{Swap, SwapNft, SwapRequest},
+    state::Sale,
+    tests::{
+        helpers::{
+            marketplace::{approve, mint},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::INITIAL_BALANCE,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+fn query_owner(app: &App, collection: &Addr, token_id: &str) -> String {
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            collection,
+            &Cw721QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap()
+        .owner
+}
+
+fn swap_nft(collection: &Addr, token_id: &str) -> SwapNft<String> {
+    SwapNft {
+        collection: collection.to_string(),
+        token_id: token_id.to_string(),
+    }
+}
+
+#[test]
+fn try_set_swap() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    let token_id = "1";
+    mint(
+        &mut app,
+        &accounts.creator,
+        &accounts.owner,
+        &collection,
+        token_id,
+    );
+    approve(
+        &mut app,
+        &accounts.owner,
+        &collection,
+        &marketplace,
+        token_id,
+    );
+
+    // Funds can only be exchanged in one direction
+    let offered_funds = coin(1_000_000, NATIVE_DENOM);
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![swap_nft(&collection, token_id)],
+        requested_nfts: vec![SwapRequest::Collection {
+            collection: collection.to_string(),
+        }],
+        requested_funds: Some(coin(1_000_000, NATIVE_DENOM)),
+        recipient: None,
+        expires: None,
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_swap,
+        &[offered_funds.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("swap cannot both offer and request funds".to_string())
+            .to_string(),
+    );
+
+    // Swaps must request NFTs
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![swap_nft(&collection, token_id)],
+        requested_nfts: vec![],
+        requested_funds: None,
+        recipient: None,
+        expires: None,
+    };
+    let response =
+        app.execute_contract(accounts.owner.clone(), marketplace.clone(), &set_swap, &[]);
+    assert!(response
+        .unwrap_err()
+        .source()
+        .unwrap()
+        .to_string()
+        .starts_with("InvalidInput: number of requested nfts must be between"));
+
+    // The offered NFT and funds are escrowed
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![swap_nft(&collection, token_id)],
+        requested_nfts: vec![SwapRequest::Collection {
+            collection: collection.to_string(),
+        }],
+        requested_funds: None,
+        recipient: None,
+        expires: None,
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &set_swap,
+        &[offered_funds.clone()],
+    );
+    let swap_id = find_attrs(response.unwrap(), "wasm-set-swap", "id")
+        .pop()
+        .unwrap();
+    assert_eq!(
+        query_owner(&app, &collection, token_id),
+        marketplace.to_string()
+    );
+
+    let swaps: Vec<Swap> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::SwapsByCreator {
+                creator: accounts.owner.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].id, swap_id);
+    assert_eq!(swaps[0].offered_funds, Some(offered_funds));
+
+    // Only the creator can remove the swap, escrowed NFTs and funds are returned
+    let remove_swap = ExecuteMsg::RemoveSwap {
+        id: swap_id.clone(),
+    };
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &remove_swap,
+        &[],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of swap can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &remove_swap,
+        &[],
+    );
+    assert!(response.is_ok());
+    assert_eq!(
+        query_owner(&app, &collection, token_id),
+        accounts.owner.to_string()
+    );
+    let balance = app
+        .wrap()
+        .query_balance(&accounts.owner, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(balance.amount.u128(), INITIAL_BALANCE);
+
+    let swap: Option<Swap> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Swap(swap_id))
+        .unwrap();
+    assert!(swap.is_none());
+}
+
+#[test]
+fn try_accept_swap() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts,
+    } = test_context();
+
+    for (owner, token_id) in [(&accounts.owner, "1"), (&accounts.bidder, "2")] {
+        mint(&mut app, &accounts.creator, owner, &collection, token_id);
+        approve(&mut app, owner, &collection, &marketplace, token_id);
+    }
+
+    let requested_funds = coin(1_000_000, NATIVE_DENOM);
+    let set_swap = ExecuteMsg::SetSwap {
+        offered_nfts: vec![swap_nft(&collection, "1")],
+        requested_nfts: vec![SwapRequest::Collection {
+            collection: collection.to_string(),
+        }],
+        requested_funds: Some(requested_funds.clone()),
+        recipient: None,
+        expires: None,
+    };
+    let response =
+        app.execute_contract(accounts.owner.clone(), marketplace.clone(), &set_swap, &[]);
+    let swap_id = find_attrs(response.unwrap(), "wasm-set-swap", "id")
+        .pop()
+        .unwrap();
+
+    // The creator cannot fill their own swap
+    let accept_swap = ExecuteMsg::AcceptSwap {
+        id: swap_id.clone(),
+        nfts: vec![swap_nft(&collection, "2")],
+        recipient: None,
+    };
+    let response = app.execute_contract(
+        accounts.owner.clone(),
+        marketplace.clone(),
+        &accept_swap,
+        &[requested_funds.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput("swap creator cannot accept the swap".to_string()).to_string(),
+    );
+
+    // The given NFTs must match the requested NFTs
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::AcceptSwap {
+            id: swap_id.clone(),
+            nfts: vec![swap_nft(&marketplace, "2")],
+            recipient: None,
+        },
+        &[requested_funds.clone()],
+    );
+    assert_error(
+        response,
+        ContractError::InvalidInput(format!(
+            "nft does not match requested nft [{}/*]",
+            collection
+        ))
+        .to_string(),
+    );
+
+    // The requested funds must be paid
+    let response = app.execute_contract(
+        accounts.bidder.clone(),
+        marketplace.clone(),
+        &accept_swap,
+        &[coin(999_999, NATIVE_DENOM)],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    // Both sides receive the NFTs, the funds are paid to the creator
+    let response = app
+        .execute_contract(
+            accounts.bidder.clone(),
+            marketplace.clone(),
+            &accept_swap,
+            &[requested_funds.clone()],
+        )
+        .unwrap();
+    let seller_payment = find_attrs(response, "wasm-finalize-swap", "seller")
+        .pop()
+        .unwrap();
+    assert_eq!(seller_payment, requested_funds.amount.to_string());
+
+    assert_eq!(
+        query_owner(&app, &collection, "1"),
+        accounts.bidder.to_string()
+    );
+    assert_eq!(
+        query_owner(&app, &collection, "2"),
+        accounts.owner.to_string()
+    );
+    let balance = app
+        .wrap()
+        .query_balance(&accounts.owner, NATIVE_DENOM)
+        .unwrap();
+    assert_eq!(
+        balance.amount.u128(),
+        INITIAL_BALANCE + requested_funds.amount.u128()
+    );
+
+    let swap: Option<Swap> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::Swap(swap_id))
+        .unwrap();
+    assert!(swap.is_none());
+
+    // The NFT paid for with the funds is recorded as sold, the NFT given in return is not
+    let block_time = app.block_info().time;
+    let last_sale: Option<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::LastSale {
+                collection: collection.to_string(),
+                token_id: Some("1".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        last_sale,
+        Some(Sale {
+            id: 0,
+            collection: collection.clone(),
+            token_id: "1".to_string(),
+            price: requested_funds,
+            buyer: accounts.bidder.clone(),
+            seller: accounts.owner.clone(),
+            time: block_time,
+        })
+    );
+    let last_sale: Option<Sale> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::LastSale {
+                collection: collection.to_string(),
+                token_id: Some("2".to_string()),
+            },
+        )
+        .unwrap();
+    assert!(last_sale.is_none());
+}
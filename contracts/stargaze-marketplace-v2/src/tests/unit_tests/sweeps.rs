@@ -0,0 +1,138 @@
+use crate::{
+    msg::ExecuteMsg,
+    orders::OrderDetails,
+    tests::{
+        helpers::{
+            marketplace::mint_and_set_ask,
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::NATIVE_DENOM,
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::coin;
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::Executor;
+use cw_utils::NativeBalance;
+use std::ops::Sub;
+
+#[test]
+fn try_sweep_collection() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    for idx in 1..=5u128 {
+        mint_and_set_ask(
+            &mut app,
+            &creator,
+            &owner,
+            &marketplace,
+            &collection,
+            &idx.to_string(),
+            OrderDetails {
+                price: coin(1_000_000 * idx, NATIVE_DENOM),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        );
+    }
+
+    // Sweep without asks under the max price fails
+    let sweep = ExecuteMsg::SweepCollection {
+        collection: collection.to_string(),
+        max_items: 3,
+        max_price_per_item: coin(500_000, NATIVE_DENOM),
+        max_total: coin(5_000_000, NATIVE_DENOM),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &sweep,
+        &[coin(5_000_000, NATIVE_DENOM)],
+    );
+    assert_error(response, ContractError::NoMatchFound.to_string());
+
+    // Sweep without sufficient funds fails
+    let sweep = ExecuteMsg::SweepCollection {
+        collection: collection.to_string(),
+        max_items: 3,
+        max_price_per_item: coin(4_000_000, NATIVE_DENOM),
+        max_total: coin(5_000_000, NATIVE_DENOM),
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &sweep,
+        &[coin(2_000_000, NATIVE_DENOM)],
+    );
+    assert_error(response, ContractError::InsufficientFunds.to_string());
+
+    // Sweep buys the cheapest asks within the max total and refunds unused funds
+    let bidder_native_balances_before =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &sweep,
+        &[coin(6_000_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    let response = response.unwrap();
+
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-finalize-sale", "token_id"),
+        vec!["1".to_string(), "2".to_string()]
+    );
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-sweep-collection", "num_items"),
+        vec!["2".to_string()]
+    );
+    assert_eq!(
+        find_attrs(response, "wasm-sweep-collection", "total"),
+        vec!["3000000".to_string()]
+    );
+
+    let bidder_native_balances_after =
+        NativeBalance(app.wrap().query_all_balances(bidder.clone()).unwrap());
+    assert_eq!(
+        bidder_native_balances_before
+            .sub(coin(3_000_000, NATIVE_DENOM))
+            .unwrap(),
+        bidder_native_balances_after
+    );
+
+    for token_id in ["1", "2"] {
+        let owner_of: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(owner_of.owner, bidder.to_string());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    msg::{CollectionStatsResponse, ExecuteMsg, QueryMsg},
+    orders::{OrderDetails, TraitBid},
+    tests::{
+        helpers::{
+            marketplace::{approve, mint_with_traits},
+            utils::{assert_error, find_attrs},
+        },
+        setup::{
+            setup_accounts::TestAccounts,
+            setup_contracts::{setup_cw721_with_traits, LISTING_FEE, NATIVE_DENOM},
+            templates::{test_context, TestContext, TestContracts},
+        },
+    },
+    ContractError,
+};
+
+use cosmwasm_std::{coin, Addr};
+use cw721::{Cw721QueryMsg, OwnerOfResponse};
+use cw_multi_test::{App, Executor};
+use sg_marketplace_common::MarketplaceStdError;
+
+fn query_trait_bid_count(app: &App, marketplace: &Addr, collection: &Addr) -> u64 {
+    app.wrap()
+        .query_wasm_smart::<CollectionStatsResponse>(
+            marketplace,
+            &QueryMsg::CollectionStats {
+                collection: collection.to_string(),
+                denom: NATIVE_DENOM.to_string(),
+            },
+        )
+        .unwrap()
+        .stats
+        .trait_bid_count
+}
+
+#[test]
+fn try_set_update_remove_trait_bid() {
+    let TestContext {
+        mut app,
+        contracts:
+            TestContracts {
+                marketplace,
+                collection,
+                ..
+            },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    // Trait bid hooks are informed of trait bids, hooks that are not contracts fail
+    let response = app.execute_contract(
+        creator.clone(),
+        marketplace.clone(),
+        &ExecuteMsg::AddTraitBidHook {
+            hook: "hook".to_string(),
+        },
+        &[],
+    );
+    assert!(response.is_ok());
+
+    // Create trait bid succeeds
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let set_trait_bid = ExecuteMsg::SetTraitBid {
+        collection: collection.to_string(),
+        trait_type: "Background".to_string(),
+        trait_value: "Gold".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &set_trait_bid,
+        &[bid_price.clone()],
+    );
+    let response = response.unwrap();
+    assert_eq!(
+        find_attrs(response.clone(), "wasm-trait-bid-hook-failed", "error").len(),
+        1
+    );
+    let trait_bid_id = find_attrs(response, "wasm-set-trait-bid", "id")
+        .pop()
+        .unwrap();
+    assert_eq!(query_trait_bid_count(&app, &marketplace, &collection), 1);
+
+    let trait_bids: Vec<TraitBid> = app
+        .wrap()
+        .query_wasm_smart(
+            &marketplace,
+            &QueryMsg::TraitBidsByPrice {
+                collection: collection.to_string(),
+                trait_type: "Background".to_string(),
+                trait_value: "Gold".to_string(),
+                denom: NATIVE_DENOM.to_string(),
+                query_options: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(trait_bids.len(), 1);
+    assert_eq!(trait_bids[0].id, trait_bid_id);
+    assert_eq!(trait_bids[0].details.price, bid_price);
+
+    // Non creator updating trait bid fails
+    let new_price = coin(2_000_000, NATIVE_DENOM);
+    let update_trait_bid = ExecuteMsg::UpdateTraitBid {
+        id: trait_bid_id.clone(),
+        details: OrderDetails {
+            price: new_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &update_trait_bid,
+        &[coin(1_000_000, NATIVE_DENOM)],
+    );
+    assert_error(
+        response,
+        MarketplaceStdError::Unauthorized(
+            "only the creator of trait bid can perform this action".to_string(),
+        )
+        .to_string(),
+    );
+
+    // Creator updating trait bid succeeds
+    let response = app.execute_contract(
+        bidder.clone(),
+        marketplace.clone(),
+        &update_trait_bid,
+        &[coin(1_000_000, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+
+    let trait_bid: Option<TraitBid> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::TraitBid(trait_bid_id.clone()))
+        .unwrap();
+    assert_eq!(trait_bid.unwrap().details.price, new_price);
+
+    // Creator removing trait bid succeeds
+    let remove_trait_bid = ExecuteMsg::RemoveTraitBid {
+        id: trait_bid_id.clone(),
+    };
+    let response =
+        app.execute_contract(bidder.clone(), marketplace.clone(), &remove_trait_bid, &[]);
+    assert!(response.is_ok());
+
+    let trait_bid: Option<TraitBid> = app
+        .wrap()
+        .query_wasm_smart(&marketplace, &QueryMsg::TraitBid(trait_bid_id))
+        .unwrap();
+    assert!(trait_bid.is_none());
+    assert_eq!(query_trait_bid_count(&app, &marketplace, &collection), 0);
+}
+
+#[test]
+fn try_trait_bid_sales() {
+    let TestContext {
+        mut app,
+        contracts: TestContracts { marketplace, .. },
+        accounts:
+            TestAccounts {
+                creator,
+                owner,
+                bidder,
+                ..
+            },
+    } = test_context();
+
+    let collection = setup_cw721_with_traits(&mut app, &creator).unwrap();
+    mint_with_traits(
+        &mut app,
+        &creator,
+        &owner,
+        &collection,
+        "1",
+        vec![("Background", "Gold"), ("Eyes", "Laser")],
+    );
+    mint_with_traits(
+        &mut app,
+        &creator,
+        &owner,
+        &collection,
+        "2",
+        vec![("Background", "Blue")],
+    );
+    mint_with_traits(
+        &mut app,
+        &creator,
+        &owner,
+        &collection,
+        "3",
+        vec![("Background", "Gold")],
+    );
+
+    let bid_price = coin(1_000_000, NATIVE_DENOM);
+    let mut trait_bid_ids: Vec<String> = vec![];
+    for _ in 0..2 {
+        let set_trait_bid = ExecuteMsg::SetTraitBid {
+            collection: collection.to_string(),
+            trait_type: "Background".to_string(),
+            trait_value: "Gold".to_string(),
+            details: OrderDetails {
+                price: bid_price.clone(),
+                recipient: None,
+                finder: None,
+                expires: None,
+                royalty_tip: None,
+            },
+        };
+        let response = app.execute_contract(
+            bidder.clone(),
+            marketplace.clone(),
+            &set_trait_bid,
+            &[bid_price.clone()],
+        );
+        trait_bid_ids.push(
+            find_attrs(response.unwrap(), "wasm-set-trait-bid", "id")
+                .pop()
+                .unwrap(),
+        );
+    }
+
+    // Accepting a trait bid with a token that does not have the trait fails
+    approve(&mut app, &owner, &collection, &marketplace, "2");
+    let accept_trait_bid = ExecuteMsg::AcceptTraitBid {
+        id: trait_bid_ids[0].clone(),
+        token_id: "2".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(owner.clone(), marketplace.clone(), &accept_trait_bid, &[]);
+    assert_error(
+        response,
+        ContractError::InvalidInput("token does not have the trait".to_string()).to_string(),
+    );
+
+    // Accepting a trait bid with a token that has the trait succeeds
+    approve(&mut app, &owner, &collection, &marketplace, "1");
+    let accept_trait_bid = ExecuteMsg::AcceptTraitBid {
+        id: trait_bid_ids[0].clone(),
+        token_id: "1".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+    };
+    let response = app.execute_contract(owner.clone(), marketplace.clone(), &accept_trait_bid, &[]);
+    assert!(response.is_ok());
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-finalize-sale", "trait_bid"),
+        vec![trait_bid_ids[0].clone()]
+    );
+
+    // Setting an ask on a token with the trait matches the remaining trait bid
+    approve(&mut app, &owner, &collection, &marketplace, "3");
+    let set_ask = ExecuteMsg::SetAsk {
+        collection: collection.to_string(),
+        token_id: "3".to_string(),
+        details: OrderDetails {
+            price: bid_price.clone(),
+            recipient: None,
+            finder: None,
+            expires: None,
+            royalty_tip: None,
+        },
+        non_custodial: None,
+        reserved_for: None,
+        dutch_auction: None,
+    };
+    let response = app.execute_contract(
+        owner.clone(),
+        marketplace.clone(),
+        &set_ask,
+        &[coin(LISTING_FEE, NATIVE_DENOM)],
+    );
+    assert!(response.is_ok());
+    assert_eq!(
+        find_attrs(response.unwrap(), "wasm-finalize-sale", "trait_bid"),
+        vec![trait_bid_ids[1].clone()]
+    );
+
+    for token_id in ["1", "3"] {
+        let owner_of: OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(owner_of.owner, bidder.to_string());
+    }
+}
@@ -0,0 +1,187 @@
+use cosmwasm_std::{Order, Record, Storage};
+use std::{cmp::Ordering, collections::BTreeMap, iter::Peekable, ops::Bound};
+
+/// A write cache on top of a read only storage. Writes are only applied to the
+/// underlying storage once the transaction is committed, which allows the
+/// changes of a failed batch action to be discarded.
+pub struct StorageTransaction<'a> {
+    storage: &'a dyn Storage,
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a> StorageTransaction<'a> {
+    pub fn new(storage: &'a dyn Storage) -> Self {
+        Self {
+            storage,
+            writes: BTreeMap::new(),
+        }
+    }
+
+    /// Consumes the transaction, returning the pending writes where `None` marks a removal
+    pub fn into_writes(self) -> BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+        self.writes
+    }
+}
+
+/// Applies the writes of a transaction to the underlying storage
+pub fn commit(storage: &mut dyn Storage, writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>) {
+    for (key, value) in writes {
+        match value {
+            Some(value) => storage.set(&key, &value),
+            None => storage.remove(&key),
+        }
+    }
+}
+
+impl<'a> Storage for StorageTransaction<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.writes.get(key) {
+            Some(value) => value.clone(),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Box::new(std::iter::empty());
+            }
+        }
+
+        let bounds = (
+            start.map_or(Bound::Unbounded, Bound::Included),
+            end.map_or(Bound::Unbounded, Bound::Excluded),
+        );
+        let writes = self
+            .writes
+            .range::<[u8], _>(bounds)
+            .map(|(key, value)| (key.clone(), value.clone()));
+        let writes: Box<dyn Iterator<Item = (Vec<u8>, Option<Vec<u8>>)> + 'b> = match order {
+            Order::Ascending => Box::new(writes),
+            Order::Descending => Box::new(writes.rev()),
+        };
+
+        Box::new(MergedRange {
+            base: self.storage.range(start, end, order).peekable(),
+            writes: writes.peekable(),
+            order,
+        })
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.writes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.writes.insert(key.to_vec(), None);
+    }
+}
+
+// Merges the records of the underlying storage with the pending writes,
+// pending writes take precedence over stored records with the same key
+struct MergedRange<B, W>
+where
+    B: Iterator<Item = Record>,
+    W: Iterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
+{
+    base: Peekable<B>,
+    writes: Peekable<W>,
+    order: Order,
+}
+
+impl<B, W> Iterator for MergedRange<B, W>
+where
+    B: Iterator<Item = Record>,
+    W: Iterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
+{
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let take_write = match (self.base.peek(), self.writes.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => false,
+                (None, Some(_)) => true,
+                (Some((base_key, _)), Some((write_key, _))) => {
+                    match base_key.as_slice().cmp(write_key.as_slice()) {
+                        Ordering::Equal => {
+                            self.base.next();
+                            true
+                        }
+                        Ordering::Less => self.order == Order::Descending,
+                        Ordering::Greater => self.order == Order::Ascending,
+                    }
+                }
+            };
+
+            if !take_write {
+                return self.base.next();
+            }
+
+            // Removed keys are skipped
+            if let Some((key, Some(value))) = self.writes.next() {
+                return Some((key, value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    #[test]
+    fn try_transaction_range() {
+        let mut storage = MockStorage::new();
+        storage.set(b"a", b"1");
+        storage.set(b"c", b"3");
+        storage.set(b"e", b"5");
+
+        let mut transaction = StorageTransaction::new(&storage);
+        transaction.set(b"b", b"2");
+        transaction.set(b"c", b"33");
+        transaction.remove(b"e");
+        transaction.set(b"f", b"6");
+
+        let ascending: Vec<Record> = transaction.range(None, None, Order::Ascending).collect();
+        assert_eq!(
+            ascending,
+            vec![
+                (b"a".to_vec(), b"1".to_vec()),
+                (b"b".to_vec(), b"2".to_vec()),
+                (b"c".to_vec(), b"33".to_vec()),
+                (b"f".to_vec(), b"6".to_vec()),
+            ]
+        );
+
+        let descending: Vec<Record> = transaction
+            .range(
+                Some(b"b".as_slice()),
+                Some(b"f".as_slice()),
+                Order::Descending,
+            )
+            .collect();
+        assert_eq!(
+            descending,
+            vec![
+                (b"c".to_vec(), b"33".to_vec()),
+                (b"b".to_vec(), b"2".to_vec()),
+            ]
+        );
+
+        // Nothing is written before the transaction is committed
+        assert_eq!(storage.get(b"b"), None);
+
+        let writes = transaction.into_writes();
+        commit(&mut storage, writes);
+        assert_eq!(storage.get(b"b"), Some(b"2".to_vec()));
+        assert_eq!(storage.get(b"c"), Some(b"33".to_vec()));
+        assert_eq!(storage.get(b"e"), None);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::coin::transfer_coins;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Api, Coin, Response, StdResult, SubMsg, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+/// Prefix of the denom under which amounts of a cw20 token are recorded in a `Coin`
+pub const CW20_DENOM_PREFIX: &str = "cw20:";
+
+/// An asset that NFTs can be priced in, either a native denom or a cw20 token.
+#[cw_serde]
+pub enum Asset {
+    Native(String),
+    Cw20(Addr),
+}
+
+impl Asset {
+    /// The denom used for amounts of the asset, cw20 tokens are prefixed with `cw20:`
+    pub fn denom(&self) -> String {
+        match self {
+            Asset::Native(denom) => denom.to_string(),
+            Asset::Cw20(contract) => format!("{}{}", CW20_DENOM_PREFIX, contract),
+        }
+    }
+
+    /// Invoke `from_denom` to recover the asset of a `Coin` denom, the address of a cw20 token
+    /// is validated.
+    pub fn from_denom(api: &dyn Api, denom: &str) -> StdResult<Self> {
+        match denom.strip_prefix(CW20_DENOM_PREFIX) {
+            Some(contract) => Ok(Asset::Cw20(api.addr_validate(contract)?)),
+            None => Ok(Asset::Native(denom.to_string())),
+        }
+    }
+}
+
+/// Invoke `is_cw20_denom` to check whether a denom is reserved for cw20 tokens, native funds
+/// using the prefix must be rejected so they cannot pose as a cw20 token.
+pub fn is_cw20_denom(denom: &str) -> bool {
+    denom.starts_with(CW20_DENOM_PREFIX)
+}
+
+/// Invoke `transfer_asset` to build a `SubMsg` to transfer a native coin or a cw20 token to an address.
+pub fn transfer_asset(
+    api: &dyn Api,
+    send_coin: Coin,
+    to: &Addr,
+    response: Response,
+) -> StdResult<Response> {
+    transfer_assets(api, vec![send_coin], to, response)
+}
+
+/// Invoke `transfer_assets` to build the `SubMsg`s to transfer a vector of native coins and
+/// cw20 tokens to an address. Native coins are sent in a single bank message.
+pub fn transfer_assets(
+    api: &dyn Api,
+    funds: Vec<Coin>,
+    to: &Addr,
+    mut response: Response,
+) -> StdResult<Response> {
+    let mut native_funds: Vec<Coin> = vec![];
+    for send_coin in funds {
+        match Asset::from_denom(api, &send_coin.denom)? {
+            Asset::Native(_) => native_funds.push(send_coin),
+            Asset::Cw20(contract) => {
+                response = response.add_submessage(SubMsg::new(WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: to.to_string(),
+                        amount: send_coin.amount,
+                    })?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+
+    if !native_funds.is_empty() {
+        response = transfer_coins(native_funds, to, response);
+    }
+
+    Ok(response)
+}
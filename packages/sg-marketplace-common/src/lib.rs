@@ -3,12 +3,15 @@
 //! The `sg-marketplace-common` common package is used to manage functionality shared between marketplace contracts. The package is divided into the following modules:
 //!
 //! - `mod address`: functionality related to the `cosmwasm_std::Addr` type
+//! - `mod asset`: functionality related to native and cw20 assets
 //! - `mod coin`: functionality related to the `cosmwasm_std::Coin` type
 //! - `mod nft`: functionality related to NFT data
 //! - `mod query`: functionality related to querying smart contracts
 //! - `mod sale`: functionality related to NFT sales
+//! - `mod types`: types shared with the royalty registry and sg721 collections
 
 pub mod address;
+pub mod asset;
 pub mod coin;
 pub mod constants;
 mod errors;
@@ -16,6 +19,6 @@ pub mod nft;
 pub mod royalties;
 pub mod sale;
 mod tests;
-mod types;
+pub mod types;
 
 pub use crate::errors::MarketplaceStdError;
@@ -1,4 +1,4 @@
-use crate::types::{CollectionInfoResponse, RoyaltyEntry, Sg721QueryMsg};
+use crate::types::{CollectionInfoResponse, MultiRoyaltyEntry, RoyaltyEntry, Sg721QueryMsg};
 
 use cosmwasm_std::{
     to_json_binary, Addr, Decimal, Deps, QuerierWrapper, Response, StdResult, WasmMsg,
@@ -79,3 +79,18 @@ pub fn fetch_or_set_royalties(
         Err(_) => Ok((None, response)),
     }
 }
+
+/// Invoke `fetch_or_set_multi_royalties` to fetch the royalty entry of a collection as a
+/// multi-recipient entry, to be paid with `NftSaleProcessor::add_split_fee`.
+/// Entries with a single recipient are returned as an entry with one recipient.
+pub fn fetch_or_set_multi_royalties(
+    deps: Deps,
+    royalty_registry: &Addr,
+    collection: &Addr,
+    protocol: Option<&Addr>,
+    response: Response,
+) -> StdResult<(Option<MultiRoyaltyEntry>, Response)> {
+    let (royalty_entry, response) =
+        fetch_or_set_royalties(deps, royalty_registry, collection, protocol, response)?;
+    Ok((royalty_entry.map(MultiRoyaltyEntry::from), response))
+}
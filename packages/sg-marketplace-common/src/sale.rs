@@ -1,7 +1,7 @@
-use crate::coin::transfer_coins;
+use crate::asset::transfer_asset;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{coin, Addr, Coin, Decimal, Response, StdError};
+use cosmwasm_std::{coin, Addr, Api, Coin, Decimal, Response, StdError, StdResult, Uint128};
 
 pub struct Fee {
     pub label: String,
@@ -9,6 +9,22 @@ pub struct Fee {
     pub share: Decimal,
 }
 
+/// A fee divided between several recipients by weight
+pub struct SplitFee {
+    pub label: String,
+    /// The recipients of the fee and their weights within the fee share
+    pub recipients: Vec<(Addr, u64)>,
+    pub share: Decimal,
+}
+
+/// An amount paid on top of the sale price, divided between several recipients by weight
+pub struct Tip {
+    pub label: String,
+    /// The recipients of the tip and their weights within the tip amount
+    pub recipients: Vec<(Addr, u64)>,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct Payment {
     pub label: String,
@@ -20,6 +36,8 @@ pub struct NftSaleProcessor {
     pub sale_coin: Coin,
     pub seller_recipient: Addr,
     pub fees: Vec<Fee>,
+    pub split_fees: Vec<SplitFee>,
+    pub tips: Vec<Tip>,
     pub payments: Vec<Payment>,
 }
 
@@ -29,6 +47,8 @@ impl NftSaleProcessor {
             sale_coin,
             seller_recipient,
             fees: vec![],
+            split_fees: vec![],
+            tips: vec![],
             payments: vec![],
         }
     }
@@ -41,6 +61,26 @@ impl NftSaleProcessor {
         });
     }
 
+    /// Invoke `add_split_fee` to divide a fee between several recipients by weight,
+    /// e.g. the recipients of a `MultiRoyaltyEntry`. Split fees are paid after the other fees.
+    pub fn add_split_fee(&mut self, label: String, share: Decimal, recipients: Vec<(Addr, u64)>) {
+        self.split_fees.push(SplitFee {
+            label,
+            share,
+            recipients,
+        });
+    }
+
+    /// Invoke `add_tip` to pay an amount on top of the sale price to several recipients by
+    /// weight, e.g. a royalty tip of the buyer. Tips do not reduce the seller proceeds.
+    pub fn add_tip(&mut self, label: String, amount: Uint128, recipients: Vec<(Addr, u64)>) {
+        self.tips.push(Tip {
+            label,
+            recipients,
+            amount,
+        });
+    }
+
     pub fn build_payments(&mut self) -> Result<(), StdError> {
         let mut seller_amount = self.sale_coin.amount;
         let denom = self.sale_coin.denom.clone();
@@ -61,6 +101,35 @@ impl NftSaleProcessor {
             });
         }
 
+        for fee in &self.split_fees {
+            if fee.share.is_zero() {
+                continue;
+            }
+
+            let fee_amount = self.sale_coin.amount.mul_ceil(fee.share);
+            seller_amount = seller_amount.checked_sub(fee_amount)?;
+
+            self.payments.extend(split_payments(
+                &fee.label,
+                fee_amount,
+                &fee.recipients,
+                &denom,
+            )?);
+        }
+
+        for tip in &self.tips {
+            if tip.amount.is_zero() {
+                continue;
+            }
+
+            self.payments.extend(split_payments(
+                &tip.label,
+                tip.amount,
+                &tip.recipients,
+                &denom,
+            )?);
+        }
+
         if !seller_amount.is_zero() {
             self.payments.push(Payment {
                 label: "seller".to_string(),
@@ -72,15 +141,64 @@ impl NftSaleProcessor {
         Ok(())
     }
 
+    /// Invoke `find_payment` to get the first payment of a label, split fees pay several
+    /// recipients under the same label, see `find_payments` and `total_payment`.
     pub fn find_payment(&self, label: String) -> Option<&Payment> {
         self.payments.iter().find(|p| p.label == label)
     }
 
-    pub fn payout(&self, mut response: Response) -> Response {
+    pub fn find_payments(&self, label: &str) -> Vec<&Payment> {
+        self.payments.iter().filter(|p| p.label == label).collect()
+    }
+
+    /// Invoke `total_payment` to sum the payments of a label across all of its recipients.
+    pub fn total_payment(&self, label: &str) -> Option<Coin> {
+        let payments = self.find_payments(label);
+        let first = payments.first()?;
+        let amount: Uint128 = payments.iter().map(|p| p.funds.amount).sum();
+        Some(coin(amount.u128(), &first.funds.denom))
+    }
+
+    /// Invoke `payout` to transfer the payments, cw20 denominated payments are sent as cw20 transfers.
+    pub fn payout(&self, api: &dyn Api, mut response: Response) -> StdResult<Response> {
         for payment in self.payments.iter() {
-            response = transfer_coins(vec![payment.funds.clone()], &payment.recipient, response);
+            response = transfer_asset(api, payment.funds.clone(), &payment.recipient, response)?;
         }
 
-        response
+        Ok(response)
     }
 }
+
+/// Divides an amount between recipients by weight. Recipients are paid their weighted part
+/// rounded down, the first recipient receives the remainder.
+fn split_payments(
+    label: &str,
+    amount: Uint128,
+    recipients: &[(Addr, u64)],
+    denom: &str,
+) -> Result<Vec<Payment>, StdError> {
+    let total_weight: u64 = recipients.iter().map(|(_, weight)| weight).sum();
+    if total_weight == 0 {
+        return Err(StdError::generic_err(format!(
+            "payment {} has no weighted recipients",
+            label
+        )));
+    }
+    let split_amounts = recipients
+        .iter()
+        .skip(1)
+        .map(|(_, weight)| amount.multiply_ratio(*weight, total_weight))
+        .collect::<Vec<Uint128>>();
+    let remainder = amount.checked_sub(split_amounts.iter().sum())?;
+
+    Ok(recipients
+        .iter()
+        .zip(std::iter::once(remainder).chain(split_amounts))
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|((recipient, _), amount)| Payment {
+            label: label.to_string(),
+            recipient: recipient.clone(),
+            funds: coin(amount.u128(), denom),
+        })
+        .collect())
+}
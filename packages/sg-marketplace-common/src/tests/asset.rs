@@ -0,0 +1,65 @@
+use crate::{
+    asset::{is_cw20_denom, transfer_asset, transfer_assets, Asset},
+    constants::NATIVE_DENOM,
+};
+
+use cosmwasm_std::{
+    coin, testing::MockApi, to_json_binary, Addr, BankMsg, Response, SubMsg, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+#[test]
+fn try_asset_denom() {
+    let api = MockApi::default();
+
+    let native = Asset::Native(NATIVE_DENOM.to_string());
+    assert_eq!(native.denom(), NATIVE_DENOM);
+    assert_eq!(Asset::from_denom(&api, &native.denom()).unwrap(), native);
+    assert!(!is_cw20_denom(&native.denom()));
+
+    let cw20 = Asset::Cw20(Addr::unchecked("token"));
+    assert_eq!(cw20.denom(), "cw20:token");
+    assert_eq!(Asset::from_denom(&api, &cw20.denom()).unwrap(), cw20);
+    assert!(is_cw20_denom(&cw20.denom()));
+
+    // The address of a cw20 token must be valid
+    assert!(Asset::from_denom(&api, "cw20:TOKEN").is_err());
+}
+
+#[test]
+fn try_transfer_assets() {
+    let api = MockApi::default();
+    let recipient = Addr::unchecked("recipient");
+    let cw20_denom = Asset::Cw20(Addr::unchecked("token")).denom();
+
+    let response =
+        transfer_asset(&api, coin(100, &cw20_denom), &recipient, Response::new()).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: 100u128.into(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let response = transfer_assets(
+        &api,
+        vec![coin(100, &cw20_denom), coin(200, NATIVE_DENOM)],
+        &recipient,
+        Response::new(),
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 2);
+    assert_eq!(
+        response.messages[1],
+        SubMsg::new(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(200, NATIVE_DENOM)],
+        })
+    );
+}
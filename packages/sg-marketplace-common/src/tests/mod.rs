@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod address;
 #[cfg(test)]
+mod asset;
+#[cfg(test)]
 mod coin;
 #[cfg(test)]
 mod nft;
@@ -1,6 +1,10 @@
-use crate::{constants::NATIVE_DENOM, sale::NftSaleProcessor};
+use crate::{
+    constants::NATIVE_DENOM,
+    sale::NftSaleProcessor,
+    types::{MultiRoyaltyEntry, RoyaltyEntry, RoyaltyRecipient},
+};
 
-use cosmwasm_std::{coin, Addr, BankMsg, CosmosMsg, Decimal, Response, Uint128};
+use cosmwasm_std::{coin, testing::MockApi, Addr, BankMsg, CosmosMsg, Decimal, Response, Uint128};
 
 pub struct RoyaltyInfo {
     pub payment_address: Addr,
@@ -29,7 +33,9 @@ fn try_payout_nft_sale_fees() {
         fair_burn.clone(),
     );
     nft_sale_processor.build_payments().unwrap();
-    let response = nft_sale_processor.payout(Response::new());
+    let response = nft_sale_processor
+        .payout(&MockApi::default(), Response::new())
+        .unwrap();
 
     for event in response.events.iter() {
         if event.ty == "wasm-fair-burn-fee" {
@@ -60,7 +66,9 @@ fn try_payout_nft_sale_fees() {
         finder.clone(),
     );
     nft_sale_processor.build_payments().unwrap();
-    let response = nft_sale_processor.payout(Response::new());
+    let response = nft_sale_processor
+        .payout(&MockApi::default(), Response::new())
+        .unwrap();
 
     let assert_fair_burn_payment = coin(200u128, NATIVE_DENOM);
     let assert_finder_payment = coin(300u128, NATIVE_DENOM);
@@ -129,3 +137,125 @@ fn try_payout_nft_sale_fees() {
         _ => panic!("Unexpected message type"),
     }
 }
+
+#[test]
+fn try_split_royalty_payments() {
+    let sale_coin = coin(10_000u128, NATIVE_DENOM);
+    let seller = Addr::unchecked("seller");
+
+    let royalty_entry = MultiRoyaltyEntry {
+        recipients: vec![
+            RoyaltyRecipient {
+                recipient: Addr::unchecked("creator-1"),
+                weight: 1,
+            },
+            RoyaltyRecipient {
+                recipient: Addr::unchecked("creator-2"),
+                weight: 1,
+            },
+            RoyaltyRecipient {
+                recipient: Addr::unchecked("creator-3"),
+                weight: 1,
+            },
+        ],
+        share: Decimal::percent(5u64),
+        updated: None,
+    };
+
+    let mut nft_sale_processor = NftSaleProcessor::new(sale_coin, seller.clone());
+    nft_sale_processor.add_split_fee(
+        "royalty".to_string(),
+        royalty_entry.share,
+        royalty_entry.weighted_recipients(),
+    );
+    nft_sale_processor.build_payments().unwrap();
+
+    // The royalty of 500 is rounded down per recipient, the remainder goes to the first recipient
+    let amounts = nft_sale_processor
+        .payments
+        .iter()
+        .map(|p| (p.recipient.to_string(), p.funds.amount.u128()))
+        .collect::<Vec<(String, u128)>>();
+    assert_eq!(
+        amounts,
+        vec![
+            ("creator-1".to_string(), 168),
+            ("creator-2".to_string(), 166),
+            ("creator-3".to_string(), 166),
+            ("seller".to_string(), 9_500),
+        ]
+    );
+
+    // The royalty label covers the payments to all recipients
+    assert_eq!(nft_sale_processor.find_payments("royalty").len(), 3);
+    assert_eq!(
+        nft_sale_processor.total_payment("royalty"),
+        Some(coin(500u128, NATIVE_DENOM))
+    );
+    assert_eq!(
+        nft_sale_processor
+            .find_payment("royalty".to_string())
+            .unwrap()
+            .recipient,
+        Addr::unchecked("creator-1")
+    );
+
+    // Single recipient entries pay the full royalty to the recipient
+    let royalty_entry: MultiRoyaltyEntry = RoyaltyEntry {
+        recipient: Addr::unchecked("creator"),
+        share: Decimal::percent(10u64),
+        updated: None,
+    }
+    .into();
+    let mut nft_sale_processor = NftSaleProcessor::new(coin(1_001u128, NATIVE_DENOM), seller);
+    nft_sale_processor.add_split_fee(
+        "royalty".to_string(),
+        royalty_entry.share,
+        royalty_entry.weighted_recipients(),
+    );
+    nft_sale_processor.build_payments().unwrap();
+    assert_eq!(
+        nft_sale_processor
+            .find_payment("royalty".to_string())
+            .unwrap()
+            .funds,
+        coin(101u128, NATIVE_DENOM)
+    );
+    assert_eq!(
+        nft_sale_processor
+            .find_payment("seller".to_string())
+            .unwrap()
+            .funds,
+        coin(900u128, NATIVE_DENOM)
+    );
+}
+
+#[test]
+fn try_tip_payments() {
+    let seller = Addr::unchecked("seller");
+    let mut nft_sale_processor = NftSaleProcessor::new(coin(10_000u128, NATIVE_DENOM), seller);
+    nft_sale_processor.add_tip(
+        "royalty".to_string(),
+        Uint128::new(101),
+        vec![
+            (Addr::unchecked("creator-1"), 1),
+            (Addr::unchecked("creator-2"), 1),
+        ],
+    );
+    nft_sale_processor.build_payments().unwrap();
+
+    // The tip is paid on top of the sale price, the seller receives the full price
+    let amounts = nft_sale_processor
+        .payments
+        .iter()
+        .map(|p| (p.recipient.to_string(), p.funds.amount.u128()))
+        .collect::<Vec<(String, u128)>>();
+    assert_eq!(
+        amounts,
+        vec![
+            ("creator-1".to_string(), 51),
+            ("creator-2".to_string(), 50),
+            ("seller".to_string(), 10_000),
+        ]
+    );
+}
@@ -41,6 +41,46 @@ impl From<RegistryRoyaltyEntry> for RoyaltyEntry {
     }
 }
 
+#[cw_serde]
+pub struct RoyaltyRecipient {
+    pub recipient: Addr,
+    /// The part of the royalty share paid to the recipient, relative to the other recipients
+    pub weight: u64,
+}
+
+/// A royalty entry whose share is split between several recipients
+#[cw_serde]
+pub struct MultiRoyaltyEntry {
+    /// The recipients of the royalty payments, the first recipient receives the rounding remainder
+    pub recipients: Vec<RoyaltyRecipient>,
+    /// The percentage of sales that should be paid to all recipients together
+    pub share: Decimal,
+    /// The last time the royalty entry was updated
+    pub updated: Option<Timestamp>,
+}
+
+impl MultiRoyaltyEntry {
+    pub fn weighted_recipients(&self) -> Vec<(Addr, u64)> {
+        self.recipients
+            .iter()
+            .map(|r| (r.recipient.clone(), r.weight))
+            .collect()
+    }
+}
+
+impl From<RoyaltyEntry> for MultiRoyaltyEntry {
+    fn from(e: RoyaltyEntry) -> Self {
+        Self {
+            recipients: vec![RoyaltyRecipient {
+                recipient: e.recipient,
+                weight: 1,
+            }],
+            share: e.share,
+            updated: e.updated,
+        }
+    }
+}
+
 #[cw_serde]
 pub enum Sg721QueryMsg {
     CollectionInfo {},